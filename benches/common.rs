@@ -352,8 +352,8 @@ pub fn wkb_feed_compose(wkb: &mut wkb::WKB, keys: &[(u32, bool)]) -> Option<char
             wkb.release_key(code)
         };
         let result = wkb.compose(code);
-        if let Some(wkb::ComposeState::Finished(c)) = &result {
-            out = Some(*c);
+        if let Some(wkb::ComposeState::Finished(output)) = &result {
+            out = output.char();
         }
     }
     out
@@ -115,8 +115,8 @@ fn run_workload_wkb_xkb() -> u64 {
                 if down {
                     wb.press_key(code);
                     let result = wb.compose(code);
-                    if let Some(wkb::ComposeState::Finished(c)) = &result {
-                        checksum = checksum.wrapping_add(*c as u64);
+                    if let Some(wkb::ComposeState::Finished(output)) = &result {
+                        checksum = checksum.wrapping_add(output.text.chars().map(u64::from).sum());
                     }
                     black_box(result);
                 } else {
@@ -40,8 +40,8 @@ fn main() {
             if down {
                 wb.press_key(code);
                 let result = wb.compose(code);
-                if let Some(wkb::ComposeState::Finished(c)) = &result {
-                    checksum = checksum.wrapping_add(*c as u64);
+                if let Some(wkb::ComposeState::Finished(output)) = &result {
+                    checksum = checksum.wrapping_add(output.text.chars().map(u64::from).sum());
                 }
                 black_box(result);
             } else {
@@ -115,7 +115,7 @@ fn write_layouts_rs(layouts: &[(String, String)]) {
     text.push_str("// in instead of parsing the registry at runtime.\n");
     text.push_str("//\n");
     text.push_str("// Do not edit by hand — regenerate with `cargo run --example gen_layouts`.\n");
-    text.push_str("\n");
+    text.push('\n');
     text.push_str("/// All `(layout, variant)` pairs from the XKB registry. The base layout\n");
     text.push_str("/// has an empty variant.\n");
     text.push_str("pub static LAYOUTS: &[(&str, &str)] = &[\n");
@@ -123,7 +123,7 @@ fn write_layouts_rs(layouts: &[(String, String)]) {
         text.push_str(&format!("    (\"{layout}\", \"{variant}\"),\n"));
     }
     text.push_str("];\n");
-    text.push_str("\n");
+    text.push('\n');
     text.push_str("/// List all available `(layout, variant)` pairs from the static registry.\n");
     text.push_str("/// Returns pairs suitable for `WKB::new_from_names`. The base layout has an\n");
     text.push_str("/// empty variant string.\n");
@@ -133,7 +133,7 @@ fn write_layouts_rs(layouts: &[(String, String)]) {
     text.push_str("        .map(|&(layout, variant)| (layout.to_string(), variant.to_string()))\n");
     text.push_str("        .collect()\n");
     text.push_str("}\n");
-    text.push_str("\n");
+    text.push('\n');
     text.push_str("/// Get all available layout variants for a given locale (test utility).\n");
     text.push_str("pub fn get_all_layouts_for_locale(locale: &str) -> Vec<String> {\n");
     text.push_str("    let mut layouts: Vec<String> = LAYOUTS\n");
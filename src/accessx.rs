@@ -0,0 +1,274 @@
+//! AccessX keyboard accessibility: StickyKeys, SlowKeys and BounceKeys.
+//!
+//! [`AccessX`] filters timestamped key events before they reach a [`WKB`].
+//! Times are in milliseconds, like `wl_keyboard.key` timestamps, and may
+//! wrap around.
+
+use crate::{KeyDirection, KeyResult, StateChanges, WKB};
+
+/// StickyKeys options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StickyKeys {
+    /// Turn StickyKeys off when a modifier is held while another key is
+    /// pressed.
+    pub two_keys_disable: bool,
+}
+
+/// Which AccessX features are on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccessXConfig {
+    /// Every modifier latches when tapped, a second tap locks it and a third
+    /// unlocks it.
+    pub sticky_keys: Option<StickyKeys>,
+    /// Presses are only accepted once the key has been held this long.
+    pub slow_keys_delay: Option<u32>,
+    /// Presses of a key this soon after it was released are ignored.
+    pub bounce_keys_delay: Option<u32>,
+}
+
+/// The AccessX feature that rejected an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessXFilter {
+    SlowKeys,
+    BounceKeys,
+}
+
+/// What [`AccessX`] did with key events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessXEvent {
+    /// The event was passed to [`WKB::key_event`].
+    Accepted {
+        evdev_code: u32,
+        direction: KeyDirection,
+        result: KeyResult,
+    },
+    /// The event was dropped.
+    Rejected {
+        evdev_code: u32,
+        direction: KeyDirection,
+        filter: AccessXFilter,
+    },
+    /// SlowKeys holds the press until `accept_time` unless the key is
+    /// released first; see [`AccessX::timeout`].
+    Delayed { evdev_code: u32, accept_time: u32 },
+    /// Two keys were pressed together, turning StickyKeys off and clearing
+    /// latched and locked modifiers.
+    StickyKeysDisabled { changes: StateChanges },
+}
+
+/// Opt-in AccessX filter for the key events of one [`WKB`].
+#[derive(Debug, Clone, Default)]
+pub struct AccessX {
+    config: AccessXConfig,
+    /// Keys whose press was accepted and not yet released.
+    held: Vec<u32>,
+    /// Presses SlowKeys holds back, with their press time.
+    pending: Vec<(u32, u32)>,
+    /// Keys whose press was rejected, so their release is dropped too.
+    rejected: Vec<u32>,
+    /// Last release time of each key, for BounceKeys.
+    released: Vec<(u32, u32)>,
+}
+
+impl AccessX {
+    /// Start filtering events for `wkb` with `config`.
+    pub fn new(wkb: &mut WKB, config: AccessXConfig) -> Self {
+        let mut accessx = Self::default();
+        accessx.set_config(wkb, config);
+        accessx
+    }
+
+    pub fn config(&self) -> AccessXConfig {
+        self.config
+    }
+
+    /// Change which features are on. Turning StickyKeys off clears latched
+    /// and locked modifiers.
+    pub fn set_config(&mut self, wkb: &mut WKB, config: AccessXConfig) -> StateChanges {
+        let sticky = config.sticky_keys.is_some();
+        let changes = if sticky != self.config.sticky_keys.is_some() {
+            wkb.set_sticky_keys(sticky)
+        } else {
+            StateChanges::default()
+        };
+        if config.slow_keys_delay.is_none() {
+            self.pending.clear();
+        }
+        if config.bounce_keys_delay.is_none() {
+            self.released.clear();
+        }
+        self.config = config;
+        changes
+    }
+
+    /// Filter a key event at `time` and pass it on to `wkb` if accepted.
+    /// Delayed presses that are due are accepted first. Releases of keys
+    /// `wkb` holds from before are always accepted.
+    pub fn key_event(
+        &mut self,
+        wkb: &mut WKB,
+        evdev_code: u32,
+        direction: KeyDirection,
+        time: u32,
+    ) -> Vec<AccessXEvent> {
+        let mut events = self.timeout(wkb, time);
+        match direction {
+            KeyDirection::Down => self.press(wkb, evdev_code, time, &mut events),
+            KeyDirection::Up => self.release(wkb, evdev_code, time, &mut events),
+        }
+        events
+    }
+
+    /// Accept the presses SlowKeys delayed until `time` or earlier. Call it
+    /// at [`Self::next_timeout`].
+    pub fn timeout(&mut self, wkb: &mut WKB, time: u32) -> Vec<AccessXEvent> {
+        let mut events = Vec::new();
+        let Some(delay) = self.config.slow_keys_delay else {
+            return events;
+        };
+        let (due, pending) = self
+            .pending
+            .iter()
+            .partition(|&&(_, pressed)| time.wrapping_sub(pressed) >= delay);
+        self.pending = pending;
+        for (evdev_code, _) in due {
+            self.accept(wkb, evdev_code, KeyDirection::Down, &mut events);
+        }
+        events
+    }
+
+    /// When the next delayed press is due, if any.
+    pub fn next_timeout(&self) -> Option<u32> {
+        let delay = self.config.slow_keys_delay?;
+        self.pending
+            .first()
+            .map(|&(_, pressed)| pressed.wrapping_add(delay))
+    }
+
+    fn press(&mut self, wkb: &mut WKB, evdev_code: u32, time: u32, events: &mut Vec<AccessXEvent>) {
+        let reject = |filter| AccessXEvent::Rejected {
+            evdev_code,
+            direction: KeyDirection::Down,
+            filter,
+        };
+        if let Some(delay) = self.config.bounce_keys_delay {
+            let bounced = self
+                .released
+                .iter()
+                .any(|&(code, released)| code == evdev_code && time.wrapping_sub(released) < delay);
+            if bounced {
+                self.rejected.push(evdev_code);
+                events.push(reject(AccessXFilter::BounceKeys));
+                return;
+            }
+        }
+        if let Some(delay) = self.config.slow_keys_delay {
+            if !self.pending.iter().any(|&(code, _)| code == evdev_code) {
+                self.pending.push((evdev_code, time));
+            }
+            events.push(AccessXEvent::Delayed {
+                evdev_code,
+                accept_time: time.wrapping_add(delay),
+            });
+            return;
+        }
+        self.accept(wkb, evdev_code, KeyDirection::Down, events);
+    }
+
+    fn release(
+        &mut self,
+        wkb: &mut WKB,
+        evdev_code: u32,
+        time: u32,
+        events: &mut Vec<AccessXEvent>,
+    ) {
+        let reject = |filter| AccessXEvent::Rejected {
+            evdev_code,
+            direction: KeyDirection::Up,
+            filter,
+        };
+        if let Some(idx) = self.rejected.iter().position(|&code| code == evdev_code) {
+            self.rejected.swap_remove(idx);
+            events.push(reject(AccessXFilter::BounceKeys));
+            return;
+        }
+        if let Some(idx) = self
+            .pending
+            .iter()
+            .position(|&(code, _)| code == evdev_code)
+        {
+            // Released before `timeout` accepted the press.
+            self.pending.remove(idx);
+            events.push(reject(AccessXFilter::SlowKeys));
+            return;
+        }
+        // Keys pressed before AccessX took over are released as usual.
+        if !self.held.contains(&evdev_code) && !wkb.is_key_down(evdev_code) {
+            return;
+        }
+        self.accept(wkb, evdev_code, KeyDirection::Up, events);
+        if self.config.bounce_keys_delay.is_some() {
+            self.released.retain(|&(code, _)| code != evdev_code);
+            self.released.push((evdev_code, time));
+        }
+    }
+
+    fn accept(
+        &mut self,
+        wkb: &mut WKB,
+        evdev_code: u32,
+        direction: KeyDirection,
+        events: &mut Vec<AccessXEvent>,
+    ) {
+        match direction {
+            KeyDirection::Down => {
+                let two_keys_disable = self
+                    .config
+                    .sticky_keys
+                    .is_some_and(|sticky| sticky.two_keys_disable);
+                if two_keys_disable
+                    && self
+                        .held
+                        .iter()
+                        .any(|&code| code != evdev_code && wkb.is_modifier_key(code))
+                {
+                    let changes = wkb.set_sticky_keys(false);
+                    self.config.sticky_keys = None;
+                    events.push(AccessXEvent::StickyKeysDisabled { changes });
+                }
+                if !self.held.contains(&evdev_code) {
+                    self.held.push(evdev_code);
+                }
+            }
+            KeyDirection::Up => self.held.retain(|&code| code != evdev_code),
+        }
+        let result = wkb.key_event(evdev_code, direction);
+        events.push(AccessXEvent::Accepted {
+            evdev_code,
+            direction,
+            result,
+        });
+    }
+}
+
+impl WKB {
+    /// Switch the modifier keys of every layout between their keymap
+    /// behaviour and StickyKeys latching.
+    pub(crate) fn set_sticky_keys(&mut self, sticky: bool) -> StateChanges {
+        let before_modifiers = self.raw_modifiers();
+        let before_leds = self.leds_state();
+        for (modifiers, layout) in self.modifiers.iter_mut().zip(&self.keymap.layouts) {
+            modifiers.set_sticky(&layout.modifiers, sticky);
+        }
+        StateChanges {
+            is_modifier: false,
+            modifiers_updated: self.raw_modifiers() != before_modifiers,
+            leds_updated: self.leds_state() != before_leds,
+        }
+    }
+
+    /// Whether `evdev_code` is a modifier key on the active layout.
+    pub(crate) fn is_modifier_key(&self, evdev_code: u32) -> bool {
+        self.mods().get(evdev_code).is_some()
+    }
+}
@@ -1,50 +1,327 @@
 use arrayvec::ArrayString;
+use std::sync::Arc;
 
-/// Token fed into the composer: either a regular character or a Compose key press
+/// A key in a compose sequence: a keysym, a character or the Compose key.
+///
+/// Compose files are written in keysyms and layout files in characters. A
+/// key press matches its keysym first, then the character it produces.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum Token {
+pub enum ComposeKey {
+    /// A raw keysym, such as `dead_acute` or `KP_Add`.
+    Keysym(u32),
     Char(char),
     Compose,
 }
 
+/// A key that continues the pending compose sequence, for showing hints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeCompletion {
+    /// The key to press next.
+    pub key: ComposeKey,
+    /// How the key appears in the preedit text.
+    pub preedit: ComposeString,
+    /// What the sequence produces when this key finishes it; `None` when
+    /// more keys follow.
+    pub output: Option<ComposeOutput>,
+}
+
+/// A compose sequence and what it produces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeSequence {
+    pub keys: Vec<ComposeKey>,
+    pub output: ComposeOutput,
+}
+
+/// Placeholder shown in preedit text for the Compose key by default.
+pub const DEFAULT_COMPOSE_PLACEHOLDER: char = '·';
+
+/// A key press fed into compose processing.
+#[cfg(feature = "compose")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ComposeInput {
+    /// The Compose key.
+    Compose,
+    /// Any other key, with its keysym and the character it produces.
+    Key {
+        keysym: Option<u32>,
+        ch: Option<char>,
+    },
+}
+
+/// Characters and keysyms a layout can type, each sorted, for keeping only
+/// the compose sequences it can reach.
+#[cfg(any(feature = "xkb", feature = "compose"))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Reachable {
+    pub(crate) chars: Vec<char>,
+    pub(crate) keysyms: Vec<u32>,
+}
+
+#[cfg(any(feature = "xkb", feature = "compose"))]
+impl Reachable {
+    fn contains(&self, token: &ComposeKey) -> bool {
+        match token {
+            ComposeKey::Compose => true,
+            ComposeKey::Char(ch) => self.chars.binary_search(ch).is_ok(),
+            ComposeKey::Keysym(keysym) => self.keysyms.binary_search(keysym).is_ok(),
+        }
+    }
+}
+
 /// Compose sequence display string — fixed-size, stack-only, no allocation.
 pub type ComposeString = ArrayString<16>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ComposeState {
     Idle(char),
+    /// A sequence is in progress, with its preedit text so far.
     Composing(ComposeString),
-    Finished(char),
+    Finished(ComposeOutput),
     Cancelled,
 }
 
-/// Convert a Token to a u32 key for fast comparison.
-/// Compose = 0, Char(c) = c as u32 (always >= 1 for valid chars).
+/// Result of a completed compose sequence.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ComposeOutput {
+    /// Text the sequence produces; one or more characters.
+    pub text: Arc<str>,
+    /// Raw keysym the sequence produces, when the Compose file names one.
+    pub keysym: Option<u32>,
+}
+
+impl ComposeOutput {
+    /// Output with `text` and no keysym.
+    pub fn new(text: impl Into<Arc<str>>) -> Self {
+        Self {
+            text: text.into(),
+            keysym: None,
+        }
+    }
+
+    /// The output character, when the text is exactly one character.
+    pub fn char(&self) -> Option<char> {
+        let mut chars = self.text.chars();
+        chars.next().filter(|_| chars.next().is_none())
+    }
+}
+
+impl From<char> for ComposeOutput {
+    fn from(ch: char) -> Self {
+        Self::new(ch.encode_utf8(&mut [0; 4]) as &str)
+    }
+}
+
+/// A set of compose sequences that can be installed on a [`crate::WKB`] with
+/// [`crate::WKB::set_compose_table`].
+///
+/// Sequences are written as characters, with [`crate::ir::COMPOSE_KEY_CHAR`]
+/// standing for the Compose key as in layout files, or as keysyms as in
+/// Compose files. A key press continues a sequence through its keysym when
+/// one matches, otherwise through its character. A sequence added later
+/// replaces the output of an identical earlier one.
+#[derive(Debug, Clone)]
+pub struct ComposeTable {
+    pub(crate) entries: Vec<(Vec<ComposeKey>, ComposeOutput)>,
+    pub(crate) reachability_filter: bool,
+}
+
+impl Default for ComposeTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ComposeTable {
+    /// An empty table with the reachability filter enabled.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            reachability_filter: true,
+        }
+    }
+
+    /// Add a sequence. Returns `false`, leaving the table unchanged, when
+    /// `keys` is empty.
+    pub fn insert(&mut self, keys: &[char], output: impl Into<ComposeOutput>) -> bool {
+        if keys.is_empty() {
+            return false;
+        }
+        let tokens = keys
+            .iter()
+            .map(|&ch| match ch {
+                crate::ir::COMPOSE_KEY_CHAR => ComposeKey::Compose,
+                ch => ComposeKey::Char(ch),
+            })
+            .collect();
+        self.entries.push((tokens, output.into()));
+        true
+    }
+
+    /// Add every sequence of `other`, overriding identical sequences here.
+    pub fn merge(&mut self, other: &ComposeTable) {
+        self.entries.extend(other.entries.iter().cloned());
+    }
+
+    /// Number of sequences added, counting overridden ones.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no sequence was added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether installing the table keeps only sequences whose characters
+    /// the layout can type. Enabled by default.
+    pub fn reachability_filter(&self) -> bool {
+        self.reachability_filter
+    }
+
+    /// Enable or disable the reachability filter. Disable it for layouts
+    /// whose keys are remapped or fed characters from elsewhere.
+    pub fn set_reachability_filter(&mut self, enabled: bool) {
+        self.reachability_filter = enabled;
+    }
+
+    /// Build the trie, keeping only sequences typeable from `reachable` when
+    /// given.
+    #[cfg(any(feature = "xkb", feature = "compose"))]
+    pub(crate) fn build(&self, reachable: Option<&Reachable>) -> Composer {
+        let mut composer = Composer::new();
+        for (tokens, output) in &self.entries {
+            let typeable = reachable
+                .is_none_or(|reachable| tokens.iter().all(|token| reachable.contains(token)));
+            if typeable {
+                composer.insert(tokens, output.clone());
+            }
+        }
+        composer
+    }
+}
+
+/// Spacing forms of the combining marks dead keys produce, sorted.
+#[cfg(feature = "compose")]
+#[rustfmt::skip]
+static COMBINING_TO_SPACING: &[(char, char)] = &[
+    ('\u{300}', '`'), ('\u{301}', '´'), ('\u{302}', '^'), ('\u{303}', '~'),
+    ('\u{304}', '¯'), ('\u{306}', '˘'), ('\u{307}', '˙'), ('\u{308}', '¨'),
+    ('\u{30a}', '˚'), ('\u{30b}', '˝'), ('\u{30c}', 'ˇ'), ('\u{313}', '᾿'),
+    ('\u{314}', '῾'), ('\u{327}', '¸'), ('\u{328}', '˛'), ('\u{332}', '_'),
+    ('\u{345}', 'ͺ'), ('\u{3099}', '゛'), ('\u{309a}', '゜'),
+];
+
+/// Push the preedit form of `ch`: dead-key combining marks become their
+/// spacing accent, or sit on a dotted circle when there is none.
+#[cfg(feature = "compose")]
+fn push_preedit(buf: &mut ComposeString, ch: char) {
+    if let Ok(idx) = COMBINING_TO_SPACING.binary_search_by_key(&ch, |&(mark, _)| mark) {
+        let _ = buf.try_push(COMBINING_TO_SPACING[idx].1);
+    } else if is_combining(ch) {
+        let mut glyph = [0; 8];
+        let _ = buf.try_push_str(dotted(ch, &mut glyph));
+    } else {
+        let _ = buf.try_push(ch);
+    }
+}
+
+#[cfg(feature = "compose")]
+fn dotted(mark: char, buf: &mut [u8; 8]) -> &str {
+    let circle = '◌'.encode_utf8(buf).len();
+    let mark = mark.encode_utf8(&mut buf[circle..]).len();
+    std::str::from_utf8(&buf[..circle + mark]).unwrap_or_default()
+}
+
+#[cfg(feature = "compose")]
+fn is_combining(ch: char) -> bool {
+    matches!(ch, '\u{300}'..='\u{36f}' | '\u{1ab0}'..='\u{1aff}' | '\u{1dc0}'..='\u{1dff}' | '\u{20d0}'..='\u{20ff}' | '\u{fe20}'..='\u{fe2f}')
+}
+
+/// The character a keysym produces, when keysyms can be converted.
+pub(crate) fn keysym_char(keysym: u32) -> Option<char> {
+    #[cfg(feature = "xkb")]
+    return crate::Keysym::new(keysym).to_char();
+    #[cfg(not(feature = "xkb"))]
+    {
+        let _ = keysym;
+        None
+    }
+}
+
+/// Tag bit marking keysym keys. Keysyms fit in 29 bits and characters in 21.
+const KEYSYM_KEY: u32 = 1 << 31;
+
+/// Convert a ComposeKey to a u32 key for fast comparison.
+/// Compose = 0, Char(c) = c as u32 (always >= 1 for valid chars),
+/// Keysym(k) = k | [`KEYSYM_KEY`].
 #[inline(always)]
-fn token_key(token: &Token) -> u32 {
+fn token_key(token: &ComposeKey) -> u32 {
     match token {
-        Token::Compose => 0,
-        Token::Char(c) => *c as u32,
+        ComposeKey::Compose => 0,
+        ComposeKey::Char(c) => *c as u32,
+        ComposeKey::Keysym(k) => *k | KEYSYM_KEY,
+    }
+}
+
+/// The token a trie key was made from; the inverse of [`token_key`].
+pub(crate) fn key_token(key: u32) -> ComposeKey {
+    match key {
+        0 => ComposeKey::Compose,
+        key if key & KEYSYM_KEY != 0 => ComposeKey::Keysym(key & !KEYSYM_KEY),
+        key => ComposeKey::Char(char::from_u32(key).unwrap_or('\u{fffd}')),
     }
 }
 
 /// Trie node: children stored as sorted (key, child_index) pairs for binary search.
-/// `emit` is Some(char) if this node is a leaf that produces output.
+/// `emit` is the index into [`Composer::outputs`] if this node is a leaf that
+/// produces output.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub(crate) struct TrieNode {
     pub(crate) children: Vec<(u32, u32)>, // (token_key, node_index), sorted by token_key
-    pub(crate) emit: Option<char>,
+    pub(crate) emit: Option<u32>,
 }
 
+/// Compose trie shared by every state built from the same keymap.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Composer {
     pub(crate) nodes: Vec<TrieNode>,
-    #[serde(skip)]
+    pub(crate) outputs: Vec<ComposeOutput>,
+}
+
+/// Per-state position in a [`Composer`] trie plus the pending display string.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ComposeCursor {
     cur: u32,
-    #[serde(skip)]
     buf: ComposeString,
 }
 
+impl ComposeCursor {
+    pub(crate) fn reset(&mut self) {
+        self.cur = 0;
+        self.buf.clear();
+    }
+
+    /// The preedit text of the pending sequence, if one is in progress.
+    #[cfg(feature = "compose")]
+    pub(crate) fn preedit(&self) -> Option<ComposeString> {
+        (self.cur != 0).then_some(self.buf)
+    }
+
+    /// Trie position and pending display string, for [`crate::StateSnapshot`].
+    pub(crate) fn snapshot(&self) -> (u32, String) {
+        (self.cur, self.buf.to_string())
+    }
+
+    /// Rebuild a cursor from [`Self::snapshot`], if it fits `composer`.
+    pub(crate) fn restore(composer: &Composer, cur: u32, buf: &str) -> Option<Self> {
+        if cur as usize >= composer.nodes.len() {
+            return None;
+        }
+        let buf = ComposeString::from(buf).ok()?;
+        Some(Self { cur, buf })
+    }
+}
+
 impl Default for Composer {
     fn default() -> Self {
         Self::new()
@@ -58,13 +335,13 @@ impl Composer {
                 children: Vec::new(),
                 emit: None,
             }],
-            cur: 0,
-            buf: ComposeString::new(),
+            outputs: Vec::new(),
         }
     }
 
-    /// Insert a sequence of tokens into the trie.
-    pub fn insert(&mut self, tokens: &[Token], out: char) {
+    /// Insert a sequence of tokens into the trie. A later sequence replaces
+    /// the output of an earlier identical one.
+    pub fn insert(&mut self, tokens: &[ComposeKey], out: impl Into<ComposeOutput>) {
         let mut n = 0u32;
         for t in tokens.iter() {
             let key = token_key(t);
@@ -84,54 +361,140 @@ impl Composer {
                 }
             }
         }
-        self.nodes[n as usize].emit = Some(out);
+        let out = out.into();
+        match self.nodes[n as usize].emit {
+            Some(index) => self.outputs[index as usize] = out,
+            None => {
+                self.nodes[n as usize].emit = Some(self.outputs.len() as u32);
+                self.outputs.push(out);
+            }
+        }
     }
 
+    /// The output of the leaf at `node`, if any.
+    pub(crate) fn output(&self, node: u32) -> Option<&ComposeOutput> {
+        let index = self.nodes[node as usize].emit?;
+        Some(&self.outputs[index as usize])
+    }
+
+    /// Advance `cursor` with a key press, showing the Compose key as
+    /// `placeholder` in the preedit text. Keys that produce no character and
+    /// continue no sequence return `None`, leaving the cursor alone.
+    #[cfg(feature = "compose")]
     #[inline]
-    pub(crate) fn feed(&mut self, token: Token) -> ComposeState {
-        let key = token_key(&token);
-        let node = &self.nodes[self.cur as usize];
-
-        match node.children.binary_search_by_key(&key, |&(k, _)| k) {
-            Ok(pos) => {
-                let next = node.children[pos].1;
-                let next_node = &self.nodes[next as usize];
-                if let Some(out) = next_node.emit {
-                    self.cur = 0;
-                    self.buf.clear();
-                    ComposeState::Finished(out)
+    pub(crate) fn feed(
+        &self,
+        cursor: &mut ComposeCursor,
+        input: ComposeInput,
+        placeholder: Option<char>,
+    ) -> Option<ComposeState> {
+        let node = &self.nodes[cursor.cur as usize];
+        let (tokens, display) = match input {
+            ComposeInput::Compose => (
+                [Some(ComposeKey::Compose), None],
+                Some(placeholder.unwrap_or(DEFAULT_COMPOSE_PLACEHOLDER)),
+            ),
+            ComposeInput::Key { keysym, ch } => (
+                [keysym.map(ComposeKey::Keysym), ch.map(ComposeKey::Char)],
+                ch,
+            ),
+        };
+        let next = tokens.iter().flatten().find_map(|token| {
+            let key = token_key(token);
+            let pos = node.children.binary_search_by_key(&key, |&(k, _)| k).ok()?;
+            Some(node.children[pos].1)
+        });
+
+        match next {
+            Some(next) => {
+                if let Some(out) = self.output(next) {
+                    cursor.reset();
+                    Some(ComposeState::Finished(out.clone()))
                 } else {
-                    self.cur = next;
-                    match token {
-                        Token::Char(c) => {
-                            let _ = self.buf.try_push(c);
-                            ComposeState::Composing(self.buf)
-                        }
-                        Token::Compose => {
-                            let mut display = self.buf;
-                            let _ = display.try_push('·');
-                            ComposeState::Composing(display)
+                    cursor.cur = next;
+                    match input {
+                        ComposeInput::Key { ch: Some(c), .. } => push_preedit(&mut cursor.buf, c),
+                        ComposeInput::Key { ch: None, .. } => {}
+                        ComposeInput::Compose => {
+                            if let Some(placeholder) = placeholder {
+                                let _ = cursor.buf.try_push(placeholder);
+                            }
                         }
                     }
+                    Some(ComposeState::Composing(cursor.buf))
                 }
             }
-            Err(_) => {
-                if self.cur == 0 {
-                    match token {
-                        Token::Compose => ComposeState::Idle('·'),
-                        Token::Char(c) => ComposeState::Idle(c),
-                    }
+            None => {
+                let display = display?;
+                if cursor.cur == 0 {
+                    Some(ComposeState::Idle(display))
                 } else {
-                    self.cur = 0;
-                    self.buf.clear();
-                    ComposeState::Cancelled
+                    cursor.reset();
+                    Some(ComposeState::Cancelled)
                 }
             }
         }
     }
 
-    pub(crate) fn reset(&mut self) {
-        self.cur = 0;
-        self.buf.clear();
+    /// Every sequence below the position of `cursor`, in trie order, with
+    /// only the keys still to press.
+    pub(crate) fn sequences(&self, cursor: &ComposeCursor) -> Vec<ComposeSequence> {
+        let mut out = Vec::new();
+        self.collect_sequences(cursor.cur, &mut Vec::new(), &mut out);
+        out
+    }
+
+    fn collect_sequences(
+        &self,
+        node: u32,
+        keys: &mut Vec<ComposeKey>,
+        out: &mut Vec<ComposeSequence>,
+    ) {
+        if let Some(output) = self.output(node) {
+            out.push(ComposeSequence {
+                keys: keys.clone(),
+                output: output.clone(),
+            });
+        }
+        for &(key, child) in &self.nodes[node as usize].children {
+            keys.push(key_token(key));
+            self.collect_sequences(child, keys, out);
+            keys.pop();
+        }
+    }
+
+    /// The keys continuing the sequence at `cursor`, in trie order.
+    #[cfg(feature = "compose")]
+    pub(crate) fn completions(
+        &self,
+        cursor: &ComposeCursor,
+        placeholder: Option<char>,
+    ) -> Vec<ComposeCompletion> {
+        self.nodes[cursor.cur as usize]
+            .children
+            .iter()
+            .map(|&(key, child)| {
+                let key = key_token(key);
+                let mut preedit = ComposeString::new();
+                match key {
+                    ComposeKey::Compose => {
+                        if let Some(placeholder) = placeholder {
+                            let _ = preedit.try_push(placeholder);
+                        }
+                    }
+                    ComposeKey::Char(ch) => push_preedit(&mut preedit, ch),
+                    ComposeKey::Keysym(sym) => {
+                        if let Some(ch) = keysym_char(sym) {
+                            push_preedit(&mut preedit, ch);
+                        }
+                    }
+                }
+                ComposeCompletion {
+                    key,
+                    preedit,
+                    output: self.output(child).cloned(),
+                }
+            })
+            .collect()
     }
 }
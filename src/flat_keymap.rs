@@ -19,6 +19,13 @@ impl FlatMapValue for NamedKey {
     }
 }
 
+#[cfg(feature = "xkb")]
+impl FlatMapValue for crate::Keysym {
+    fn empty() -> Self {
+        crate::Keysym::NO_SYMBOL
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct FlatMap<T: FlatMapValue> {
     pub(crate) data: Vec<T>,
@@ -43,6 +50,36 @@ impl<T: FlatMapValue> FlatMap<T> {
             T::empty()
         }
     }
+
+    /// One slot by reference, or `None` outside the map.
+    #[cfg(feature = "xkb")]
+    pub(crate) fn slot(&self, level: usize, evdev_code: u32) -> Option<&T> {
+        let k = evdev_code as usize;
+        if k < self.num_keys {
+            self.data.get(level * self.num_keys + k)
+        } else {
+            None
+        }
+    }
+
+    /// Set one slot; keycodes outside the map are ignored.
+    pub(crate) fn set(&mut self, level: usize, evdev_code: u32, value: T) {
+        let k = evdev_code as usize;
+        if k < self.num_keys {
+            self.data[level * self.num_keys + k] = value;
+        }
+    }
+
+    /// Every level of one key.
+    pub(crate) fn key_levels(&self, evdev_code: u32) -> [T; MAX_LEVELS] {
+        std::array::from_fn(|level| self.get(level, evdev_code))
+    }
+
+    pub(crate) fn set_key_levels(&mut self, evdev_code: u32, levels: [T; MAX_LEVELS]) {
+        for (level, value) in levels.into_iter().enumerate() {
+            self.set(level, evdev_code, value);
+        }
+    }
 }
 
 /// Flat keymap: `MAX_LEVELS` planes of `num_keys` slots.
@@ -51,3 +88,7 @@ pub(crate) type FlatKeymap = FlatMap<Option<char>>;
 /// Flat named-key map: same shape as `FlatKeymap` but stores [`NamedKey`] values.
 /// [`NamedKey::Unnamed`] means no named key is mapped.
 pub(crate) type FlatNamedKeyMap = FlatMap<NamedKey>;
+/// Flat keysym map: same shape as `FlatKeymap`, storing the keysym selected
+/// by each level without lock transformations.
+#[cfg(feature = "xkb")]
+pub(crate) type FlatKeysymMap = FlatMap<crate::Keysym>;
@@ -1,6 +1,8 @@
 use crate::{KeyBitSet, KeyDirection};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
 pub enum GroupChange {
     Absolute(u8),
     Relative(i8),
@@ -90,7 +92,66 @@ impl Groups {
         self.locked = layout as i32;
         self.latched_action = None;
         self.active.clear();
+        true
+    }
+
+    /// Drop a pending group latch, returning the effective layout.
+    pub(crate) fn unlatch(&mut self, layouts: usize) -> usize {
+        self.clear_latch();
+        self.effective(layouts)
+    }
+
+    /// Whether `code` is held down.
+    pub(crate) fn is_pressed(&self, code: u32) -> bool {
+        self.pressed.contains(code)
+    }
+
+    /// The keys held down, in ascending order.
+    pub(crate) fn pressed(&self) -> impl Iterator<Item = u32> + '_ {
+        self.pressed.iter()
+    }
+
+    /// Replace the group key definitions, keeping the current group.
+    pub(crate) fn set_entries(&mut self, entries: Vec<Group>) {
+        self.group_keys = KeyBitSet::default();
+        for &key in entries.iter().flat_map(|group| &group.keys) {
+            self.group_keys.insert(key);
+        }
+        self.entries = entries;
+        self.active.clear();
+    }
+
+    /// Pressed keys and group state, for [`crate::StateSnapshot`].
+    pub(crate) fn snapshot(&self) -> GroupsSnapshot {
+        GroupsSnapshot {
+            pressed: self.pressed.iter().collect(),
+            active: self.active.clone(),
+            latched_action: self.latched_action,
+            base: self.base,
+            latched: self.latched,
+            locked: self.locked,
+        }
+    }
+
+    /// Install a [`Self::snapshot`]. Returns `false`, changing nothing, if an
+    /// active chord is not one of the group key definitions.
+    pub(crate) fn restore(&mut self, snapshot: &GroupsSnapshot) -> bool {
+        if snapshot
+            .active
+            .iter()
+            .any(|(idx, _)| *idx >= self.entries.len())
+        {
+            return false;
+        }
         self.pressed = KeyBitSet::default();
+        for &key in &snapshot.pressed {
+            self.pressed.insert(key);
+        }
+        self.active = snapshot.active.clone();
+        self.latched_action = snapshot.latched_action;
+        self.base = snapshot.base;
+        self.latched = snapshot.latched;
+        self.locked = snapshot.locked;
         true
     }
 
@@ -248,6 +309,18 @@ impl Groups {
     }
 }
 
+/// Pressed keys and group state of a [`Groups`]. Active chords are indices
+/// into its group key definitions.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct GroupsSnapshot {
+    pressed: Vec<u32>,
+    active: Vec<(usize, bool)>,
+    latched_action: Option<GroupChange>,
+    base: i32,
+    latched: i32,
+    locked: i32,
+}
+
 #[inline]
 fn release_action(action: GroupKind) -> bool {
     matches!(action, GroupKind::Tap(_) | GroupKind::LockOnRelease(_))
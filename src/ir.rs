@@ -15,12 +15,16 @@ use std::fmt::Write as _;
 
 use serde::{Deserialize, Serialize};
 
-use crate::composer::{Composer, Token};
+use crate::composer::{keysym_char, ComposeCursor, ComposeKey, ComposeOutput, Composer};
 use crate::flat_keymap::{FlatMap, FlatMapValue, MAX_LEVELS};
-use crate::modifiers::{ModKind, ModType, Modifier, Modifiers, StateModifier};
+use crate::key_types::{synthesize_key_type, KeyTypes};
+use crate::modifiers::{ModFlags, ModKind, ModType, Modifier, Modifiers, StateModifier};
 use crate::named_keys::NamedKey;
 use crate::{FlatKeymap, FlatNamedKeyMap, KBLayout, KeyBitSet};
 
+mod builder;
+pub use builder::LayoutBuilder;
+
 /// Current version of the layout file schema. Files with a different version
 /// are rejected by [`LayoutFile::validate`].
 pub const FORMAT_VERSION: u32 = 1;
@@ -44,6 +48,58 @@ pub type NamedSection = BTreeMap<u8, BTreeMap<u32, NamedKey>>;
 /// Modifier bindings: `(keycode, [(level, action)])`.
 pub type ModifierList = Vec<(u32, Vec<(u8, ModAction)>)>;
 
+/// Compose sequences: `(keys, output)`.
+pub type ComposeList = Vec<(Vec<char>, ComposeValue)>;
+
+/// Output of a compose sequence: a character `'æ'`, a string `"ǹ"`, or a
+/// string with the raw keysym the sequence produces, `("æ", 230)`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComposeValue {
+    Char(char),
+    Text(String),
+    WithKeysym(String, u32),
+}
+
+impl ComposeValue {
+    /// The text the sequence produces.
+    pub fn text(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            ComposeValue::Char(ch) => ch.to_string().into(),
+            ComposeValue::Text(text) | ComposeValue::WithKeysym(text, _) => text.as_str().into(),
+        }
+    }
+}
+
+impl From<char> for ComposeValue {
+    fn from(ch: char) -> Self {
+        ComposeValue::Char(ch)
+    }
+}
+
+impl From<&ComposeOutput> for ComposeValue {
+    fn from(output: &ComposeOutput) -> Self {
+        match (output.char(), output.keysym) {
+            (_, Some(keysym)) => ComposeValue::WithKeysym(output.text.to_string(), keysym),
+            (Some(ch), None) => ComposeValue::Char(ch),
+            (None, None) => ComposeValue::Text(output.text.to_string()),
+        }
+    }
+}
+
+impl From<&ComposeValue> for ComposeOutput {
+    fn from(value: &ComposeValue) -> Self {
+        match value {
+            ComposeValue::Char(ch) => (*ch).into(),
+            ComposeValue::Text(text) => ComposeOutput::new(text.as_str()),
+            ComposeValue::WithKeysym(text, keysym) => ComposeOutput {
+                text: text.as_str().into(),
+                keysym: Some(*keysym),
+            },
+        }
+    }
+}
+
 /// Errors from validating, serializing, or converting layout files.
 #[derive(Debug, thiserror::Error)]
 pub enum IrError {
@@ -61,10 +117,12 @@ pub enum IrError {
     EmptyModifierActions(u32),
     #[error("empty compose sequence")]
     EmptyComposeSequence,
-    #[error("compose output is NUL")]
+    #[error("compose output is empty or NUL")]
     NullComposeOutput,
     #[error("compose sequence contains NUL")]
     NullComposeKey,
+    #[error("compose keysym {0:#x} has no character")]
+    UnrepresentableComposeKey(u32),
     #[error("serialization error: {0}")]
     Serialize(String),
     #[error("deserialization error: {0}")]
@@ -118,7 +176,7 @@ pub struct LayoutFile {
     /// Compose sequences as `(keys, output)`. Only sequences whose keys are all
     /// reachable in this layout are stored.
     #[serde(default)]
-    pub compose: Vec<(Vec<char>, char)>,
+    pub compose: ComposeList,
 }
 
 impl LayoutFile {
@@ -163,7 +221,8 @@ impl LayoutFile {
             if keys.is_empty() {
                 return Err(IrError::EmptyComposeSequence);
             }
-            if *output == '\0' {
+            let text = output.text();
+            if text.is_empty() || text.contains('\0') {
                 return Err(IrError::NullComposeOutput);
             }
             if keys.contains(&'\0') {
@@ -324,6 +383,13 @@ impl TryFrom<&KBLayout> for LayoutFile {
     type Error = IrError;
 
     fn try_from(layout: &KBLayout) -> Result<Self, IrError> {
+        LayoutFile::from_layout(layout, &layout.modifiers)
+    }
+}
+
+impl LayoutFile {
+    /// Export `layout` with `modifiers` in place of the compiled defaults.
+    pub(crate) fn from_layout(layout: &KBLayout, modifiers: &Modifiers) -> Result<Self, IrError> {
         let num_keys = layout.state_keymap.num_keys as u32;
         let file = LayoutFile {
             version: FORMAT_VERSION,
@@ -331,7 +397,7 @@ impl TryFrom<&KBLayout> for LayoutFile {
             repeat_keys: (0..num_keys)
                 .filter(|&k| layout.repeat_keys.contains(k))
                 .collect(),
-            modifiers: modifiers_from_layout(&layout.modifiers),
+            modifiers: modifiers_from_layout(modifiers),
             keymap: char_section(&layout.state_keymap),
             num_lock_keys: char_section(&layout.num_lock_keys),
             caps_lock_keymap: char_section(&layout.caps_lock_keymap),
@@ -459,42 +525,34 @@ fn modaction_from_state_modifier(kind: &StateModifier) -> ModAction {
     }
 }
 
-/// Depth-first walk of the composer trie emitting reachable, sorted sequences.
-fn compose_from_composer(composer: &Composer, reachable: &[char]) -> Vec<(Vec<char>, char)> {
-    let mut out = Vec::new();
-    let mut path = Vec::new();
-    dfs_compose(composer, 0, &mut path, &mut out, reachable);
+/// The composer's reachable sequences, sorted, as layout file entries.
+fn compose_from_composer(composer: &Composer, reachable: &[char]) -> ComposeList {
+    let mut out: ComposeList = composer
+        .sequences(&ComposeCursor::default())
+        .into_iter()
+        .filter_map(|sequence| {
+            let keys = sequence
+                .keys
+                .iter()
+                .map(|key| match *key {
+                    ComposeKey::Compose => Some(COMPOSE_KEY_CHAR),
+                    ComposeKey::Char(ch) => Some(ch),
+                    // Layout files are written in characters; keysyms
+                    // without one cannot be exported.
+                    ComposeKey::Keysym(sym) => keysym_char(sym),
+                })
+                .collect::<Option<Vec<char>>>()?;
+            keys.iter()
+                .all(|ch| *ch == COMPOSE_KEY_CHAR || reachable.binary_search(ch).is_ok())
+                .then(|| (keys, (&sequence.output).into()))
+        })
+        .collect();
     out.sort();
+    // Distinct keysyms producing the same character export one sequence.
+    out.dedup_by(|a, b| a.0 == b.0);
     out
 }
 
-fn dfs_compose(
-    composer: &Composer,
-    node: u32,
-    path: &mut Vec<char>,
-    out: &mut Vec<(Vec<char>, char)>,
-    reachable: &[char],
-) {
-    let node = &composer.nodes[node as usize];
-    if let Some(emit) = node.emit {
-        let reachable = path
-            .iter()
-            .all(|ch| *ch == COMPOSE_KEY_CHAR || reachable.binary_search(ch).is_ok());
-        if reachable {
-            out.push((path.clone(), emit));
-        }
-    }
-    for &(key, child) in &node.children {
-        path.push(if key == 0 {
-            COMPOSE_KEY_CHAR
-        } else {
-            char::from_u32(key).unwrap_or('\u{fffd}')
-        });
-        dfs_compose(composer, child, path, out, reachable);
-        path.pop();
-    }
-}
-
 impl TryFrom<LayoutFile> for KBLayout {
     type Error = IrError;
 
@@ -528,6 +586,20 @@ impl TryFrom<LayoutFile> for KBLayout {
         let caps_lock_keymap = from_levels(&file.caps_lock_keymap, num_keys, Some);
         let named_key_map = from_levels(&file.keysym_map, num_keys, |key| key);
         let caps_num_lock_keys = from_levels(&file.caps_num_lock_keys, num_keys, Some);
+        #[cfg(feature = "xkb")]
+        let keysym_map = keysyms_from_maps(&state_keymap, &named_key_map);
+        let mut key_types = KeyTypes::default();
+        for key in 0..num_keys as u32 {
+            let key_type = synthesize_key_type(|a, b| {
+                state_keymap.get(a, key) != state_keymap.get(b, key)
+                    || named_key_map.get(a, key) != named_key_map.get(b, key)
+            });
+            if key_type.mask != 0 {
+                key_types.set(key, key_type);
+            }
+        }
+        #[cfg(feature = "xkb")]
+        let level_keysym_map = keysym_map.clone();
 
         Ok(KBLayout {
             name: file.layout,
@@ -538,13 +610,36 @@ impl TryFrom<LayoutFile> for KBLayout {
             num_lock_keys,
             caps_lock_keymap,
             named_key_map,
+            key_types,
             #[cfg(feature = "xkb")]
             level_exceptions_keymap: FlatKeymap::new(num_keys),
+            #[cfg(feature = "xkb")]
+            keysym_map,
+            #[cfg(feature = "xkb")]
+            level_keysym_map,
+            #[cfg(feature = "xkb")]
+            level_keysym_lists: Vec::new(),
             caps_num_lock_keys,
         })
     }
 }
 
+/// Reconstruct keysyms for a layout loaded without its XKB source: named keys
+/// map to their canonical keysym, characters to [`crate::Keysym::from_char`].
+#[cfg(feature = "xkb")]
+fn keysyms_from_maps(chars: &FlatKeymap, named: &FlatNamedKeyMap) -> crate::FlatKeysymMap {
+    let mut keysyms = crate::FlatKeysymMap::new(chars.num_keys);
+    for (idx, slot) in keysyms.data.iter_mut().enumerate() {
+        let sym = crate::xkb::keynames::named_key_to_keysym(named.data[idx]);
+        *slot = if sym != 0 {
+            crate::Keysym::new(sym)
+        } else {
+            chars.data[idx].map_or(crate::Keysym::NO_SYMBOL, crate::Keysym::from_char)
+        };
+    }
+    keysyms
+}
+
 /// Un-flatten a per-level map back into a single `FlatMap`.
 fn from_levels<T: FlatMapValue, V: Copy>(
     levels: &BTreeMap<u8, BTreeMap<u32, V>>,
@@ -564,33 +659,34 @@ fn from_levels<T: FlatMapValue, V: Copy>(
 #[rustfmt::skip]
 fn modkind_from_modaction(action: ModAction) -> StateModifier {
     match action {
-        ModAction::Press(t) => StateModifier { kind: ModKind::Press { pressed: false }, mod_type: t },
-        ModAction::Lock(t) => StateModifier { kind: ModKind::Lock { pressed: false, locked: 0 }, mod_type: t },
+        ModAction::Press(t) => StateModifier { kind: ModKind::Press { pressed: false }, mod_type: t, flags: ModFlags::default() },
+        ModAction::Lock(t) => StateModifier { kind: ModKind::Lock { pressed: false, locked: 0 }, mod_type: t, flags: ModFlags::default() },
         ModAction::UnlockOnPress(t) => StateModifier {
             kind: ModKind::UnlockOnPress {
                 pressed: false,
                 locked: false,
             },
             mod_type: t,
+            flags: ModFlags::default(),
         },
-        ModAction::Latch(t) => StateModifier { kind: ModKind::Latch { pressed: false, latched: false }, mod_type: t },
+        ModAction::Latch(t) => StateModifier { kind: ModKind::Latch { pressed: false, latched: false, locked: 0 }, mod_type: t, flags: ModFlags::default() },
     }
 }
 
-fn composer_from_compose(sequences: &[(Vec<char>, char)]) -> Composer {
+fn composer_from_compose(sequences: &[(Vec<char>, ComposeValue)]) -> Composer {
     let mut composer = Composer::new();
     for (keys, output) in sequences {
-        let tokens: Vec<Token> = keys
+        let tokens: Vec<ComposeKey> = keys
             .iter()
             .map(|&ch| {
                 if ch == COMPOSE_KEY_CHAR {
-                    Token::Compose
+                    ComposeKey::Compose
                 } else {
-                    Token::Char(ch)
+                    ComposeKey::Char(ch)
                 }
             })
             .collect();
-        composer.insert(&tokens, *output);
+        composer.insert(&tokens, ComposeOutput::from(output));
     }
     composer
 }
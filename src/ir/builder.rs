@@ -0,0 +1,254 @@
+//! Typed construction of [`LayoutFile`]s.
+
+use std::collections::BTreeMap;
+
+use super::{ComposeValue, IrError, LayoutFile, ModAction, COMPOSE_KEY_CHAR, FORMAT_VERSION};
+use crate::composer::{keysym_char, ComposeKey};
+use crate::flat_keymap::MAX_LEVELS;
+use crate::named_keys::NamedKey;
+use crate::{Keymap, WKB};
+
+/// Builds a layout key by key, for layout editors and tests that would
+/// otherwise emit XKB text.
+///
+/// Levels follow [`crate::level_index`]: 1 is Shift, 2 is Level3 (AltGr),
+/// 4 is Level5, and their sums combine them. A key without a character at a
+/// level falls back the way XKB key types do, dropping the highest modifier
+/// first, so a two-level key types its Shift character under Shift+AltGr.
+///
+/// Keys added with [`Self::key`] or [`Self::named_key`] repeat unless
+/// [`Self::repeat`] says otherwise; modifier keys do not.
+///
+/// ```
+/// use wkb::ir::{LayoutBuilder, ModAction};
+/// use wkb::{ModType, NamedKey, LEFT_SHIFT};
+///
+/// let wkb = LayoutBuilder::new("custom")
+///     .key(30, ['a', 'A'])
+///     .named_key(1, [NamedKey::Escape])
+///     .modifier(LEFT_SHIFT, ModAction::Press(ModType::Level2))
+///     .build_wkb()
+///     .unwrap();
+/// assert_eq!(wkb.key_char(30), Some('a'));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LayoutBuilder {
+    name: String,
+    keys: BTreeMap<u32, BTreeMap<u8, char>>,
+    named_keys: BTreeMap<u32, BTreeMap<u8, NamedKey>>,
+    num_lock_keys: BTreeMap<u32, BTreeMap<u8, char>>,
+    caps_lock_keys: BTreeMap<u32, BTreeMap<u8, char>>,
+    caps_num_lock_keys: BTreeMap<u32, BTreeMap<u8, char>>,
+    modifiers: BTreeMap<u32, BTreeMap<u8, ModAction>>,
+    repeat: BTreeMap<u32, bool>,
+    compose: Vec<(Vec<ComposeKey>, ComposeValue)>,
+}
+
+impl LayoutBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the characters of `evdev_code` from level 0 up.
+    pub fn key(mut self, evdev_code: u32, levels: impl IntoIterator<Item = char>) -> Self {
+        let key = self.keys.entry(evdev_code).or_default();
+        for (level, ch) in (0..).zip(levels) {
+            key.insert(level, ch);
+        }
+        self
+    }
+
+    /// Set the character of `evdev_code` at one level.
+    pub fn key_level(mut self, evdev_code: u32, level: u8, ch: char) -> Self {
+        self.keys.entry(evdev_code).or_default().insert(level, ch);
+        self
+    }
+
+    /// Set the named-key identities of `evdev_code` from level 0 up. A key
+    /// can have both characters and names, like Escape or Enter.
+    pub fn named_key(
+        mut self,
+        evdev_code: u32,
+        levels: impl IntoIterator<Item = NamedKey>,
+    ) -> Self {
+        let key = self.named_keys.entry(evdev_code).or_default();
+        for (level, named_key) in (0..).zip(levels) {
+            key.insert(level, named_key);
+        }
+        self
+    }
+
+    /// Replace the character of `evdev_code` at `level` while Caps Lock is
+    /// locked.
+    pub fn caps_lock_key(mut self, evdev_code: u32, level: u8, ch: char) -> Self {
+        self.caps_lock_keys
+            .entry(evdev_code)
+            .or_default()
+            .insert(level, ch);
+        self
+    }
+
+    /// Replace the character of `evdev_code` at `level` while Num Lock is
+    /// locked.
+    pub fn num_lock_key(mut self, evdev_code: u32, level: u8, ch: char) -> Self {
+        self.num_lock_keys
+            .entry(evdev_code)
+            .or_default()
+            .insert(level, ch);
+        self
+    }
+
+    /// Replace the character of `evdev_code` at `level` while both Caps Lock
+    /// and Num Lock are locked.
+    pub fn caps_num_lock_key(mut self, evdev_code: u32, level: u8, ch: char) -> Self {
+        self.caps_num_lock_keys
+            .entry(evdev_code)
+            .or_default()
+            .insert(level, ch);
+        self
+    }
+
+    /// Make `evdev_code` a modifier key with `action` at every level.
+    pub fn modifier(mut self, evdev_code: u32, action: ModAction) -> Self {
+        self.modifiers
+            .insert(evdev_code, BTreeMap::from([(0, action)]));
+        self
+    }
+
+    /// Give the modifier key `evdev_code` a different `action` at `level`,
+    /// like Shift+AltGr locking Level3.
+    pub fn modifier_level(mut self, evdev_code: u32, level: u8, action: ModAction) -> Self {
+        self.modifiers
+            .entry(evdev_code)
+            .or_default()
+            .insert(level, action);
+        self
+    }
+
+    /// Set whether `evdev_code` repeats.
+    pub fn repeat(mut self, evdev_code: u32, repeats: bool) -> Self {
+        self.repeat.insert(evdev_code, repeats);
+        self
+    }
+
+    /// Add a compose sequence. Keysym keys are stored as their characters.
+    pub fn compose(mut self, keys: &[ComposeKey], output: impl Into<ComposeValue>) -> Self {
+        self.compose.push((keys.to_vec(), output.into()));
+        self
+    }
+
+    /// Build the validated [`LayoutFile`].
+    pub fn build(self) -> Result<LayoutFile, IrError> {
+        let repeat_keys = self
+            .keys
+            .keys()
+            .chain(self.named_keys.keys())
+            .chain(self.repeat.keys())
+            .copied()
+            .filter(|code| {
+                self.repeat
+                    .get(code)
+                    .copied()
+                    .unwrap_or(!self.modifiers.contains_key(code))
+            })
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let modifiers = self
+            .modifiers
+            .into_iter()
+            .map(|(code, actions)| (code, actions.into_iter().collect()))
+            .collect();
+        let compose = self
+            .compose
+            .into_iter()
+            .map(|(keys, output)| {
+                let keys = keys
+                    .into_iter()
+                    .map(|key| match key {
+                        ComposeKey::Compose => Ok(COMPOSE_KEY_CHAR),
+                        ComposeKey::Char(ch) => Ok(ch),
+                        ComposeKey::Keysym(sym) => {
+                            keysym_char(sym).ok_or(IrError::UnrepresentableComposeKey(sym))
+                        }
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((keys, output))
+            })
+            .collect::<Result<_, IrError>>()?;
+        let file = LayoutFile {
+            version: FORMAT_VERSION,
+            layout: self.name,
+            repeat_keys,
+            modifiers,
+            keymap: filled_section(&self.keys)?,
+            num_lock_keys: section(self.num_lock_keys),
+            caps_lock_keymap: section(self.caps_lock_keys),
+            caps_num_lock_keys: section(self.caps_num_lock_keys),
+            keysym_map: section(self.named_keys),
+            compose,
+        };
+        file.validate()?;
+        Ok(file)
+    }
+
+    /// Build a [`Keymap`] with this layout as its only group.
+    pub fn build_keymap(self) -> Result<Keymap, IrError> {
+        Keymap::new_from_layouts(vec![self.build()?])
+    }
+
+    /// Build a [`WKB`] with this layout as its only group.
+    pub fn build_wkb(self) -> Result<WKB, IrError> {
+        WKB::new_from_layouts(vec![self.build()?])
+    }
+}
+
+/// Transpose per-key levels into a level-keyed section.
+fn section<T>(keys: BTreeMap<u32, BTreeMap<u8, T>>) -> BTreeMap<u8, BTreeMap<u32, T>> {
+    let mut out: BTreeMap<u8, BTreeMap<u32, T>> = BTreeMap::new();
+    for (code, levels) in keys {
+        for (level, value) in levels {
+            out.entry(level).or_default().insert(code, value);
+        }
+    }
+    out
+}
+
+/// Like [`section`], resolving every level plane the layout uses so keys
+/// with fewer levels fall back as under XKB key types.
+fn filled_section(
+    keys: &BTreeMap<u32, BTreeMap<u8, char>>,
+) -> Result<BTreeMap<u8, BTreeMap<u32, char>>, IrError> {
+    let max_level = keys
+        .values()
+        .filter_map(|levels| levels.keys().next_back())
+        .max()
+        .copied()
+        .unwrap_or(0);
+    if max_level as usize >= MAX_LEVELS {
+        return Err(IrError::LevelOutOfRange(max_level));
+    }
+    let planes = (max_level + 1).next_power_of_two();
+    let mut out: BTreeMap<u8, BTreeMap<u32, char>> = BTreeMap::new();
+    for (&code, levels) in keys {
+        for level in 0..planes {
+            let mut fallback = level;
+            let ch = loop {
+                if let Some(&ch) = levels.get(&fallback) {
+                    break Some(ch);
+                }
+                if fallback == 0 {
+                    break None;
+                }
+                fallback &= !(1 << (7 - fallback.leading_zeros()));
+            };
+            if let Some(ch) = ch {
+                out.entry(level).or_default().insert(code, ch);
+            }
+        }
+    }
+    Ok(out)
+}
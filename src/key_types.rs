@@ -0,0 +1,140 @@
+//! Compact key-type tables used to report which modifiers a key consumed.
+
+use crate::modifiers::{MOD_ALTGR, MOD_SCROLL_LOCK, MOD_SHIFT};
+
+/// How consumed modifiers are computed, mirroring xkbcommon's
+/// `xkb_consumed_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConsumedMode {
+    /// Every modifier in the key type's mask is consumed unless preserved,
+    /// whether or not it changed the level.
+    #[default]
+    Xkb,
+    /// Only modifiers that actually change the produced keysym are consumed,
+    /// as GTK does. A modifier that selects the same keysyms as no modifiers
+    /// stays available for shortcuts.
+    Gtk,
+}
+
+/// One `map[...]` entry of a key type, with virtual modifiers resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KeyTypeEntry {
+    pub(crate) mods: u8,
+    pub(crate) level: u8,
+    pub(crate) preserve: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct KeyType {
+    pub(crate) mask: u8,
+    /// Active entries only; the first matching entry wins.
+    pub(crate) entries: Vec<KeyTypeEntry>,
+}
+
+impl KeyType {
+    #[inline]
+    fn matching(&self, mods: u8) -> Option<&KeyTypeEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.mods == mods & self.mask)
+    }
+
+    /// Shift level selected by `mods`.
+    #[inline]
+    pub(crate) fn level(&self, mods: u8) -> u8 {
+        self.matching(mods).map_or(0, |entry| entry.level)
+    }
+
+    /// Modifiers consumed by a key of this type under `mods`.
+    ///
+    /// `same_syms(a, b)` reports whether levels `a` and `b` of the key produce
+    /// the same keysyms; only [`ConsumedMode::Gtk`] uses it.
+    pub(crate) fn consumed(
+        &self,
+        mods: u8,
+        mode: ConsumedMode,
+        same_syms: impl Fn(u8, u8) -> bool,
+    ) -> u8 {
+        let matching = self.matching(mods);
+        let preserve = matching.map_or(0, |entry| entry.preserve);
+        let consumed = match mode {
+            ConsumedMode::Xkb => self.mask,
+            ConsumedMode::Gtk => {
+                let base = self.level(0);
+                self.entries
+                    .iter()
+                    .filter(|entry| !same_syms(entry.level, base))
+                    .filter(|entry| Some(*entry) == matching || entry.mods.count_ones() == 1)
+                    .fold(0, |acc, entry| acc | (entry.mods & !entry.preserve))
+            }
+        };
+        consumed & !preserve
+    }
+}
+
+/// Key types of one layout and the type used by each key.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KeyTypes {
+    pub(crate) types: Vec<KeyType>,
+    /// Index into `types` per evdev code, or `u16::MAX` for keys without one.
+    pub(crate) by_key: Vec<u16>,
+}
+
+impl KeyTypes {
+    pub(crate) fn get(&self, evdev_code: u32) -> Option<&KeyType> {
+        let idx = *self.by_key.get(evdev_code as usize)?;
+        self.types.get(idx as usize)
+    }
+
+    /// Assign `key_type` to `evdev_code`, sharing identical types.
+    pub(crate) fn set(&mut self, evdev_code: u32, key_type: KeyType) {
+        let idx = match self.types.iter().position(|other| *other == key_type) {
+            Some(idx) => idx,
+            None => {
+                self.types.push(key_type);
+                self.types.len() - 1
+            }
+        };
+        let evdev = evdev_code as usize;
+        if self.by_key.len() <= evdev {
+            self.by_key.resize(evdev + 1, u16::MAX);
+        }
+        self.by_key[evdev] = idx as u16;
+    }
+
+    /// Leave `evdev_code` without a key type.
+    pub(crate) fn clear(&mut self, evdev_code: u32) {
+        if let Some(idx) = self.by_key.get_mut(evdev_code as usize) {
+            *idx = u16::MAX;
+        }
+    }
+}
+
+/// Synthesize a key type from flattened level planes, for layouts loaded
+/// without their XKB types. `differs(a, b)` compares two plane indices.
+pub(crate) fn synthesize_key_type(differs: impl Fn(usize, usize) -> bool) -> KeyType {
+    const PLANE_MODS: [(usize, u32); 3] = [(1, MOD_SHIFT), (2, MOD_ALTGR), (4, MOD_SCROLL_LOCK)];
+    let mut plane_mask = 0;
+    for (bit, _) in PLANE_MODS {
+        if (0..8).any(|plane| plane & bit != 0 && differs(plane, plane & !bit)) {
+            plane_mask |= bit;
+        }
+    }
+    let mods_of = |plane: usize| {
+        PLANE_MODS
+            .iter()
+            .filter(|(bit, _)| plane & bit != 0)
+            .fold(0, |acc, (_, mask)| acc | *mask as u8)
+    };
+    KeyType {
+        mask: mods_of(plane_mask),
+        entries: (1..8)
+            .filter(|plane| plane & !plane_mask == 0)
+            .map(|plane| KeyTypeEntry {
+                mods: mods_of(plane),
+                level: plane as u8,
+                preserve: 0,
+            })
+            .collect(),
+    }
+}
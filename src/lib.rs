@@ -32,16 +32,26 @@
 //!
 //! | Method | Mutates state | Use case |
 //! |--------|--------------|----------|
+//! | [`WKB::key_event`] | yes | Key down/up — state update plus a combined [`KeyResult`] |
 //! | [`WKB::press_key`] | yes | Key down — updates modifier/group state |
 //! | [`WKB::release_key`] | yes | Key up — updates modifier/group state |
+//! | [`WKB::release_all`] | yes | Release every held key on focus loss or VT switch |
 //! | [`WKB::compose`] | yes | Feed a key into compose processing |
 //! | [`WKB::repeat_key`] | yes | Feed a repeated key into compose processing |
+//! | [`KeyRepeat::repeat`] | yes | Repeats due at a timestamp, via [`WKB::repeat_key`] |
 //! | [`WKB::key_char`] | no | Character under current modifiers (before compose) |
+//! | [`WKB::key_text`] | no | Character with optional Ctrl control-character mapping |
 //! | [`WKB::named_key`] | no | Named non-character identity under current state |
+//! | [`WKB::logical_key`] | no | Named key or character under current state |
+//! | [`WKB::key_with_modifiers`] | no | Named key or character under a given mask and layout |
+//! | `WKB::key_sym` | no | Keysym under current state (`xkb` feature) |
 //! | [`WKB::physical_key`] | no | Physical position from the evdev code alone |
+//! | [`WKB::is_key_down`] | no | Whether a key is held; [`WKB::pressed_keys`] lists them |
 //!
 //! Key state mutation, key identity, character lookup, and compose processing
-//! are intentionally separate. Public keycodes are always raw Linux/evdev codes.
+//! are available separately, or combined by [`WKB::key_event`], which resolves
+//! them in the right order around modifier changes. Public keycodes are always
+//! raw Linux/evdev codes.
 //!
 //! ## Feature Flags
 //!
@@ -49,31 +59,55 @@
 //! - **`compose`** (default) — Compose-key / dead-key sequence support.
 
 use crate::modifiers::*;
-pub use composer::{ComposeState, ComposeString};
-use composer::{Composer, Token};
+pub use composer::{
+    ComposeCompletion, ComposeKey, ComposeOutput, ComposeSequence, ComposeState, ComposeString,
+    ComposeTable, DEFAULT_COMPOSE_PLACEHOLDER,
+};
+use composer::{ComposeCursor, Composer};
+#[cfg(feature = "compose")]
+use composer::{ComposeInput, Reachable};
+use std::sync::Arc;
+mod accessx;
+pub use accessx::{AccessX, AccessXConfig, AccessXEvent, AccessXFilter, StickyKeys};
 mod composer;
 mod flat_keymap;
 mod groups;
+mod key_types;
 mod modifiers;
 mod physical_keys;
+mod repeat;
+#[cfg(feature = "xkb")]
+pub(crate) use flat_keymap::FlatKeysymMap;
+#[cfg(feature = "xkb")]
+use flat_keymap::MAX_LEVELS;
 pub(crate) use flat_keymap::{FlatKeymap, FlatNamedKeyMap};
 pub use groups::{Group, GroupChange, GroupKind, Groups};
+pub use key_types::ConsumedMode;
+use key_types::KeyTypes;
 pub use modifiers::{
     level_index, KeyDirection, ModType, ALTGR, CAPS_LOCK, LEFT_SHIFT, NUM_LOCK, RIGHT_SHIFT,
     SCROLL_LOCK,
 };
 pub use physical_keys::PhysicalKey;
+pub use repeat::KeyRepeat;
+mod remap;
+mod shortcuts;
+pub use shortcuts::ShortcutKey;
+mod snapshot;
+pub use snapshot::StateSnapshot;
+mod typing;
+pub use typing::{TypingEvent, TypingPlan};
 /// Intermediate representation for persisted layout data files.
 pub mod ir;
 mod named_keys;
-pub use named_keys::NamedKey;
+pub use named_keys::{LogicalKey, NamedKey};
 #[cfg(feature = "xkb")]
 mod xkb;
 #[cfg(feature = "xkb")]
-pub use xkb::XkbError;
-#[cfg(feature = "xkb")]
 #[doc(hidden)]
 pub use xkb::{keysym_to_named_key, load_compose_from_path, load_compose_from_path_uncached};
+#[cfg(feature = "xkb")]
+pub use xkb::{Context, Diagnostic, DiagnosticCode, Keysym, Section, XkbError};
 pub(crate) const BITSET_WORDS: usize = 12;
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Default)]
@@ -116,6 +150,11 @@ impl KeyBitSet {
         *word &= !mask;
         present
     }
+
+    /// The keys in the set, in ascending order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..BITSET_WORDS as u32 * 64).filter(|&key| self.contains(key))
+    }
 }
 
 /// Errors from WKB operations (not related to XKB parsing/compilation).
@@ -124,9 +163,25 @@ pub enum WkbError {
     /// Layout index out of range.
     #[error("Invalid layout index: {0}")]
     InvalidLayout(usize),
+    /// Evdev keycode outside the keymap.
+    #[error("Invalid key: {0}")]
+    InvalidKey(u32),
+    /// Shift level out of range.
+    #[error("Invalid level: {0}")]
+    InvalidLevel(usize),
+    /// [`StateSnapshot`] format version this crate cannot read.
+    #[error("Unsupported snapshot version: {0}")]
+    UnsupportedSnapshot(u32),
+    /// [`StateSnapshot`] taken from a state with a different keymap.
+    #[error("Snapshot does not match the keymap")]
+    SnapshotMismatch,
 }
 
-/// Core keyboard state machine. Tracks modifier state, key presses, and compose sequences.
+/// Keysym lists of key levels, by evdev code and XKB level.
+#[cfg(feature = "xkb")]
+pub(crate) type KeysymLists = Vec<((u32, usize), Box<[Keysym]>)>;
+
+/// Compiled lookup tables for a single layout group.
 #[derive(Debug, Clone)]
 pub struct KBLayout {
     pub(crate) name: String,
@@ -141,14 +196,386 @@ pub struct KBLayout {
     pub(crate) named_key_map: FlatNamedKeyMap,
     #[cfg(feature = "xkb")]
     pub(crate) level_exceptions_keymap: FlatKeymap,
+    #[cfg(feature = "xkb")]
+    pub(crate) keysym_map: FlatKeysymMap,
+    pub(crate) key_types: KeyTypes,
+    /// Keysyms by XKB shift level of each key's group, like `named_key_map`.
+    #[cfg(feature = "xkb")]
+    pub(crate) level_keysym_map: FlatKeysymMap,
+    /// Every keysym of the levels written with several, by key and XKB
+    /// level. `level_keysym_map` holds their first one.
+    #[cfg(feature = "xkb")]
+    pub(crate) level_keysym_lists: KeysymLists,
+}
+
+impl KBLayout {
+    /// Characters and keysyms the layout can type, for filtering compose
+    /// tables.
+    #[cfg(feature = "compose")]
+    pub(crate) fn reachable(&self) -> Reachable {
+        let mut chars: Vec<char> = self
+            .state_keymap
+            .data
+            .iter()
+            .chain(&self.caps_lock_keymap.data)
+            .chain(&self.num_lock_keys.data)
+            .chain(&self.caps_num_lock_keys.data)
+            .filter_map(|ch| *ch)
+            .collect();
+        chars.sort_unstable();
+        chars.dedup();
+        #[cfg(feature = "xkb")]
+        let mut keysyms: Vec<u32> = self
+            .keysym_map
+            .data
+            .iter()
+            .chain(&self.level_keysym_map.data)
+            .map(|sym| sym.raw())
+            .chain(chars.iter().map(|&ch| Keysym::from_char(ch).raw()))
+            .filter(|&sym| sym != 0)
+            .collect();
+        #[cfg(not(feature = "xkb"))]
+        let mut keysyms = Vec::new();
+        keysyms.sort_unstable();
+        keysyms.dedup();
+        Reachable { chars, keysyms }
+    }
+
+    /// Modifiers selecting `level`, or `None` when the layout has no
+    /// modifier for one of its Level3/Level5 bits.
+    fn level_mods(&self, level: usize) -> Option<u32> {
+        let masks = &self.modifiers.masks;
+        let mut mods = 0;
+        for (bit, mask) in [(1, MOD_SHIFT), (2, masks.level3), (4, masks.level5)] {
+            if level & bit != 0 {
+                mods |= Some(mask).filter(|&mask| mask != 0)?;
+            }
+        }
+        Some(mods)
+    }
+
+    /// Every key `matches` accepts at some level, as a keystroke for its
+    /// lowest such level. Lower levels come first.
+    fn keystrokes(&self, matches: impl Fn(u32, usize) -> bool) -> Vec<Keystroke> {
+        let num_keys = self.state_keymap.num_keys;
+        let levels = self.state_keymap.data.len() / num_keys.max(1);
+        let mut keystrokes = Vec::new();
+        for level in 0..levels {
+            let Some(mods) = self.level_mods(level) else {
+                continue;
+            };
+            for evdev_code in 0..num_keys as u32 {
+                let seen = keystrokes
+                    .iter()
+                    .any(|keystroke: &Keystroke| keystroke.evdev_code == evdev_code);
+                if !seen && matches(evdev_code, level) {
+                    keystrokes.push(Keystroke { evdev_code, mods });
+                }
+            }
+        }
+        keystrokes
+    }
+
+    /// The first keystroke typing `key` of a compose sequence: the Compose
+    /// key, or a key producing the keysym or character.
+    #[cfg(feature = "compose")]
+    fn compose_keystroke(&self, key: ComposeKey, modifiers: &Modifiers) -> Option<Keystroke> {
+        match key {
+            ComposeKey::Compose => modifiers.iter().find_map(|(&evdev_code, modifier)| {
+                let level = match modifier {
+                    Modifier::Single(modifier) => {
+                        modifier.has_mod_type(ModType::Compose).then_some(0)
+                    }
+                    Modifier::Leveled(levels) => levels
+                        .iter()
+                        .find(|(_, modifier)| modifier.has_mod_type(ModType::Compose))
+                        .map(|(&level, _)| level as usize),
+                }?;
+                let mods = self.level_mods(level)?;
+                Some(Keystroke { evdev_code, mods })
+            }),
+            ComposeKey::Char(ch) => self
+                .keystrokes(|code, level| self.state_keymap.get(level, code) == Some(ch))
+                .first()
+                .copied(),
+            #[cfg(feature = "xkb")]
+            ComposeKey::Keysym(sym) => self
+                .keystrokes(|code, level| self.keysym_map.get(level, code).raw() == sym)
+                .first()
+                .copied(),
+            #[cfg(not(feature = "xkb"))]
+            ComposeKey::Keysym(_) => None,
+        }
+    }
+
+    /// Whether two shift levels of a key produce the same symbols.
+    fn same_level_syms(&self, evdev_code: u32, a: usize, b: usize) -> bool {
+        #[cfg(feature = "xkb")]
+        {
+            self.level_keysym_map.get(a, evdev_code) == self.level_keysym_map.get(b, evdev_code)
+        }
+        #[cfg(not(feature = "xkb"))]
+        {
+            self.state_keymap.get(a, evdev_code) == self.state_keymap.get(b, evdev_code)
+                && self.named_key_map.get(a, evdev_code) == self.named_key_map.get(b, evdev_code)
+        }
+    }
+
+    /// Level index selected by the Level2/3/5 modifiers in the effective
+    /// modifier mask `mods`, clamped to the levels this layout defines.
+    fn selected_level(&self, mods: u32) -> usize {
+        let nk = self.state_keymap.num_keys;
+        let len = self.state_keymap.data.len();
+        let masks = &self.modifiers.masks;
+        let level5 = mods & masks.level5 != 0 && len > 4 * nk;
+        let level3 = mods & masks.level3 != 0 && len > 2 * nk;
+        let level2 = mods & MOD_SHIFT != 0 && len > nk;
+        level_index(level5, level3, level2)
+    }
+
+    /// Character replacing the level's base character under the Caps/Num
+    /// Lock bits of `locked`.
+    fn lock_override(&self, evdev_code: u32, level: usize, locked: u32) -> Option<char> {
+        let num_locked = locked & self.modifiers.masks.num != 0;
+        let caps_locked = locked & MOD_CAPS_LOCK != 0;
+        if num_locked && caps_locked {
+            if let Some(c) = self.caps_num_lock_keys.get(level, evdev_code) {
+                return Some(c);
+            }
+        }
+        if num_locked {
+            if let Some(c) = self.num_lock_keys.get(level, evdev_code) {
+                return Some(c);
+            }
+        }
+        if caps_locked {
+            if let Some(c) = self.caps_lock_keymap.get(level, evdev_code) {
+                return Some(c);
+            }
+        }
+        None
+    }
+
+    /// Character at the level selected by `mods`, including lock overrides.
+    fn selected_char(&self, evdev_code: u32, mods: u32, locked: u32) -> Option<char> {
+        let level = self.selected_level(mods);
+        self.lock_override(evdev_code, level, locked)
+            .or_else(|| self.state_keymap.get(level, evdev_code))
+    }
+
+    /// Named key at the level selected by `mods`, falling back to lower
+    /// levels so ONE_LEVEL keys keep their identity.
+    fn named_key(&self, evdev_code: u32, mods: u32) -> NamedKey {
+        let nk = self.named_key_map.num_keys;
+        let len = self.named_key_map.data.len();
+        let masks = &self.modifiers.masks;
+        let level5 = mods & masks.level5 != 0 && len > 4 * nk;
+        let level3 = mods & masks.level3 != 0 && len > 2 * nk;
+        let level2 = mods & MOD_SHIFT != 0 && len > nk;
+        let level = level_index(level5, level3, level2);
+        (0..=level)
+            .rev()
+            .map(|l| self.named_key_map.get(l, evdev_code))
+            .find(|named| *named != NamedKey::Unnamed)
+            .unwrap_or(NamedKey::Unnamed)
+    }
+
+    /// Every keysym at XKB `level` of a key.
+    #[cfg(feature = "xkb")]
+    fn level_syms(&self, evdev_code: u32, level: usize) -> &[Keysym] {
+        if let Some((_, syms)) = self
+            .level_keysym_lists
+            .iter()
+            .find(|(key, _)| *key == (evdev_code, level))
+        {
+            return syms;
+        }
+        self.level_keysym_map
+            .slot(level, evdev_code)
+            .filter(|sym| **sym != Keysym::NO_SYMBOL)
+            .map_or(&[], std::slice::from_ref)
+    }
+
+    /// ASCII character produced at the level `mods` select for a key, used
+    /// by the Ctrl fallback to Latin layouts.
+    fn ascii_char(&self, evdev_code: u32, mods: u8) -> Option<char> {
+        let level = self
+            .key_types
+            .get(evdev_code)
+            .map_or(0, |key_type| key_type.level(mods)) as usize;
+        #[cfg(feature = "xkb")]
+        {
+            let sym = self.level_keysym_map.get(level, evdev_code);
+            (sym != Keysym::NO_SYMBOL && sym.raw() <= 0x7f).then(|| sym.raw() as u8 as char)
+        }
+        #[cfg(not(feature = "xkb"))]
+        {
+            self.state_keymap
+                .get(level, evdev_code)
+                .filter(char::is_ascii)
+        }
+    }
+}
+
+/// Immutable compiled keymap: the per-layout lookup tables, compose tries and
+/// group key definitions.
+///
+/// A `Keymap` is built once and shared through an [`Arc`] by any number of
+/// [`WKB`] states, mirroring xkbcommon's keymap/state split.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    pub(crate) layouts: Vec<KBLayout>,
+    pub(crate) groups: Groups,
+    pub(crate) mods: ModMap,
+}
+
+#[cfg(feature = "xkb")]
+impl Keymap {
+    /// Compile a keymap from RMLVO names, matching xkbcommon's `xkb_keymap_new_from_names`.
+    ///
+    /// `layout` and `variant` are comma-separated lists (e.g. `"us,fr"`, `"dvorak,azerty"`).
+    /// The components are resolved through the `rules` file (`evdev` when
+    /// empty) found under `rules/` in the default include paths of
+    /// [`Context::new`]. An empty `model` selects `pc105`, and `None` options
+    /// fall back to `XKB_DEFAULT_OPTIONS`. Use [`Context::keymap_from_names`]
+    /// to search other directories or in-memory files.
+    pub fn new_from_names(
+        rules: &str,
+        model: &str,
+        layout: &str,
+        variant: &str,
+        options: Option<&str>,
+    ) -> Result<Self, XkbError> {
+        Self::new_from_names_with_warnings(rules, model, layout, variant, options)
+            .map(|(keymap, _)| keymap)
+    }
+
+    /// Like [`Keymap::new_from_names`], also returning the warnings for
+    /// input skipped while compiling, such as unknown keysyms.
+    pub fn new_from_names_with_warnings(
+        rules: &str,
+        model: &str,
+        layout: &str,
+        variant: &str,
+        options: Option<&str>,
+    ) -> Result<(Self, Vec<Diagnostic>), XkbError> {
+        Context::new().keymap_from_names(rules, model, layout, variant, options)
+    }
+
+    /// Compile a keymap from an XKB keymap string (v1 text format).
+    ///
+    /// On failure, [`XkbError::diagnostic`] gives the section, file, line
+    /// and token the compilation stopped at.
+    pub fn new_from_string(keymap: &str) -> Result<Self, XkbError> {
+        Self::new_from_string_with_warnings(keymap).map(|(keymap, _)| keymap)
+    }
+
+    /// Like [`Keymap::new_from_string`], also returning the warnings for
+    /// input skipped while compiling, such as unknown keysyms, aliases to
+    /// undefined keys and malformed statements.
+    pub fn new_from_string_with_warnings(
+        keymap: &str,
+    ) -> Result<(Self, Vec<Diagnostic>), XkbError> {
+        Context::new().keymap_from_string(keymap)
+    }
+
+    /// Serialize the keymap to XKB v1 text format.
+    ///
+    /// Generates the string on demand from the flat keysym tables.
+    pub fn as_xkb_string(&self) -> String {
+        self.generate_xkb_string()
+    }
+}
+
+impl Keymap {
+    /// Rebuild a [`Keymap`] from one or more [`ir::LayoutFile`]s. Each file
+    /// becomes one layout group, in order.
+    pub fn new_from_layouts(files: Vec<ir::LayoutFile>) -> Result<Self, ir::IrError> {
+        let mut layouts = Vec::with_capacity(files.len());
+        for file in files {
+            layouts.push(KBLayout::try_from(file)?);
+        }
+        Ok(Keymap {
+            layouts,
+            groups: Groups::default(),
+            mods: ModMap::default(),
+        })
+    }
+
+    /// Return the number of modifiers the keymap declares, real and virtual.
+    pub fn num_mods(&self) -> usize {
+        self.mods.mods.len()
+    }
+
+    /// Return the index of a modifier by name, like xkbcommon's
+    /// `xkb_keymap_mod_get_index`.
+    ///
+    /// The eight real modifiers (`Shift`, `Lock`, `Control`, `Mod1`…`Mod5`)
+    /// come first; virtual modifiers such as `Alt`, `Super`, `NumLock` or
+    /// `LevelThree` follow in the keymap's declaration order. Bit `i` of a
+    /// `wl_keyboard.modifiers` mask refers to modifier `i`.
+    pub fn mod_index(&self, name: &str) -> Option<usize> {
+        self.mods.index(name)
+    }
+
+    /// Return the name of the modifier at `idx`.
+    pub fn mod_name(&self, idx: usize) -> Option<&str> {
+        self.mods.name(idx)
+    }
+
+    /// Return the real modifier mask a modifier maps to, e.g. `Mod1` for
+    /// `Alt` in the usual evdev keymaps. Returns `0` for unknown or unmapped
+    /// modifiers.
+    pub fn mod_mask(&self, name: &str) -> u32 {
+        self.mods.mask(name)
+    }
+
+    /// Return the number of layouts in this keymap.
+    pub fn num_layouts(&self) -> usize {
+        self.layouts.len()
+    }
+
+    /// Return the name of the layout at the given index.
+    pub fn layout_name(&self, layout_idx: usize) -> Option<&str> {
+        self.layouts.get(layout_idx).map(|s| s.name.as_str())
+    }
+
+    /// Export a layout as an [`ir::LayoutFile`] for persistence.
+    pub fn export_layout(&self, layout_idx: usize) -> Result<ir::LayoutFile, ir::IrError> {
+        let layout = self
+            .layouts
+            .get(layout_idx)
+            .ok_or(ir::IrError::InvalidLayoutIndex(layout_idx))?;
+        ir::LayoutFile::try_from(layout)
+    }
 }
 
 /// Core keyboard state machine. Tracks modifier state, key presses, and compose sequences.
+///
+/// The lookup tables live in a shared [`Keymap`]; a `WKB` only owns the
+/// per-seat modifier, group and compose state, so cloning one or creating
+/// several from the same keymap with [`WKB::new`] is cheap.
 #[derive(Debug, Clone)]
 pub struct WKB {
-    pub(crate) layouts: Vec<KBLayout>,
+    pub(crate) keymap: Arc<Keymap>,
+    /// The shared keymap before [`WKB::remap_key`] and friends made
+    /// `keymap` a private copy.
+    pub(crate) base_keymap: Option<Arc<Keymap>>,
     pub(crate) current_layout_idx: usize,
     pub(crate) groups: Groups,
+    /// Modifier definitions and state, one entry per layout.
+    pub(crate) modifiers: Vec<Modifiers>,
+    /// Compose sequence progress, one cursor per layout.
+    pub(crate) compose_cursors: Vec<ComposeCursor>,
+    /// Compose tables installed with [`WKB::set_compose_table`], replacing
+    /// the keymap's, one per layout.
+    pub(crate) composers: Vec<Option<Arc<Composer>>>,
+    /// Preedit glyph for the Compose key; `None` hides it.
+    #[cfg(feature = "compose")]
+    pub(crate) compose_placeholder: Option<char>,
+    pub(crate) text_mode: TextMode,
+    /// Layout chosen with [`WKB::set_shortcut_layout`].
+    pub(crate) shortcut_layout: Option<usize>,
 }
 
 #[cfg(feature = "xkb")]
@@ -163,75 +590,107 @@ impl WKB {
         variant: &str,
         options: Option<&str>,
     ) -> Result<Self, XkbError> {
-        xkb::new_from_names(rules, model, layout, variant, options)
+        Keymap::new_from_names(rules, model, layout, variant, options)
+            .map(|keymap| Self::new(Arc::new(keymap)))
     }
 
     /// Create WKB instance from an XKB keymap string (v1 text format).
     pub fn new_from_string(keymap: &str) -> Result<Self, XkbError> {
-        xkb::new_from_string(keymap)
+        Keymap::new_from_string(keymap).map(|keymap| Self::new(Arc::new(keymap)))
     }
 }
 
 impl WKB {
-    /// Reset all transient input state: compose sequence.
+    /// Create a fresh state for a shared keymap.
+    pub fn new(keymap: Arc<Keymap>) -> Self {
+        let modifiers = keymap
+            .layouts
+            .iter()
+            .map(|layout| layout.modifiers.clone())
+            .collect();
+        let compose_cursors = vec![ComposeCursor::default(); keymap.layouts.len()];
+        let composers = vec![None; keymap.layouts.len()];
+        Self {
+            groups: keymap.groups.clone(),
+            keymap,
+            base_keymap: None,
+            current_layout_idx: 0,
+            modifiers,
+            compose_cursors,
+            composers,
+            #[cfg(feature = "compose")]
+            compose_placeholder: Some(DEFAULT_COMPOSE_PLACEHOLDER),
+            text_mode: TextMode::default(),
+            shortcut_layout: None,
+        }
+    }
+
+    /// Return the shared keymap backing this state.
+    pub fn keymap(&self) -> &Arc<Keymap> {
+        &self.keymap
+    }
+
+    #[inline]
+    fn layout(&self) -> &KBLayout {
+        &self.keymap.layouts[self.current_layout_idx]
+    }
+
+    #[inline]
+    fn mods(&self) -> &Modifiers {
+        &self.modifiers[self.current_layout_idx]
+    }
+
+    /// Reset all transient input state: compose sequences on every layout.
     /// Call on wl_keyboard.leave or when focus changes.
     pub fn reset_state(&mut self) {
-        self.layouts[self.current_layout_idx].composer.reset();
+        for cursor in &mut self.compose_cursors {
+            cursor.reset();
+        }
     }
 
     /// Return the raw modifier bitmasks for `wl_keyboard.modifiers`.
     ///
     /// Returns depressed, latched, locked bitmasks and the active layout index.
     pub fn raw_modifiers(&self) -> RawModifiers {
-        self.layouts[self.current_layout_idx]
-            .modifiers
-            .state(self.current_layout_idx)
+        self.mods().state(self.current_layout_idx)
     }
 
     /// Return `true` if the Shift modifier is active.
     pub fn shift(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_SHIFT != 0
+        self.effective_mods() & MOD_SHIFT != 0
     }
 
     /// Return `true` if the Control modifier is active.
     pub fn ctrl(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_CTRL != 0
+        self.effective_mods() & MOD_CTRL != 0
     }
 
     /// Return `true` if the Alt modifier is active.
     pub fn alt(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_ALT != 0
+        self.effective_mods() & self.mods().masks.alt != 0
     }
 
     /// Return `true` if the Logo (Super/Windows) modifier is active.
     pub fn logo(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_LOGO != 0
+        self.effective_mods() & self.mods().masks.logo != 0
     }
 
     /// Return `true` if Caps Lock is active.
     pub fn caps_lock(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_CAPS_LOCK != 0
+        self.effective_mods() & MOD_CAPS_LOCK != 0
     }
 
     /// Return `true` if Num Lock is active.
     pub fn num_lock(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_NUM_LOCK != 0
+        self.effective_mods() & self.mods().masks.num != 0
     }
 
     pub fn mod3(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_SCROLL_LOCK != 0
+        self.effective_mods() & MOD_SCROLL_LOCK != 0
     }
 
     pub fn mod5(&self) -> bool {
-        let raw = self.raw_modifiers();
-        (raw.depressed | raw.latched | raw.locked) & modifiers::MOD_ALTGR != 0
+        self.effective_mods() & MOD_ALTGR != 0
     }
 
     /// Apply modifier state received from `wl_keyboard.modifiers`.
@@ -240,6 +699,9 @@ impl WKB {
     /// Returns whether the externally observable raw modifiers or LED state
     /// actually changed. An invalid group that causes no effective change
     /// reports neither flag.
+    ///
+    /// Masks use the keymap's modifier indices (see [`Keymap::mod_index`]);
+    /// virtual modifier bits are folded into the real modifiers they map to.
     pub fn update_modifiers(
         &mut self,
         depressed: u32,
@@ -253,9 +715,12 @@ impl WKB {
             self.groups.set_layout(group as usize, self.num_layouts());
             self.current_layout_idx = group as usize;
         }
-        self.layouts[self.current_layout_idx]
-            .modifiers
-            .update(depressed, latched, locked);
+        let mods = &self.keymap.mods;
+        self.modifiers[self.current_layout_idx].update(
+            mods.resolve(depressed),
+            mods.resolve(latched),
+            mods.resolve(locked),
+        );
         StateChanges {
             is_modifier: false,
             modifiers_updated: self.raw_modifiers() != before_mods,
@@ -273,19 +738,17 @@ impl WKB {
 
     /// Return the LED indicator state.
     pub fn leds_state(&self) -> LedState {
-        self.layouts[self.current_layout_idx].modifiers.leds_state()
+        self.mods().leds_state()
     }
 
     /// Return whether the given evdev keycode is a repeating key.
     pub fn key_repeats(&self, evdev_code: u32) -> bool {
-        self.layouts[self.current_layout_idx]
-            .repeat_keys
-            .contains(evdev_code)
+        self.layout().repeat_keys.contains(evdev_code)
     }
 
     /// Return the number of layouts in this keymap.
     pub fn num_layouts(&self) -> usize {
-        self.layouts.len()
+        self.keymap.num_layouts()
     }
 
     /// Return the index of the currently active layout.
@@ -295,15 +758,13 @@ impl WKB {
 
     /// Switch to a different layout by index.
     pub fn set_layout(&mut self, layout_idx: usize) -> Result<(), WkbError> {
-        if layout_idx >= self.layouts.len() {
+        if layout_idx >= self.num_layouts() {
             return Err(WkbError::InvalidLayout(layout_idx));
         }
         let old_layout = self.current_layout_idx;
         if layout_idx != old_layout {
-            let raw = self.layouts[old_layout].modifiers.state(layout_idx);
-            self.layouts[layout_idx]
-                .modifiers
-                .update(raw.depressed, raw.latched, raw.locked);
+            let raw = self.modifiers[old_layout].state(layout_idx);
+            self.modifiers[layout_idx].update(raw.depressed, raw.latched, raw.locked);
         }
         self.groups.set_layout(layout_idx, self.num_layouts());
         self.current_layout_idx = layout_idx;
@@ -312,7 +773,7 @@ impl WKB {
 
     /// Return the name of the layout at the given index.
     pub fn layout_name(&self, layout_idx: usize) -> Option<&str> {
-        self.layouts.get(layout_idx).map(|s| s.name.as_str())
+        self.keymap.layout_name(layout_idx)
     }
 
     /// Serialize the underlying XKB keymap to v1 text format.
@@ -321,7 +782,7 @@ impl WKB {
     /// Returns the generated XKB v1 keymap string.
     #[cfg(feature = "xkb")]
     pub fn as_xkb_string(&self) -> Option<String> {
-        Some(self.generate_xkb_string())
+        Some(self.keymap.as_xkb_string())
     }
 
     /// Get the named, non-character identity for an evdev keycode under the
@@ -332,27 +793,16 @@ impl WKB {
     /// selected level has no named mapping, lower levels are tried so ONE_LEVEL
     /// keys such as Shift and Escape keep their identity.
     pub fn named_key(&self, evdev_code: u32) -> NamedKey {
-        let kb_layout = &self.layouts[self.current_layout_idx];
-        let (_none_active, level2, level3, level5) = kb_layout.modifiers.active_none_and_levels();
-        let nk = kb_layout.named_key_map.num_keys;
-        let level5 = level5 && kb_layout.named_key_map.data.len() > 4 * nk;
-        let level3 = level3 && kb_layout.named_key_map.data.len() > 2 * nk;
-        let level2 = level2 && kb_layout.named_key_map.data.len() > nk;
-        let level = level_index(level5, level3, level2);
-        for l in (0..=level).rev() {
-            let named = kb_layout.named_key_map.get(l, evdev_code);
-            if named != NamedKey::Unnamed {
-                return named;
-            }
-        }
-        NamedKey::Unnamed
+        self.layout().named_key(evdev_code, self.effective_mods())
     }
 
     /// Get the named key at a specific layout and level for an evdev keycode.
     /// Bypasses current modifier state.
     /// Returns [`NamedKey::Unnamed`] if no named key is mapped.
     pub fn level_named_key(&self, evdev_code: u32, layout: usize, level: usize) -> NamedKey {
-        self.layouts[layout].named_key_map.get(level, evdev_code)
+        self.keymap.layouts[layout]
+            .named_key_map
+            .get(level, evdev_code)
     }
 
     /// Get the character at a specific layout and level for an evdev keycode.
@@ -360,64 +810,266 @@ impl WKB {
     /// Does not consider caps lock or num lock overrides.
     pub fn level_char(&self, evdev_code: u32, layout: usize, level: usize) -> Option<char> {
         #[cfg(feature = "xkb")]
-        if let Some(exception_char) = self.layouts[layout]
+        if let Some(exception_char) = self.keymap.layouts[layout]
             .level_exceptions_keymap
             .get(level, evdev_code)
         {
             return Some(exception_char);
         }
-        self.layouts[layout].state_keymap.get(level, evdev_code)
+        self.keymap.layouts[layout]
+            .state_keymap
+            .get(level, evdev_code)
+    }
+
+    /// Return the keysym for an evdev keycode under the current state, like
+    /// xkbcommon's `xkb_state_key_get_one_sym`.
+    ///
+    /// Caps Lock and Num Lock apply, so Caps Lock + `a` yields `A` and Num
+    /// Lock selects the keypad digits. Ctrl/Alt/Logo never blank the keysym.
+    /// Levels written with several keysyms give their first one; see
+    /// [`Self::key_syms`].
+    #[cfg(feature = "xkb")]
+    pub fn key_sym(&self, evdev_code: u32) -> Keysym {
+        let kb_layout = self.layout();
+        let level = kb_layout.selected_level(self.effective_mods());
+        let sym = kb_layout.keysym_map.get(level, evdev_code);
+        let locked = self.raw_modifiers().locked;
+        let Some(ch) = kb_layout.lock_override(evdev_code, level, locked) else {
+            return sym;
+        };
+        if sym.to_upper().to_char() == Some(ch) {
+            return sym.to_upper();
+        }
+        let levels = &kb_layout.level_keysym_map;
+        (0..MAX_LEVELS)
+            .map(|level| levels.get(level, evdev_code))
+            .find(|sym| sym.to_char() == Some(ch))
+            .unwrap_or_else(|| Keysym::from_char(ch))
+    }
+
+    /// Return every keysym of the level the current state selects for an
+    /// evdev keycode, like xkbcommon's `xkb_state_key_get_syms`.
+    ///
+    /// Levels written as `{ a, b }` yield all their keysyms, and the slice is
+    /// empty when the level has none. Unlike [`Self::key_sym`], Caps Lock
+    /// does not transform the result.
+    #[cfg(feature = "xkb")]
+    pub fn key_syms(&self, evdev_code: u32) -> &[Keysym] {
+        let kb_layout = self.layout();
+        let mods = self.effective_mods() as u8;
+        let level = kb_layout
+            .key_types
+            .get(evdev_code)
+            .map_or(0, |key_type| key_type.level(mods)) as usize;
+        kb_layout.level_syms(evdev_code, level)
+    }
+
+    /// Return the keysym at a specific layout and shift level for an evdev
+    /// keycode, like xkbcommon's `xkb_keymap_key_get_syms_by_level`.
+    /// Bypasses current modifier state and lock transformations. Levels
+    /// written with several keysyms give their first one; see
+    /// [`Self::level_syms`].
+    #[cfg(feature = "xkb")]
+    pub fn level_sym(&self, evdev_code: u32, layout: usize, level: usize) -> Keysym {
+        self.keymap.layouts[layout]
+            .level_keysym_map
+            .get(level, evdev_code)
+    }
+
+    /// Return every keysym at a specific layout and shift level for an evdev
+    /// keycode, like xkbcommon's `xkb_keymap_key_get_syms_by_level`.
+    #[cfg(feature = "xkb")]
+    pub fn level_syms(&self, evdev_code: u32, layout: usize, level: usize) -> &[Keysym] {
+        self.keymap.layouts[layout].level_syms(evdev_code, level)
     }
 
     /// Resolve the character for the given evdev keycode under the current modifier state.
     /// This is a pure lookup with no side effects — it does not update modifier state
     /// or advance compose sequences. Use this for:
     /// - `text_with_all_modifiers` (winit): the raw character including all modifier effects
-    /// - Re-resolving characters when modifiers change during key repeat
+    /// - Re-resolving characters when modifiers change during key repeat, as
+    ///   [`KeyRepeat`] does through [`Self::repeat_key`]
     ///
     /// Returns `None` while Ctrl, Alt, or Logo are active so callers do not treat
     /// shortcut chords as typed text.
     pub fn key_char(&self, evdev_code: u32) -> Option<char> {
-        let kb_layout = &self.layouts[self.current_layout_idx];
-        let (none_active, level2, level3, level5) = kb_layout.modifiers.active_none_and_levels();
-        if none_active {
+        if self.mods().active_none_and_levels().0 {
             return None;
         }
-        let nk = kb_layout.state_keymap.num_keys;
-        let level5 = level5 && kb_layout.state_keymap.data.len() > 4 * nk;
-        let level3 = level3 && kb_layout.state_keymap.data.len() > 2 * nk;
-        let level2 = level2 && kb_layout.state_keymap.data.len() > nk;
-        let base_level = level_index(level5, level3, level2);
-        if kb_layout.modifiers.num_locked() && kb_layout.modifiers.caps_locked() {
-            if let Some(c) = kb_layout.caps_num_lock_keys.get(base_level, evdev_code) {
-                return Some(c);
-            }
+        self.selected_char(evdev_code)
+    }
+
+    /// Character at the level selected by the current modifiers, including
+    /// lock overrides, without blanking Ctrl/Alt/Logo chords.
+    fn selected_char(&self, evdev_code: u32) -> Option<char> {
+        let locked = self.raw_modifiers().locked;
+        self.layout()
+            .selected_char(evdev_code, self.effective_mods(), locked)
+    }
+
+    /// Effective (depressed, latched or locked) modifier mask.
+    #[inline]
+    fn effective_mods(&self) -> u32 {
+        let raw = self.raw_modifiers();
+        raw.depressed | raw.latched | raw.locked
+    }
+
+    /// Resolve the logical identity of an evdev keycode on `layout` under a
+    /// caller-supplied modifier mask instead of the current state.
+    ///
+    /// `mods` uses the bit layout of [`Self::raw_modifiers`]; its Caps Lock and
+    /// Num Lock bits apply the lock overrides as if locked. Like
+    /// [`Self::logical_key`], Ctrl/Alt/Logo do not hide the character. Pass the
+    /// current effective mask with some bits cleared for "current state minus
+    /// Ctrl/Alt", or `0` for the base level of a layout. Returns
+    /// [`LogicalKey::Unidentified`] for an invalid layout index.
+    pub fn key_with_modifiers(&self, evdev_code: u32, layout: usize, mods: u32) -> LogicalKey {
+        let Some(kb_layout) = self.keymap.layouts.get(layout) else {
+            return LogicalKey::Unidentified;
+        };
+        match kb_layout.named_key(evdev_code, mods) {
+            NamedKey::Unnamed => kb_layout
+                .selected_char(evdev_code, mods, mods)
+                .map_or(LogicalKey::Unidentified, LogicalKey::Character),
+            named => LogicalKey::Named(named),
         }
-        if kb_layout.modifiers.num_locked() {
-            if let Some(c) = kb_layout.num_lock_keys.get(base_level, evdev_code) {
-                return Some(c);
-            }
+    }
+
+    /// Resolve the character of an evdev keycode on `layout` under a
+    /// caller-supplied modifier mask. See [`Self::key_with_modifiers`].
+    pub fn char_with_modifiers(&self, evdev_code: u32, layout: usize, mods: u32) -> Option<char> {
+        self.keymap
+            .layouts
+            .get(layout)?
+            .selected_char(evdev_code, mods, mods)
+    }
+
+    /// Resolve the logical identity of an evdev keycode on the active layout
+    /// with no modifiers or locks applied, like winit's
+    /// `key_without_modifiers`.
+    pub fn key_without_modifiers(&self, evdev_code: u32) -> LogicalKey {
+        self.key_with_modifiers(evdev_code, self.current_layout_idx, 0)
+    }
+
+    /// Resolve the text for an evdev keycode under the current state.
+    ///
+    /// [`TextMode::Plain`] behaves like [`Self::key_char`]. [`TextMode::Control`]
+    /// matches xkbcommon's `xkb_state_key_get_utf8`: Alt and Logo do not hide
+    /// the character, and an unconsumed Ctrl maps it to a control character,
+    /// so Ctrl+A yields U+0001 and Ctrl+[ yields ESC. When the key does not
+    /// produce ASCII, the first layout that does at the same level is used,
+    /// so Ctrl+C still works on a Cyrillic layout.
+    pub fn key_text(&self, evdev_code: u32, mode: TextMode) -> Option<KeyText> {
+        let plain = |ch| KeyText { ch, control: false };
+        if mode == TextMode::Plain {
+            return self.key_char(evdev_code).map(plain);
         }
-        if kb_layout.modifiers.caps_locked() {
-            if let Some(c) = kb_layout.caps_lock_keymap.get(base_level, evdev_code) {
-                return Some(c);
-            }
+        let ch = self.selected_char(evdev_code);
+        if !self.ctrl() || self.mod_is_consumed(evdev_code, MOD_CTRL, ConsumedMode::Xkb) {
+            return ch.map(plain);
         }
-        kb_layout.state_keymap.get(base_level, evdev_code)
+        #[cfg(feature = "xkb")]
+        let is_ascii = self.key_sym(evdev_code).raw() <= 0x7f;
+        #[cfg(not(feature = "xkb"))]
+        let is_ascii = ch.is_some_and(|ch| ch.is_ascii());
+        let ch = if is_ascii {
+            ch
+        } else {
+            let mods = self.effective_mods() as u8;
+            self.keymap
+                .layouts
+                .iter()
+                .find_map(|layout| layout.ascii_char(evdev_code, mods))
+                .or(ch)
+        };
+        ch.map(|ch| match control_char(ch) {
+            Some(ch) => KeyText { ch, control: true },
+            None => plain(ch),
+        })
+    }
+
+    /// Return the [`TextMode`] used for [`KeyResult::text`].
+    pub fn text_mode(&self) -> TextMode {
+        self.text_mode
+    }
+
+    /// Select the [`TextMode`] used for [`KeyResult::text`] by
+    /// [`Self::key_event`] and [`Self::repeat_key`].
+    pub fn set_text_mode(&mut self, mode: TextMode) {
+        self.text_mode = mode;
+    }
+
+    /// Resolve the logical identity of an evdev keycode under the current state.
+    ///
+    /// Returns [`LogicalKey::Named`] when [`Self::named_key`] has a mapping,
+    /// otherwise the character at the selected level. Unlike
+    /// [`Self::key_char`], Ctrl/Alt/Logo do not hide the character, so
+    /// Ctrl+A still reports `Character('a')`.
+    pub fn logical_key(&self, evdev_code: u32) -> LogicalKey {
+        match self.named_key(evdev_code) {
+            NamedKey::Unnamed => self
+                .selected_char(evdev_code)
+                .map_or(LogicalKey::Unidentified, LogicalKey::Character),
+            named => LogicalKey::Named(named),
+        }
+    }
+
+    /// Return the modifiers consumed to select the current level of a key,
+    /// like xkbcommon's `xkb_state_key_get_consumed_mods2`.
+    ///
+    /// Shortcut matching should ignore consumed modifiers, so that Shift+`=`
+    /// producing `+` matches a Ctrl++ binding. The result is a real modifier
+    /// mask in the same bit layout as [`Self::raw_modifiers`].
+    pub fn consumed_modifiers(&self, evdev_code: u32, mode: ConsumedMode) -> u32 {
+        let mods = self.effective_mods() as u8;
+        let kb_layout = self.layout();
+        kb_layout.key_types.get(evdev_code).map_or(0, |key_type| {
+            key_type.consumed(mods, mode, |a, b| {
+                kb_layout.same_level_syms(evdev_code, a.into(), b.into())
+            })
+        }) as u32
+    }
+
+    /// Return whether every modifier in `mask` is consumed by the key under
+    /// the current state. See [`Self::consumed_modifiers`].
+    pub fn mod_is_consumed(&self, evdev_code: u32, mask: u32, mode: ConsumedMode) -> bool {
+        mask != 0 && self.consumed_modifiers(evdev_code, mode) & mask == mask
+    }
+
+    /// Return whether the modifier at `idx` is active (depressed, latched or
+    /// locked), like xkbcommon's `xkb_state_mod_index_is_active` with
+    /// `XKB_STATE_MODS_EFFECTIVE`.
+    ///
+    /// A virtual modifier is active when all real modifiers it maps to are;
+    /// unknown and unmapped modifiers are never active.
+    pub fn mod_index_is_active(&self, idx: usize) -> bool {
+        let mapping = self
+            .keymap
+            .mods
+            .mods
+            .get(idx)
+            .map_or(0, |(_, mapping)| *mapping);
+        mapping != 0 && self.effective_mods() & mapping == mapping
+    }
+
+    /// Return whether the named modifier is active. See
+    /// [`Self::mod_index_is_active`].
+    pub fn mod_name_is_active(&self, name: &str) -> bool {
+        self.keymap
+            .mod_index(name)
+            .is_some_and(|idx| self.mod_index_is_active(idx))
     }
 
     /// Return whether the given modifier type is currently active.
     #[doc(hidden)]
     pub fn active_mod_type(&self, mod_type: ModType) -> bool {
-        self.layouts[self.current_layout_idx]
-            .modifiers
-            .active_mod_type(mod_type)
+        self.mods().active_mod_type(mod_type)
     }
 
     /// Return the keycode (and optional level) for the given modifier type.
     #[doc(hidden)]
     pub fn level_code(&self, mod_type: ModType) -> Option<(u32, Option<u8>)> {
-        let modifiers = &self.layouts[self.current_layout_idx].modifiers;
+        let modifiers = self.mods();
         let mut other_mod = None;
 
         for (code, modifier) in modifiers.iter() {
@@ -463,12 +1115,13 @@ impl WKB {
     /// replaced.
     #[cfg(feature = "compose")]
     pub fn set_compose_key(&mut self, evdev_code: u32) {
-        for layout in &mut self.layouts {
-            layout.modifiers.set_modifier(
+        for modifiers in &mut self.modifiers {
+            modifiers.set_modifier(
                 evdev_code,
                 Modifier::Single(StateModifier {
                     kind: ModKind::Press { pressed: false },
                     mod_type: ModType::Compose,
+                    flags: ModFlags::default(),
                 }),
             );
         }
@@ -498,26 +1151,119 @@ impl WKB {
         self.change_key_state(evdev_code, KeyDirection::Up)
     }
 
+    /// Return whether the given evdev keycode is held down.
+    pub fn is_key_down(&self, evdev_code: u32) -> bool {
+        self.groups.is_pressed(evdev_code)
+    }
+
+    /// Return the evdev keycodes held down, in ascending order.
+    pub fn pressed_keys(&self) -> impl Iterator<Item = u32> + '_ {
+        self.groups.pressed()
+    }
+
+    /// Release every held key, clear latched modifiers and groups, and reset
+    /// compose, returning to a neutral state on focus loss or VT switch.
+    /// Locked modifiers and groups are kept.
+    ///
+    /// Returns each released key with the changes its release caused, for
+    /// forwarding as `wl_keyboard.key` releases. The last key's changes
+    /// include the cleared latches.
+    pub fn release_all(&mut self) -> Vec<(u32, StateChanges)> {
+        let pressed: Vec<u32> = self.pressed_keys().collect();
+        let mut released: Vec<(u32, StateChanges)> = pressed
+            .into_iter()
+            .map(|evdev_code| (evdev_code, self.release_key(evdev_code)))
+            .collect();
+        let changes = self.clear_latches();
+        if let Some((_, last)) = released.last_mut() {
+            last.modifiers_updated |= changes.modifiers_updated;
+            last.leds_updated |= changes.leds_updated;
+        }
+        self.reset_state();
+        released
+    }
+
+    /// Drop latched modifiers and groups, such as those a release latched.
+    fn clear_latches(&mut self) -> StateChanges {
+        let before_modifiers = self.raw_modifiers();
+        let before_leds = self.leds_state();
+        let old_layout = self.current_layout_idx;
+        self.modifiers[old_layout].unlatch();
+        let new_layout = self.groups.unlatch(self.num_layouts());
+        if new_layout != old_layout {
+            let raw = self.modifiers[old_layout].state(new_layout);
+            self.modifiers[new_layout].update(raw.depressed, raw.latched, raw.locked);
+            self.current_layout_idx = new_layout;
+        }
+        StateChanges {
+            is_modifier: false,
+            modifiers_updated: self.raw_modifiers() != before_modifiers,
+            leds_updated: self.leds_state() != before_leds,
+        }
+    }
+
+    /// Process a key event and resolve everything a client needs for it.
+    ///
+    /// Identity, text, and compose state are resolved against the state
+    /// *before* the event, so a latched modifier still applies to the key that
+    /// consumes it; [`KeyResult::changes`] compares the state before and after.
+    /// Text and compose are only produced for [`KeyDirection::Down`].
+    pub fn key_event(&mut self, evdev_code: u32, key_direction: KeyDirection) -> KeyResult {
+        let mut result = self.resolve_key(evdev_code, key_direction == KeyDirection::Down);
+        result.changes = self.change_key_state(evdev_code, key_direction);
+        result
+    }
+
+    /// Resolve a repeated key press without changing modifier or group state.
+    ///
+    /// Repeats advance compose processing like an initial press.
+    pub fn repeat_key(&mut self, evdev_code: u32) -> KeyResult {
+        self.resolve_key(evdev_code, true)
+    }
+
+    fn resolve_key(&mut self, evdev_code: u32, typed: bool) -> KeyResult {
+        let key_text = typed
+            .then(|| self.key_text(evdev_code, self.text_mode))
+            .flatten();
+        let text = key_text.map(|text| text.ch);
+        #[cfg(feature = "compose")]
+        let compose = typed.then(|| self.compose(evdev_code)).flatten();
+        #[cfg(not(feature = "compose"))]
+        let compose = None;
+        let text = match &compose {
+            Some(ComposeState::Idle(_)) | None => text,
+            Some(ComposeState::Finished(output)) => output.char(),
+            Some(ComposeState::Composing(_) | ComposeState::Cancelled) => None,
+        };
+        let control_text = text.is_some() && key_text.is_some_and(|text| text.control);
+        KeyResult {
+            physical_key: self.physical_key(evdev_code),
+            logical_key: self.logical_key(evdev_code),
+            text,
+            control_text,
+            compose,
+            repeats: self.key_repeats(evdev_code),
+            changes: StateChanges::default(),
+        }
+    }
+
     #[inline]
     fn change_key_state(&mut self, evdev_code: u32, key_direction: KeyDirection) -> StateChanges {
         let before_modifiers = self.raw_modifiers();
         let before_leds = self.leds_state();
-        let layouts = self.layouts.len();
+        let layouts = self.num_layouts();
         let old_layout = self.current_layout_idx;
-        let kb_layout = &mut self.layouts[self.current_layout_idx];
-        let is_modifier = kb_layout.modifiers.set_state(evdev_code, key_direction);
+        let is_modifier = self.modifiers[old_layout].set_state(evdev_code, key_direction);
         let new_layout = self
             .groups
             .update(evdev_code, key_direction, !is_modifier, layouts);
         if new_layout != old_layout {
-            let raw = kb_layout.modifiers.state(new_layout);
-            self.layouts[new_layout]
-                .modifiers
-                .update(raw.depressed, raw.latched, raw.locked);
+            let raw = self.modifiers[old_layout].state(new_layout);
+            self.modifiers[new_layout].update(raw.depressed, raw.latched, raw.locked);
             self.current_layout_idx = new_layout;
         }
         if !is_modifier && key_direction == KeyDirection::Down {
-            self.layouts[self.current_layout_idx].modifiers.unlatch();
+            self.modifiers[self.current_layout_idx].unlatch();
         }
         StateChanges {
             is_modifier,
@@ -533,50 +1279,189 @@ impl WKB {
     /// by the preceding key press.
     #[cfg(feature = "compose")]
     pub fn compose(&mut self, evdev_code: u32) -> Option<ComposeState> {
-        let is_compose_key =
-            self.layouts[self.current_layout_idx]
-                .modifiers
+        let is_compose_key = self.mods().iter().any(|(code, modifier)| {
+            *code == evdev_code
+                && match modifier {
+                    Modifier::Single(modifier) => modifier.has_mod_type(ModType::Compose),
+                    Modifier::Leveled(levels) => levels
+                        .iter()
+                        .any(|(_, modifier)| modifier.has_mod_type(ModType::Compose)),
+                }
+        });
+        let input = if is_compose_key {
+            ComposeInput::Compose
+        } else {
+            #[cfg(feature = "xkb")]
+            let keysym = Some(self.key_sym(evdev_code).raw()).filter(|&sym| sym != 0);
+            #[cfg(not(feature = "xkb"))]
+            let keysym = None;
+            ComposeInput::Key {
+                keysym,
+                ch: self.key_char(evdev_code),
+            }
+        };
+        let idx = self.current_layout_idx;
+        let composer = self.composers[idx]
+            .as_deref()
+            .unwrap_or(&self.keymap.layouts[idx].composer);
+        composer.feed(
+            &mut self.compose_cursors[idx],
+            input,
+            self.compose_placeholder,
+        )
+    }
+
+    /// The compose trie of the current layout.
+    #[cfg(feature = "compose")]
+    fn composer(&self) -> &Composer {
+        let idx = self.current_layout_idx;
+        self.composers[idx]
+            .as_deref()
+            .unwrap_or(&self.keymap.layouts[idx].composer)
+    }
+
+    /// Preedit text of the compose sequence in progress on the current
+    /// layout, as last reported by [`ComposeState::Composing`].
+    ///
+    /// Dead keys show as spacing accents (`´` rather than U+0301) and the
+    /// Compose key as the placeholder set with
+    /// [`Self::set_compose_placeholder`].
+    #[cfg(feature = "compose")]
+    pub fn compose_preedit(&self) -> Option<ComposeString> {
+        self.compose_cursors[self.current_layout_idx].preedit()
+    }
+
+    /// Set the preedit glyph for the Compose key, `·` by default. `None`
+    /// leaves the Compose key out of the preedit text.
+    #[cfg(feature = "compose")]
+    pub fn set_compose_placeholder(&mut self, placeholder: Option<char>) {
+        self.compose_placeholder = placeholder;
+    }
+
+    /// The keys that can follow the compose sequence in progress on the
+    /// current layout, or start one when none is, with the output of those
+    /// that finish it.
+    #[cfg(feature = "compose")]
+    pub fn compose_completions(&self) -> Vec<ComposeCompletion> {
+        let cursor = &self.compose_cursors[self.current_layout_idx];
+        self.composer()
+            .completions(cursor, self.compose_placeholder)
+    }
+
+    /// Every sequence the current layout's compose table can finish from the
+    /// sequence in progress, or every sequence when none is, with the keys
+    /// still to press.
+    #[cfg(feature = "compose")]
+    pub fn compose_candidates(&self) -> Vec<ComposeSequence> {
+        self.composer()
+            .sequences(&self.compose_cursors[self.current_layout_idx])
+    }
+
+    /// Ways to type `ch` on the current layout, shortest first: single keys
+    /// with the modifiers to hold, then compose sequences through the
+    /// Compose key, dead keys and the other keys of the layout. Empty when
+    /// `ch` cannot be typed.
+    pub fn key_sequences(&self, ch: char) -> Vec<Vec<Keystroke>> {
+        self.layout_key_sequences(self.current_layout_idx, ch)
+    }
+
+    /// [`Self::key_sequences`] on the layout at index `layout_idx`.
+    pub(crate) fn layout_key_sequences(&self, layout_idx: usize, ch: char) -> Vec<Vec<Keystroke>> {
+        let layout = &self.keymap.layouts[layout_idx];
+        let ways: Vec<Vec<Keystroke>> = layout
+            .keystrokes(|code, level| layout.state_keymap.get(level, code) == Some(ch))
+            .into_iter()
+            .map(|keystroke| vec![keystroke])
+            .collect();
+        #[cfg(feature = "compose")]
+        let ways = {
+            let mut ways = ways;
+            let composer = self.composers[layout_idx]
+                .as_deref()
+                .unwrap_or(&layout.composer);
+            let modifiers = &self.modifiers[layout_idx];
+            let sequences = composer.sequences(&ComposeCursor::default());
+            let typed = sequences
                 .iter()
-                .any(|(code, modifier)| {
-                    *code == evdev_code
-                        && match modifier {
-                            Modifier::Single(modifier) => modifier.has_mod_type(ModType::Compose),
-                            Modifier::Leveled(levels) => levels
-                                .iter()
-                                .any(|(_, modifier)| modifier.has_mod_type(ModType::Compose)),
-                        }
+                .filter(|sequence| sequence.output.char() == Some(ch))
+                .filter_map(|sequence| {
+                    sequence
+                        .keys
+                        .iter()
+                        .map(|&key| layout.compose_keystroke(key, modifiers))
+                        .collect::<Option<Vec<_>>>()
                 });
-        let token = if is_compose_key {
-            Token::Compose
-        } else {
-            Token::Char(self.key_char(evdev_code)?)
+            ways.extend(typed);
+            ways.sort_by_key(Vec::len);
+            ways.dedup();
+            ways
         };
-        Some(self.layouts[self.current_layout_idx].composer.feed(token))
+        ways
+    }
+
+    /// Use `table` for compose processing on `layout`, or on every layout
+    /// when `None`, instead of the keymap's Compose table. Pending sequences
+    /// on those layouts are cancelled.
+    ///
+    /// With the table's reachability filter enabled, only sequences whose
+    /// characters the layout can type are kept.
+    #[cfg(feature = "compose")]
+    pub fn set_compose_table(
+        &mut self,
+        table: &ComposeTable,
+        layout: Option<usize>,
+    ) -> Result<(), WkbError> {
+        let unfiltered = (!table.reachability_filter()).then(|| Arc::new(table.build(None)));
+        for idx in self.compose_layouts(layout)? {
+            let composer = unfiltered.clone().unwrap_or_else(|| {
+                let reachable = self.keymap.layouts[idx].reachable();
+                Arc::new(table.build(Some(&reachable)))
+            });
+            self.composers[idx] = Some(composer);
+            self.compose_cursors[idx].reset();
+        }
+        Ok(())
+    }
+
+    /// Go back to the keymap's Compose table on `layout`, or on every layout
+    /// when `None`.
+    #[cfg(feature = "compose")]
+    pub fn reset_compose_table(&mut self, layout: Option<usize>) -> Result<(), WkbError> {
+        for idx in self.compose_layouts(layout)? {
+            self.composers[idx] = None;
+            self.compose_cursors[idx].reset();
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "compose")]
+    fn compose_layouts(&self, layout: Option<usize>) -> Result<std::ops::Range<usize>, WkbError> {
+        match layout {
+            None => Ok(0..self.num_layouts()),
+            Some(idx) if idx < self.num_layouts() => Ok(idx..idx + 1),
+            Some(idx) => Err(WkbError::InvalidLayout(idx)),
+        }
     }
 
     /// Export a layout as an [`ir::LayoutFile`] for persistence. This is the
     /// generation path for wkb layout data files.
+    ///
+    /// Modifier keys reflect this state, including any added with
+    /// [`Self::set_compose_key`].
     pub fn export_layout(&self, layout_idx: usize) -> Result<ir::LayoutFile, ir::IrError> {
         let layout = self
+            .keymap
             .layouts
             .get(layout_idx)
             .ok_or(ir::IrError::InvalidLayoutIndex(layout_idx))?;
-        ir::LayoutFile::try_from(layout)
+        ir::LayoutFile::from_layout(layout, &self.modifiers[layout_idx])
     }
 
     /// Rebuild a [`WKB`] from one or more [`ir::LayoutFile`]s. Each file
     /// becomes one layout group, in order. This is the loading path for
     /// standalone wkb without XKB compilation.
     pub fn new_from_layouts(files: Vec<ir::LayoutFile>) -> Result<Self, ir::IrError> {
-        let mut layouts = Vec::with_capacity(files.len());
-        for file in files {
-            layouts.push(KBLayout::try_from(file)?);
-        }
-        Ok(WKB {
-            current_layout_idx: 0,
-            layouts,
-            groups: Groups::default(),
-        })
+        Keymap::new_from_layouts(files).map(|keymap| Self::new(Arc::new(keymap)))
     }
 }
 
@@ -590,3 +1475,83 @@ pub struct StateChanges {
     pub leds_updated: bool,
     pub is_modifier: bool,
 }
+
+/// Everything resolved for a single key event by [`WKB::key_event`] or
+/// [`WKB::repeat_key`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyResult {
+    /// Layout-independent physical position.
+    pub physical_key: PhysicalKey,
+    /// Logical identity under the state before the event.
+    pub logical_key: LogicalKey,
+    /// Text to commit after compose processing. `None` on release, while a
+    /// compose sequence is pending, or while Ctrl/Alt/Logo are held in
+    /// [`TextMode::Plain`]. See [`WKB::set_text_mode`]. Also `None` when a
+    /// compose sequence produces several characters; see
+    /// [`KeyResult::commit_text`].
+    pub text: Option<char>,
+    /// Whether `text` is a control character produced from a Ctrl chord in
+    /// [`TextMode::Control`].
+    pub control_text: bool,
+    /// Compose processing result, or `None` when the key is not fed to the
+    /// composer.
+    pub compose: Option<ComposeState>,
+    /// Whether the key repeats when held.
+    pub repeats: bool,
+    /// Modifier, LED, and group changes caused by the event.
+    pub changes: StateChanges,
+}
+
+impl KeyResult {
+    /// Text to commit for this event: the output of a finished compose
+    /// sequence, which may be several characters, or else [`Self::text`].
+    pub fn commit_text(&self) -> Option<std::borrow::Cow<'_, str>> {
+        match &self.compose {
+            Some(ComposeState::Finished(output)) => Some(output.text.as_ref().into()),
+            _ => self.text.map(|ch| ch.to_string().into()),
+        }
+    }
+}
+
+/// How [`WKB::key_text`] and [`KeyResult::text`] treat modifier chords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextMode {
+    /// No text while Ctrl, Alt or Logo are active.
+    #[default]
+    Plain,
+    /// Text as xkbcommon produces it, with the XKB Ctrl transformation to
+    /// control characters. Suited to terminal emulators.
+    Control,
+}
+
+/// A character resolved by [`WKB::key_text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyText {
+    pub ch: char,
+    /// Whether `ch` was produced by the Ctrl transformation.
+    pub control: bool,
+}
+
+/// A key press in a way to type a character, found by
+/// [`WKB::key_sequences`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keystroke {
+    pub evdev_code: u32,
+    /// Modifiers to hold while pressing the key, in the bit layout of
+    /// [`WKB::raw_modifiers`].
+    pub mods: u32,
+}
+
+/// The XKB Ctrl transformation of an ASCII character (`XkbToControl`).
+fn control_char(ch: char) -> Option<char> {
+    let c = u8::try_from(ch).ok().filter(u8::is_ascii)?;
+    let c = match c {
+        b'@'..=b'~' | b' ' => c & 0x1f,
+        b'2' => 0,
+        b'3'..=b'7' => c - (b'3' - 0x1b),
+        b'8' => 0x7f,
+        b'/' => b'_' & 0x1f,
+        _ => return None,
+    };
+    Some(c as char)
+}
@@ -36,13 +36,164 @@ pub const CAPS_LOCK: u32 = 58;
 pub const NUM_LOCK: u32 = 69;
 pub const SCROLL_LOCK: u32 = 70;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Names of the eight real modifiers, in bit order.
+pub(crate) const REAL_MOD_NAMES: [&str; 8] = [
+    "Shift", "Lock", "Control", "Mod1", "Mod2", "Mod3", "Mod4", "Mod5",
+];
+
+/// Virtual modifiers of the default evdev keymap and their real mappings,
+/// used for layouts loaded without an XKB keymap.
+const DEFAULT_VIRTUAL_MODS: [(&str, u32); 7] = [
+    ("NumLock", MOD_NUM_LOCK),
+    ("Alt", MOD_ALT),
+    ("LevelThree", MOD_ALTGR),
+    ("LevelFive", MOD_SCROLL_LOCK),
+    ("Meta", MOD_ALT),
+    ("Super", MOD_LOGO),
+    ("Hyper", MOD_LOGO),
+];
+
+/// The modifiers a keymap declares: real modifiers first, then virtual ones,
+/// each with the real modifier mask it maps to.
+///
+/// Indices match the keymap's own modifier indices, so bit `i` of a
+/// `wl_keyboard.modifiers` mask refers to modifier `i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ModMap {
+    pub(crate) mods: Vec<(String, u32)>,
+}
+
+impl Default for ModMap {
+    fn default() -> Self {
+        let real = REAL_MOD_NAMES
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.to_string(), 1 << idx));
+        let virtual_mods = DEFAULT_VIRTUAL_MODS
+            .iter()
+            .map(|(name, mapping)| (name.to_string(), *mapping));
+        Self {
+            mods: real.chain(virtual_mods).collect(),
+        }
+    }
+}
+
+impl ModMap {
+    pub(crate) fn index(&self, name: &str) -> Option<usize> {
+        self.mods.iter().position(|(mod_name, _)| mod_name == name)
+    }
+
+    pub(crate) fn name(&self, idx: usize) -> Option<&str> {
+        self.mods.get(idx).map(|(name, _)| name.as_str())
+    }
+
+    /// Real modifier mask of the named modifier, or `0` when it is unknown or
+    /// unmapped.
+    pub(crate) fn mask(&self, name: &str) -> u32 {
+        self.index(name).map_or(0, |idx| self.mods[idx].1)
+    }
+
+    /// Fold a mask in the keymap's mask layout down to real modifiers.
+    pub(crate) fn resolve(&self, mask: u32) -> u32 {
+        self.mods
+            .iter()
+            .enumerate()
+            .skip(REAL_MOD_NAMES.len())
+            .filter(|(idx, _)| mask & (1 << idx) != 0)
+            .fold(mask & 0xff, |acc, (_, (_, mapping))| acc | mapping)
+    }
+
+    /// Fill in virtual modifiers the keymap left unmapped with their usual
+    /// evdev mapping.
+    #[cfg(feature = "xkb")]
+    pub(crate) fn with_default_mappings(mut self) -> Self {
+        for (name, mapping) in &mut self.mods {
+            if *mapping == 0 {
+                if let Some((_, default)) = DEFAULT_VIRTUAL_MODS.iter().find(|(n, _)| n == name) {
+                    *mapping = *default;
+                }
+            }
+        }
+        self
+    }
+
+    /// Real masks of the modifiers wkb gives a special role.
+    ///
+    /// Roles the keymap does not map fall back to their usual evdev modifier,
+    /// unless the keymap already maps another role to it.
+    pub(crate) fn masks(&self) -> ModMasks {
+        let mapped = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| self.mask(name))
+                .find(|&mask| mask != 0)
+                .unwrap_or(0)
+        };
+        let roles: [(&[&str], u32); 6] = [
+            (&["Alt", "Meta"], MOD_ALT),
+            (&["NumLock"], MOD_NUM_LOCK),
+            (&["Super"], MOD_LOGO),
+            (&["LevelThree"], MOD_ALTGR),
+            (&["LevelFive"], MOD_SCROLL_LOCK),
+            (&["ScrollLock"], MOD_SCROLL_LOCK),
+        ];
+        let claimed = roles.iter().fold(0, |acc, (names, _)| acc | mapped(names));
+        let [alt, num, logo, level3, level5, scroll] = roles.map(|(names, fallback)| {
+            let mask = mapped(names);
+            if mask != 0 {
+                mask
+            } else {
+                fallback & !claimed
+            }
+        });
+        ModMasks {
+            alt,
+            num,
+            logo,
+            level3,
+            level5,
+            scroll,
+        }
+    }
+}
+
+/// Real modifier masks for the roles wkb tracks. Shift, Lock and Control are
+/// always the first three real modifiers; the others follow the keymap's
+/// virtual modifier mappings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ModMasks {
+    pub(crate) alt: u32,
+    pub(crate) num: u32,
+    pub(crate) logo: u32,
+    pub(crate) level3: u32,
+    pub(crate) level5: u32,
+    pub(crate) scroll: u32,
+}
+
+impl Default for ModMasks {
+    fn default() -> Self {
+        ModMap::default().masks()
+    }
+}
+
+impl ModMasks {
+    /// Modifiers that turn a key press into a shortcut chord. A modifier
+    /// that selects a shift level never does.
+    #[inline]
+    pub(crate) fn shortcut(&self) -> u32 {
+        (MOD_CTRL | self.alt | self.logo) & !(self.level3 | self.level5)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyDirection {
     Up,
     Down,
 }
 
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub struct RawModifiers {
     pub depressed: u32,
     pub latched: u32,
@@ -69,16 +220,45 @@ pub enum ModType {
     Scroll,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ModKind {
-    Press { pressed: bool },
-    Lock { pressed: bool, locked: u8 },
-    UnlockOnPress { pressed: bool, locked: bool },
-    Latch { pressed: bool, latched: bool },
+    Press {
+        pressed: bool,
+    },
+    Lock {
+        pressed: bool,
+        locked: u8,
+    },
+    UnlockOnPress {
+        pressed: bool,
+        locked: bool,
+    },
+    Latch {
+        pressed: bool,
+        latched: bool,
+        locked: u8,
+    },
+}
+
+/// Behaviour flags of the keymap action bound to a modifier key, following
+/// the `clearLocks`, `latchToLock` and `affect` fields of xkb mod actions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ModFlags {
+    /// Releasing the key without another key pressed in between unlocks its
+    /// modifiers.
+    pub(crate) clear_locks: bool,
+    /// Pressing a latch key while it is latched locks it instead.
+    pub(crate) latch_to_lock: bool,
+    /// Lock keys never lock, only unlock (`affect=unlock`).
+    pub(crate) no_lock: bool,
+    /// Lock keys never unlock, only lock (`affect=lock`).
+    pub(crate) no_unlock: bool,
 }
 
 impl ModKind {
-    pub fn update(&mut self, key_direction: KeyDirection) {
+    /// `tapped` tells a release apart from one with other keys pressed since
+    /// the key went down.
+    pub(crate) fn update(&mut self, key_direction: KeyDirection, flags: ModFlags, tapped: bool) {
         match self {
             ModKind::Press { ref mut pressed } => match key_direction {
                 KeyDirection::Down => *pressed = true,
@@ -90,13 +270,13 @@ impl ModKind {
             } => match key_direction {
                 KeyDirection::Down => {
                     *pressed = true;
-                    if *locked == 0 {
+                    if *locked == 0 && !flags.no_lock {
                         *locked = 2;
                     }
                 }
                 KeyDirection::Up => {
                     *pressed = false;
-                    if *locked != 0 {
+                    if *locked == 2 || (*locked == 1 && !flags.no_unlock) {
                         *locked -= 1;
                     }
                 }
@@ -111,28 +291,46 @@ impl ModKind {
             ModKind::Latch {
                 ref mut pressed,
                 ref mut latched,
+                ref mut locked,
             } => match key_direction {
+                // While the key is held, `latched` means the latch is pending:
+                // it only takes effect if the key is released untouched.
                 KeyDirection::Down => {
                     *pressed = true;
-                    *latched = !*latched;
+                    if *latched && flags.latch_to_lock {
+                        *latched = false;
+                        *locked = 2;
+                    } else {
+                        *latched = !*latched;
+                    }
                 }
                 KeyDirection::Up => {
                     *pressed = false;
+                    if !tapped {
+                        *latched = false;
+                    }
+                    if *locked == 2 {
+                        *locked = 1;
+                    }
                 }
             },
         }
     }
 
     fn unlatch(&mut self) {
-        if let ModKind::Latch {
-            pressed: _,
-            latched,
-        } = self
-        {
+        if let ModKind::Latch { latched, .. } = self {
             *latched = false
         }
     }
 
+    fn unlock(&mut self) {
+        match self {
+            ModKind::Lock { locked, .. } | ModKind::Latch { locked, .. } => *locked = 0,
+            ModKind::UnlockOnPress { locked, .. } => *locked = false,
+            ModKind::Press { .. } => {}
+        }
+    }
+
     pub fn pressed(&self) -> bool {
         match self {
             ModKind::Press { pressed, .. }
@@ -146,14 +344,18 @@ impl ModKind {
         match self {
             ModKind::Press { pressed } => *pressed,
             ModKind::Lock { pressed, .. } => *pressed,
-            ModKind::Latch { pressed, latched } => *pressed && *latched,
+            // A press that cancels the latch or turns it into a lock sets
+            // nothing.
+            ModKind::Latch {
+                pressed, latched, ..
+            } => *pressed && *latched,
             ModKind::UnlockOnPress { pressed, locked } => *pressed && *locked,
         }
     }
 
     pub fn locked(&self) -> bool {
         match self {
-            ModKind::Lock { locked, .. } => *locked > 0,
+            ModKind::Lock { locked, .. } | ModKind::Latch { locked, .. } => *locked > 0,
             ModKind::UnlockOnPress { locked, .. } => *locked,
             _ => false,
         }
@@ -161,7 +363,9 @@ impl ModKind {
 
     pub fn latched(&self) -> bool {
         match self {
-            ModKind::Latch { latched, .. } => *latched,
+            ModKind::Latch {
+                pressed, latched, ..
+            } => *latched && !*pressed,
             _ => false,
         }
     }
@@ -171,6 +375,7 @@ impl ModKind {
 pub(crate) struct StateModifier {
     pub(crate) mod_type: ModType,
     pub(crate) kind: ModKind,
+    pub(crate) flags: ModFlags,
 }
 
 impl StateModifier {
@@ -182,8 +387,8 @@ impl StateModifier {
         self.kind.unlatch();
     }
 
-    pub fn update(&mut self, key_direction: KeyDirection) {
-        self.kind.update(key_direction);
+    pub(crate) fn update(&mut self, key_direction: KeyDirection, tapped: bool) {
+        self.kind.update(key_direction, self.flags, tapped);
     }
 }
 
@@ -213,12 +418,26 @@ impl Modifier {
 pub struct Modifiers {
     /// Flat array of (evdev_code, Modifier) pairs. Typically 10-20 entries.
     pub(crate) entries: Vec<(u32, Modifier)>,
+    /// Real modifier masks of the keymap these modifiers belong to.
+    pub(crate) masks: ModMasks,
+    /// Real modifiers set by Ctrl/Alt/Logo-style keys, from the keymap's
+    /// `modifier_map`. Keys not listed fall back to their usual bit.
+    pub(crate) key_masks: Vec<(u32, u32)>,
+    /// The key pressed last while it is still held, so that a release can
+    /// tell whether another key was pressed in between.
+    tap: Option<u32>,
     raw: RawModifiers,
 }
 
 impl Default for Modifiers {
     fn default() -> Self {
-        let single = |mod_type, kind| Modifier::Single(StateModifier { mod_type, kind });
+        let single = |mod_type, kind| {
+            Modifier::Single(StateModifier {
+                mod_type,
+                kind,
+                flags: ModFlags::default(),
+            })
+        };
         let press = |mod_type| single(mod_type, ModKind::Press { pressed: false });
         let lock = |mod_type| {
             single(
@@ -243,6 +462,9 @@ impl Default for Modifiers {
         ];
         Self {
             entries,
+            masks: ModMasks::default(),
+            key_masks: Vec::new(),
+            tap: None,
             raw: RawModifiers::default(),
         }
     }
@@ -252,6 +474,9 @@ impl Modifiers {
     pub fn new() -> Self {
         Self {
             entries: Vec::with_capacity(MAX_MOD_SLOTS),
+            masks: ModMasks::default(),
+            key_masks: Vec::new(),
+            tap: None,
             raw: RawModifiers::default(),
         }
     }
@@ -280,6 +505,87 @@ impl Modifiers {
         self.rebuild_raw();
     }
 
+    /// Make every modifier that `defaults` defines as a plain press latch
+    /// instead, locking on a second tap and unlocking on a third, as AccessX
+    /// StickyKeys does; or restore them from `defaults`. Held keys stay held.
+    pub(crate) fn set_sticky(&mut self, defaults: &Modifiers, sticky: bool) {
+        for (code, modifier) in &mut self.entries {
+            let default = defaults.get(*code);
+            match modifier {
+                Modifier::Single(state_modifier) => {
+                    if let Some(Modifier::Single(default)) = default {
+                        set_sticky(state_modifier, default, sticky);
+                    }
+                }
+                Modifier::Leveled(levels) => {
+                    for (level, state_modifier) in levels {
+                        if let Some(Modifier::Leveled(default)) = default {
+                            if let Some(default) = default.get(level) {
+                                set_sticky(state_modifier, default, sticky);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        self.rebuild_raw();
+    }
+
+    /// Key state of every modifier, for [`crate::StateSnapshot`].
+    pub(crate) fn snapshot(&self) -> ModifiersSnapshot {
+        let keys = self
+            .entries
+            .iter()
+            .map(|(code, modifier)| {
+                let mut kinds = Vec::new();
+                modifier.for_each(|state_modifier| kinds.push(state_modifier.kind));
+                (*code, kinds)
+            })
+            .collect();
+        ModifiersSnapshot {
+            keys,
+            tap: self.tap,
+            raw: self.raw,
+        }
+    }
+
+    /// Install a [`Self::snapshot`] taken from modifiers of the same keymap.
+    /// Returns `false`, changing nothing, if the modifier keys or their
+    /// kinds differ.
+    pub(crate) fn restore(&mut self, snapshot: &ModifiersSnapshot) -> bool {
+        let matches = self.entries.len() == snapshot.keys.len()
+            && self.entries.iter().zip(&snapshot.keys).all(
+                |((code, modifier), (snapshot_code, kinds))| {
+                    let mut current = Vec::new();
+                    modifier.for_each(|state_modifier| {
+                        current.push(std::mem::discriminant(&state_modifier.kind))
+                    });
+                    code == snapshot_code
+                        && current
+                            .into_iter()
+                            .eq(kinds.iter().map(std::mem::discriminant))
+                },
+            );
+        if !matches {
+            return false;
+        }
+        for ((_, modifier), (_, kinds)) in self.entries.iter_mut().zip(&snapshot.keys) {
+            let mut kinds = kinds.iter();
+            modifier.for_each_mut(|state_modifier| {
+                state_modifier.kind = *kinds.next().unwrap();
+            });
+        }
+        self.tap = snapshot.tap;
+        self.raw = snapshot.raw;
+        true
+    }
+
+    /// Remove the modifier of the given evdev code, if any.
+    pub(crate) fn remove_modifier(&mut self, evdev_code: u32) {
+        self.entries.retain(|(c, _)| *c != evdev_code);
+        self.rebuild_raw();
+    }
+
     #[inline]
     fn effective(&self) -> u32 {
         self.raw.depressed | self.raw.latched | self.raw.locked
@@ -287,13 +593,13 @@ impl Modifiers {
 
     pub fn active_mod_type(&self, mod_type: ModType) -> bool {
         match mod_type {
-            ModType::None => self.effective() & (MOD_CTRL | MOD_ALT | MOD_LOGO) != 0,
+            ModType::None => self.effective() & self.masks.shortcut() != 0,
             ModType::Level2 => self.effective() & MOD_SHIFT != 0,
-            ModType::Level3 => self.effective() & MOD_ALTGR != 0,
-            ModType::Level5 => self.effective() & MOD_SCROLL_LOCK != 0,
+            ModType::Level3 => self.effective() & self.masks.level3 != 0,
+            ModType::Level5 => self.effective() & self.masks.level5 != 0,
             ModType::Caps => self.raw.locked & MOD_CAPS_LOCK != 0,
-            ModType::Num => self.raw.locked & MOD_NUM_LOCK != 0,
-            ModType::Scroll => self.raw.locked & MOD_SCROLL_LOCK != 0,
+            ModType::Num => self.raw.locked & self.masks.num != 0,
+            ModType::Scroll => self.raw.locked & self.masks.scroll != 0,
             ModType::Compose => self.entries.iter().any(|(_, modifier)| {
                 let mut active = false;
                 modifier.for_each(|state_modifier| {
@@ -307,26 +613,16 @@ impl Modifiers {
 
     #[inline(always)]
     pub fn active_none_and_levels(&self) -> (bool, bool, bool, bool) {
-        let effective = self.raw.depressed | self.raw.latched | self.raw.locked;
+        let effective = self.effective();
 
         (
-            effective & (MOD_CTRL | MOD_ALT | MOD_LOGO) != 0,
+            effective & self.masks.shortcut() != 0,
             effective & MOD_SHIFT != 0,
-            effective & MOD_ALTGR != 0,
-            effective & MOD_SCROLL_LOCK != 0,
+            effective & self.masks.level3 != 0,
+            effective & self.masks.level5 != 0,
         )
     }
 
-    #[inline]
-    pub fn caps_locked(&self) -> bool {
-        self.raw.locked & MOD_CAPS_LOCK != 0
-    }
-
-    #[inline]
-    pub fn num_locked(&self) -> bool {
-        self.raw.locked & MOD_NUM_LOCK != 0
-    }
-
     pub fn unlatch(&mut self) {
         self.entries
             .iter_mut()
@@ -335,37 +631,58 @@ impl Modifiers {
     }
 
     pub(crate) fn set_state(&mut self, evdev_code: u32, key_direction: KeyDirection) -> bool {
+        let tapped = self.tap == Some(evdev_code);
+        self.tap = (key_direction == KeyDirection::Down).then_some(evdev_code);
         let position = match self.entries.iter().position(|(c, _)| *c == evdev_code) {
             Some(p) => p,
             None => return false,
         };
-        let is_leveled = matches!(&self.entries[position].1, Modifier::Leveled(_));
-        let is_modifier = if is_leveled {
-            let (_, level2, level3, level5) = self.active_none_and_levels();
-
-            let level = level_index(level5, level3, level2) as u8;
-
-            let Modifier::Leveled(levels) = &mut self.entries[position].1 else {
-                unreachable!();
-            };
-
-            let Some(modifier) = levels.get_mut(&level) else {
-                return false;
-            };
-
-            modifier.update(key_direction);
-            !modifier.has_mod_type(ModType::Compose)
-        } else {
-            let Modifier::Single(modifier) = &mut self.entries[position].1 else {
-                unreachable!();
-            };
-
-            modifier.update(key_direction);
-            !modifier.has_mod_type(ModType::Compose)
+        let (_, level2, level3, level5) = self.active_none_and_levels();
+        let level = level_index(level5, level3, level2) as u8;
+        let modifier = match &mut self.entries[position].1 {
+            Modifier::Single(modifier) => modifier,
+            Modifier::Leveled(levels) => match levels.get_mut(&level) {
+                Some(modifier) => modifier,
+                None => return false,
+            },
         };
+        let just_locked = matches!(modifier.kind, ModKind::Latch { locked: 2, .. });
+        modifier.update(key_direction, tapped);
+        let mod_type = modifier.mod_type;
+        let clear_locks = key_direction == KeyDirection::Up && tapped && modifier.flags.clear_locks;
+        if clear_locks {
+            let mask = self.modifier_mask(evdev_code, mod_type);
+            let modifier = match &mut self.entries[position].1 {
+                Modifier::Single(modifier) => modifier,
+                Modifier::Leveled(levels) => levels.get_mut(&level).expect("updated above"),
+            };
+            match modifier.kind {
+                ModKind::Press { .. } => self.clear_locks(mask),
+                // Tapping a latch key while its modifiers are locked unlocks
+                // them rather than latching.
+                ModKind::Latch { .. } if !just_locked && self.raw.locked & mask == mask => {
+                    modifier.unlatch();
+                    self.clear_locks(mask);
+                }
+                _ => {}
+            }
+        }
 
         self.rebuild_raw();
-        is_modifier
+        mod_type != ModType::Compose
+    }
+
+    /// Unlock every modifier key setting any of `mask`, as a tapped
+    /// `clearLocks` key does.
+    fn clear_locks(&mut self, mask: u32) {
+        let (masks, key_masks) = (&self.masks, &self.key_masks);
+        for (code, modifier) in &mut self.entries {
+            modifier.for_each_mut(|state_modifier| {
+                if modifier_mask(masks, key_masks, *code, state_modifier.mod_type) & mask != 0 {
+                    state_modifier.kind.unlock();
+                }
+            });
+        }
     }
 
     pub fn state(&self, layout_index: usize) -> RawModifiers {
@@ -394,7 +711,7 @@ impl Modifiers {
 
         for (code, modifier) in &self.entries {
             modifier.for_each(|state_modifier| {
-                let mask = modifier_mask(*code, state_modifier.mod_type);
+                let mask = self.modifier_mask(*code, state_modifier.mod_type);
 
                 if mask == 0 {
                     return;
@@ -418,21 +735,64 @@ impl Modifiers {
 
     pub(crate) fn leds_state(&self) -> LedState {
         LedState {
-            num_lock: self.raw.locked & MOD_NUM_LOCK != 0,
+            num_lock: self.raw.locked & self.masks.num != 0,
             caps_lock: self.raw.locked & MOD_CAPS_LOCK != 0,
-            scroll_lock: self.raw.locked & MOD_SCROLL_LOCK != 0,
+            scroll_lock: self.raw.locked & self.masks.scroll != 0,
         }
     }
+
+    fn modifier_mask(&self, code: u32, mod_type: ModType) -> u32 {
+        modifier_mask(&self.masks, &self.key_masks, code, mod_type)
+    }
+}
+
+/// Key state of a [`Modifiers`]: each key's [`ModKind`] in entry order,
+/// one per level for leveled keys.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ModifiersSnapshot {
+    keys: Vec<(u32, Vec<ModKind>)>,
+    tap: Option<u32>,
+    raw: RawModifiers,
+}
+
+fn set_sticky(modifier: &mut StateModifier, default: &StateModifier, sticky: bool) {
+    if !matches!(default.kind, ModKind::Press { .. }) || default.mod_type == ModType::Compose {
+        return;
+    }
+    let pressed = modifier.kind.pressed();
+    *modifier = *default;
+    if sticky {
+        modifier.kind = ModKind::Latch {
+            pressed,
+            latched: false,
+            locked: 0,
+        };
+        modifier.flags.latch_to_lock = true;
+        modifier.flags.clear_locks = true;
+    } else {
+        modifier.kind = ModKind::Press { pressed };
+    }
 }
 
-fn modifier_mask(code: u32, mod_type: ModType) -> u32 {
+/// Real modifiers a modifier key sets. Keys with an entry in `key_masks`
+/// set exactly those; the rest follow their role.
+fn modifier_mask(masks: &ModMasks, key_masks: &[(u32, u32)], code: u32, mod_type: ModType) -> u32 {
+    if mod_type == ModType::Compose {
+        return 0;
+    }
+    if let Some((_, mask)) = key_masks
+        .iter()
+        .find(|(mapped_code, _)| *mapped_code == code)
+    {
+        return *mask;
+    }
     match mod_type {
         ModType::Level2 => MOD_SHIFT,
-        ModType::Level3 => MOD_ALTGR,
-        ModType::Level5 => MOD_SCROLL_LOCK,
+        ModType::Level3 => masks.level3,
+        ModType::Level5 => masks.level5,
         ModType::Caps => MOD_CAPS_LOCK,
-        ModType::Num => MOD_NUM_LOCK,
-        ModType::Scroll => MOD_SCROLL_LOCK,
+        ModType::Num => masks.num,
+        ModType::Scroll => masks.scroll,
         ModType::Compose => 0,
 
         // None is used for Ctrl, Alt and Logo. Preserve their distinct
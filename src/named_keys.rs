@@ -161,3 +161,17 @@ pub enum NamedKey {
     // Korean input (1)
     HangulHanja,
 }
+
+/// Logical identity of a key under the current layout and modifier state.
+///
+/// Named keys take precedence, so Space, Enter and Tab report
+/// [`LogicalKey::Named`] even though they also produce text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogicalKey {
+    /// The key produces this character at the selected level.
+    Character(char),
+    /// The key has a semantic, non-character identity.
+    Named(NamedKey),
+    /// The key has neither a character nor a named mapping.
+    Unidentified,
+}
@@ -0,0 +1,411 @@
+//! Per-state key remapping on top of the shared keymap.
+//!
+//! Remapping edits a private copy of the [`Keymap`], made on the first
+//! change, so other states sharing the keymap are unaffected and
+//! [`WKB::as_xkb_string`] serializes the remapped keys.
+
+use std::sync::Arc;
+
+use crate::flat_keymap::MAX_LEVELS;
+use crate::groups::Group;
+use crate::key_types::{synthesize_key_type, KeyType};
+use crate::modifiers::Modifier;
+use crate::named_keys::NamedKey;
+#[cfg(feature = "xkb")]
+use crate::Keysym;
+use crate::{KBLayout, Keymap, WkbError, WKB};
+
+/// Everything one layout defines for a key.
+#[derive(Debug, Clone)]
+struct LayoutKey {
+    repeats: bool,
+    modifier: Option<Modifier>,
+    key_mask: Option<u32>,
+    /// Base characters, then the Caps Lock, Num Lock and Caps+Num Lock
+    /// overrides.
+    chars: [[Option<char>; MAX_LEVELS]; 4],
+    named: [NamedKey; MAX_LEVELS],
+    key_type: Option<KeyType>,
+    #[cfg(feature = "xkb")]
+    level_chars: [Option<char>; MAX_LEVELS],
+    #[cfg(feature = "xkb")]
+    keysyms: [Keysym; MAX_LEVELS],
+    #[cfg(feature = "xkb")]
+    level_keysyms: [Keysym; MAX_LEVELS],
+    #[cfg(feature = "xkb")]
+    level_keysym_lists: Vec<(usize, Box<[Keysym]>)>,
+}
+
+impl LayoutKey {
+    fn disabled() -> Self {
+        Self {
+            repeats: false,
+            modifier: None,
+            key_mask: None,
+            chars: [[None; MAX_LEVELS]; 4],
+            named: [NamedKey::Unnamed; MAX_LEVELS],
+            key_type: None,
+            #[cfg(feature = "xkb")]
+            level_chars: [None; MAX_LEVELS],
+            #[cfg(feature = "xkb")]
+            keysyms: [Keysym::NO_SYMBOL; MAX_LEVELS],
+            #[cfg(feature = "xkb")]
+            level_keysyms: [Keysym::NO_SYMBOL; MAX_LEVELS],
+            #[cfg(feature = "xkb")]
+            level_keysym_lists: Vec::new(),
+        }
+    }
+}
+
+impl KBLayout {
+    fn key(&self, evdev_code: u32) -> LayoutKey {
+        LayoutKey {
+            repeats: self.repeat_keys.contains(evdev_code),
+            modifier: self.modifiers.get(evdev_code).cloned(),
+            key_mask: self
+                .modifiers
+                .key_masks
+                .iter()
+                .find(|(code, _)| *code == evdev_code)
+                .map(|&(_, mask)| mask),
+            chars: [
+                &self.state_keymap,
+                &self.caps_lock_keymap,
+                &self.num_lock_keys,
+                &self.caps_num_lock_keys,
+            ]
+            .map(|map| map.key_levels(evdev_code)),
+            named: self.named_key_map.key_levels(evdev_code),
+            key_type: self.key_types.get(evdev_code).cloned(),
+            #[cfg(feature = "xkb")]
+            level_chars: self.level_exceptions_keymap.key_levels(evdev_code),
+            #[cfg(feature = "xkb")]
+            keysyms: self.keysym_map.key_levels(evdev_code),
+            #[cfg(feature = "xkb")]
+            level_keysyms: self.level_keysym_map.key_levels(evdev_code),
+            #[cfg(feature = "xkb")]
+            level_keysym_lists: self
+                .level_keysym_lists
+                .iter()
+                .filter(|((code, _), _)| *code == evdev_code)
+                .map(|((_, level), syms)| (*level, syms.clone()))
+                .collect(),
+        }
+    }
+
+    fn set_key(&mut self, evdev_code: u32, key: LayoutKey) {
+        if key.repeats {
+            self.repeat_keys.insert(evdev_code);
+        } else {
+            self.repeat_keys.remove(evdev_code);
+        }
+        match key.modifier {
+            Some(modifier) => self.modifiers.set_modifier(evdev_code, modifier),
+            None => self.modifiers.remove_modifier(evdev_code),
+        }
+        let key_masks = &mut self.modifiers.key_masks;
+        key_masks.retain(|(code, _)| *code != evdev_code);
+        key_masks.extend(key.key_mask.map(|mask| (evdev_code, mask)));
+        let [base, caps, num, caps_num] = key.chars;
+        self.state_keymap.set_key_levels(evdev_code, base);
+        self.caps_lock_keymap.set_key_levels(evdev_code, caps);
+        self.num_lock_keys.set_key_levels(evdev_code, num);
+        self.caps_num_lock_keys.set_key_levels(evdev_code, caps_num);
+        self.named_key_map.set_key_levels(evdev_code, key.named);
+        match key.key_type {
+            Some(key_type) => self.key_types.set(evdev_code, key_type),
+            None => self.key_types.clear(evdev_code),
+        }
+        #[cfg(feature = "xkb")]
+        {
+            self.level_exceptions_keymap
+                .set_key_levels(evdev_code, key.level_chars);
+            self.keysym_map.set_key_levels(evdev_code, key.keysyms);
+            self.level_keysym_map
+                .set_key_levels(evdev_code, key.level_keysyms);
+            let lists = &mut self.level_keysym_lists;
+            lists.retain(|((code, _), _)| *code != evdev_code);
+            lists.extend(
+                key.level_keysym_lists
+                    .into_iter()
+                    .map(|(level, syms)| ((evdev_code, level), syms)),
+            );
+        }
+    }
+
+    /// Give `key` a key type matching the levels it distinguishes.
+    fn resynthesize_key_type(key: &mut LayoutKey) {
+        let key_type = synthesize_key_type(|a, b| {
+            key.chars[0][a] != key.chars[0][b] || key.named[a] != key.named[b]
+        });
+        key.key_type = (key_type.mask != 0).then_some(key_type);
+    }
+}
+
+impl WKB {
+    /// Make `evdev_code` act like `target` in every layout: its characters,
+    /// named keys, modifier and group actions and repeat flag. `target`
+    /// keeps its own behaviour, so `remap_key(CAPS_LOCK, LEFT_CTRL)` turns
+    /// Caps Lock into a second Control key.
+    ///
+    /// Remapping applies to this state only and shows in
+    /// [`Self::as_xkb_string`]. Remapped modifier keys are released.
+    pub fn remap_key(&mut self, evdev_code: u32, target: u32) -> Result<(), WkbError> {
+        self.check_key(evdev_code)?;
+        self.check_key(target)?;
+        let keys: Vec<LayoutKey> = self
+            .keymap
+            .layouts
+            .iter()
+            .map(|layout| layout.key(target))
+            .collect();
+        self.set_keys(evdev_code, keys);
+        self.remap_groups(|group| {
+            let own = group.keys.contains(&evdev_code);
+            let copy = group.keys.contains(&target).then(|| Group {
+                keys: group
+                    .keys
+                    .iter()
+                    .map(|&key| if key == target { evdev_code } else { key })
+                    .collect(),
+                action: group.action,
+            });
+            (!own)
+                .then(|| group.clone())
+                .into_iter()
+                .chain(copy)
+                .collect()
+        });
+        Ok(())
+    }
+
+    /// Exchange the behaviour of two keys in every layout, like swapping
+    /// Alt and Logo.
+    pub fn swap_keys(&mut self, a: u32, b: u32) -> Result<(), WkbError> {
+        self.check_key(a)?;
+        self.check_key(b)?;
+        let (keys_a, keys_b) = self
+            .keymap
+            .layouts
+            .iter()
+            .map(|layout| (layout.key(a), layout.key(b)))
+            .unzip();
+        self.set_keys(a, keys_b);
+        self.set_keys(b, keys_a);
+        self.remap_groups(|group| {
+            let keys = group
+                .keys
+                .iter()
+                .map(|&key| match key {
+                    key if key == a => b,
+                    key if key == b => a,
+                    key => key,
+                })
+                .collect();
+            vec![Group {
+                keys,
+                action: group.action,
+            }]
+        });
+        Ok(())
+    }
+
+    /// Make `evdev_code` do nothing in every layout. It is also left out of
+    /// [`Self::as_xkb_string`].
+    pub fn disable_key(&mut self, evdev_code: u32) -> Result<(), WkbError> {
+        self.check_key(evdev_code)?;
+        let keys = vec![LayoutKey::disabled(); self.num_layouts()];
+        self.set_keys(evdev_code, keys);
+        self.remap_groups(|group| {
+            if group.keys.contains(&evdev_code) {
+                Vec::new()
+            } else {
+                vec![group.clone()]
+            }
+        });
+        Ok(())
+    }
+
+    /// Set the character `evdev_code` types at `level` of `layout`, or clear
+    /// it with `None`. Levels follow [`crate::level_index`]. Caps Lock types
+    /// the character's uppercase form.
+    pub fn set_key_char(
+        &mut self,
+        layout: usize,
+        evdev_code: u32,
+        level: usize,
+        ch: Option<char>,
+    ) -> Result<(), WkbError> {
+        self.edit_key(layout, evdev_code, level, |key| {
+            let upper = ch
+                .map(|ch| ch.to_uppercase())
+                .filter(|upper| upper.len() == 1)
+                .and_then(|mut upper| upper.next())
+                .filter(|&upper| Some(upper) != ch);
+            #[cfg(feature = "xkb")]
+            let sym = ch.map_or(Keysym::NO_SYMBOL, Keysym::from_char);
+            for plane in fallback_planes(key, level) {
+                key.chars[0][plane] = ch;
+                key.chars[1][plane] = upper;
+                key.chars[2][plane] = None;
+                key.chars[3][plane] = upper;
+                key.named[plane] = NamedKey::Unnamed;
+                #[cfg(feature = "xkb")]
+                {
+                    key.keysyms[plane] = sym;
+                }
+            }
+            #[cfg(feature = "xkb")]
+            {
+                key.level_chars[level] = ch;
+                key.level_keysyms[level] = sym;
+                key.level_keysym_lists
+                    .retain(|(list_level, _)| *list_level != level);
+            }
+        })
+    }
+
+    /// Set the named key `evdev_code` reports at `level` of `layout`,
+    /// keeping its character.
+    pub fn set_key_named(
+        &mut self,
+        layout: usize,
+        evdev_code: u32,
+        level: usize,
+        named_key: NamedKey,
+    ) -> Result<(), WkbError> {
+        self.edit_key(layout, evdev_code, level, |key| {
+            #[cfg(feature = "xkb")]
+            let sym = crate::xkb::keynames::named_key_to_keysym(named_key);
+            for plane in fallback_planes(key, level) {
+                key.named[plane] = named_key;
+                #[cfg(feature = "xkb")]
+                if sym != 0 {
+                    key.keysyms[plane] = Keysym::new(sym);
+                }
+            }
+            #[cfg(feature = "xkb")]
+            if sym != 0 {
+                key.level_keysyms[level] = Keysym::new(sym);
+                key.level_keysym_lists
+                    .retain(|(list_level, _)| *list_level != level);
+            }
+        })
+    }
+
+    /// Undo all remapping, returning to the shared keymap. Group keys return
+    /// to the keymap's, dropping those set with [`Self::set_group_key`].
+    pub fn reset_remapping(&mut self) {
+        let Some(base) = self.base_keymap.take() else {
+            return;
+        };
+        let codes: Vec<u32> = self
+            .keymap
+            .layouts
+            .iter()
+            .chain(&base.layouts)
+            .flat_map(|layout| layout.modifiers.iter().map(|(&code, _)| code))
+            .collect();
+        self.keymap = base;
+        for code in codes {
+            self.sync_modifier(code);
+        }
+        self.groups.set_entries(self.keymap.groups.entries.clone());
+    }
+
+    fn check_key(&self, evdev_code: u32) -> Result<(), WkbError> {
+        let num_keys = self
+            .keymap
+            .layouts
+            .iter()
+            .map(|layout| layout.state_keymap.num_keys)
+            .min()
+            .unwrap_or(0);
+        if (evdev_code as usize) < num_keys {
+            Ok(())
+        } else {
+            Err(WkbError::InvalidKey(evdev_code))
+        }
+    }
+
+    /// The keymap copy private to this state.
+    fn keymap_mut(&mut self) -> &mut Keymap {
+        if self.base_keymap.is_none() {
+            self.base_keymap = Some(self.keymap.clone());
+        }
+        Arc::make_mut(&mut self.keymap)
+    }
+
+    fn edit_key(
+        &mut self,
+        layout: usize,
+        evdev_code: u32,
+        level: usize,
+        edit: impl FnOnce(&mut LayoutKey),
+    ) -> Result<(), WkbError> {
+        if layout >= self.num_layouts() {
+            return Err(WkbError::InvalidLayout(layout));
+        }
+        if level >= MAX_LEVELS {
+            return Err(WkbError::InvalidLevel(level));
+        }
+        self.check_key(evdev_code)?;
+        let mut key = self.keymap.layouts[layout].key(evdev_code);
+        edit(&mut key);
+        KBLayout::resynthesize_key_type(&mut key);
+        self.keymap_mut().layouts[layout].set_key(evdev_code, key);
+        Ok(())
+    }
+
+    /// Install one [`LayoutKey`] per layout for `evdev_code`.
+    fn set_keys(&mut self, evdev_code: u32, keys: Vec<LayoutKey>) {
+        let keymap = self.keymap_mut();
+        for (layout, key) in keymap.layouts.iter_mut().zip(keys) {
+            layout.set_key(evdev_code, key);
+        }
+        self.sync_modifier(evdev_code);
+    }
+
+    /// Copy the keymap's modifier definition for `evdev_code` into this
+    /// state's modifiers.
+    fn sync_modifier(&mut self, evdev_code: u32) {
+        for (modifiers, layout) in self.modifiers.iter_mut().zip(&self.keymap.layouts) {
+            match layout.modifiers.get(evdev_code) {
+                Some(modifier) => modifiers.set_modifier(evdev_code, modifier.clone()),
+                None => modifiers.remove_modifier(evdev_code),
+            }
+            let key_mask = layout
+                .modifiers
+                .key_masks
+                .iter()
+                .find(|(code, _)| *code == evdev_code)
+                .copied();
+            modifiers.key_masks.retain(|(code, _)| *code != evdev_code);
+            modifiers.key_masks.extend(key_mask);
+        }
+    }
+
+    /// Rewrite every group key definition with `remap`, in the keymap and
+    /// in this state.
+    fn remap_groups(&mut self, remap: impl Fn(&Group) -> Vec<Group>) {
+        let entries: Vec<Group> = self.groups.entries.iter().flat_map(&remap).collect();
+        self.groups.set_entries(entries);
+        let groups = &mut self.keymap_mut().groups;
+        let entries = groups.entries.iter().flat_map(&remap).collect();
+        groups.set_entries(entries);
+    }
+}
+
+/// `level` and the higher planes that fall back to it: those adding
+/// modifiers to it that resolve to the same character and named key. Named
+/// keys are only set on the levels a key defines, so an unnamed plane
+/// matches any.
+fn fallback_planes(key: &LayoutKey, level: usize) -> Vec<usize> {
+    (level..MAX_LEVELS)
+        .filter(|&plane| {
+            plane & level == level
+                && key.chars[0][plane] == key.chars[0][level]
+                && (key.named[plane] == NamedKey::Unnamed || key.named[plane] == key.named[level])
+        })
+        .collect()
+}
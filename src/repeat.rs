@@ -0,0 +1,159 @@
+//! Timestamp-driven key repeat.
+
+use crate::{KeyDirection, KeyResult, WKB};
+
+/// Most repeats [`KeyRepeat::repeat`] produces in one call.
+const MAX_CATCH_UP: i64 = 8;
+
+/// Key repeat timer for one [`WKB`], configured like `wl_keyboard.repeat_info`.
+///
+/// Feed key events through [`Self::key_event`] and call [`Self::repeat`]
+/// when [`Self::next_repeat`] is due. Times are in milliseconds, like
+/// `wl_keyboard.key` timestamps, and may wrap around.
+///
+/// Pressing another repeating key takes over the repeat, and releasing the
+/// repeating key stops it. Keys that do not repeat, such as modifiers, leave
+/// it running: each repeat is resolved by [`WKB::repeat_key`] under the
+/// state at that time, so holding Shift mid-repeat switches `a` to `A`.
+#[derive(Debug, Clone, Default)]
+pub struct KeyRepeat {
+    rate: u32,
+    delay: u32,
+    key: Option<RepeatingKey>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RepeatingKey {
+    evdev_code: u32,
+    /// When the first repeat is due.
+    start: u32,
+    /// Repeats produced since `start`.
+    count: u32,
+}
+
+impl KeyRepeat {
+    /// Repeat `rate` times per second after `delay` milliseconds. A rate of
+    /// zero disables repeat.
+    pub fn new(rate: u32, delay: u32) -> Self {
+        Self {
+            rate,
+            delay,
+            key: None,
+        }
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    pub fn delay(&self) -> u32 {
+        self.delay
+    }
+
+    /// Change the rate and delay, e.g. on a new `wl_keyboard.repeat_info`.
+    /// A repeat in progress keeps its next repeat time and continues at the
+    /// new rate.
+    pub fn set_repeat_info(&mut self, rate: u32, delay: u32) {
+        let next = self.next_repeat();
+        self.rate = rate;
+        self.delay = delay;
+        match (&mut self.key, next) {
+            (Some(key), Some(next)) if rate > 0 => {
+                key.start = next;
+                key.count = 0;
+            }
+            _ => self.key = None,
+        }
+    }
+
+    /// Pass a key event at `time` to [`WKB::key_event`] and start or stop
+    /// repeating.
+    pub fn key_event(
+        &mut self,
+        wkb: &mut WKB,
+        evdev_code: u32,
+        direction: KeyDirection,
+        time: u32,
+    ) -> KeyResult {
+        let result = wkb.key_event(evdev_code, direction);
+        match direction {
+            KeyDirection::Down if result.repeats && self.rate > 0 => {
+                self.key = Some(RepeatingKey {
+                    evdev_code,
+                    start: time.wrapping_add(self.delay),
+                    count: 0,
+                });
+            }
+            KeyDirection::Down => {}
+            KeyDirection::Up => {
+                if self.repeating_key() == Some(evdev_code) {
+                    self.key = None;
+                }
+            }
+        }
+        result
+    }
+
+    /// The key being repeated, if any.
+    pub fn repeating_key(&self) -> Option<u32> {
+        self.key.map(|key| key.evdev_code)
+    }
+
+    /// When the next repeat is due, if a key is repeating.
+    pub fn next_repeat(&self) -> Option<u32> {
+        self.key.map(|key| self.repeat_time(key))
+    }
+
+    /// Produce the repeats due by `now` through [`WKB::repeat_key`], so they
+    /// advance compose like the initial press.
+    ///
+    /// Late calls catch up with at most eight repeats. When more are due,
+    /// e.g. after a suspend, the rest are dropped and the repeat continues
+    /// from `now`.
+    pub fn repeat(&mut self, wkb: &mut WKB, now: u32) -> Vec<KeyResult> {
+        let Some(key) = self.key else {
+            return Vec::new();
+        };
+        // Negative once `now` is before `start`.
+        let elapsed = i64::from(now.wrapping_sub(key.start) as i32);
+        if elapsed < 0 {
+            return Vec::new();
+        }
+        // Repeats whose offset is at most `elapsed`.
+        let due = ((elapsed + 1) * i64::from(self.rate.max(1)) + 999) / 1000;
+        let missed = due - i64::from(key.count);
+        if missed <= 0 {
+            return Vec::new();
+        }
+        self.key = Some(if missed > MAX_CATCH_UP {
+            RepeatingKey {
+                start: key.start.wrapping_add(self.offset(due) as u32),
+                count: 0,
+                ..key
+            }
+        } else {
+            RepeatingKey {
+                count: due as u32,
+                ..key
+            }
+        });
+        (0..missed.min(MAX_CATCH_UP))
+            .map(|_| wkb.repeat_key(key.evdev_code))
+            .collect()
+    }
+
+    /// Stop repeating, e.g. when the surface loses keyboard focus.
+    pub fn cancel(&mut self) {
+        self.key = None;
+    }
+
+    fn repeat_time(&self, key: RepeatingKey) -> u32 {
+        key.start.wrapping_add(self.offset(key.count.into()) as u32)
+    }
+
+    /// Milliseconds from `start` to repeat number `count`. Computed from the
+    /// count so rounding does not drift.
+    fn offset(&self, count: i64) -> i64 {
+        count * 1000 / i64::from(self.rate.max(1))
+    }
+}
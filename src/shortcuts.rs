@@ -0,0 +1,98 @@
+//! Resolving keys for keyboard shortcuts on non-Latin layouts.
+
+#[cfg(feature = "xkb")]
+use crate::Keysym;
+use crate::{KBLayout, WkbError, WKB};
+
+/// A key resolved for shortcut bindings by [`WKB::shortcut_key`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortcutKey {
+    /// The layout the key was resolved on: the active one, or the shortcut
+    /// layout when the active one is not Latin.
+    pub layout: usize,
+    pub ch: Option<char>,
+    #[cfg(feature = "xkb")]
+    pub keysym: Keysym,
+}
+
+impl KBLayout {
+    /// Whether the base level types every ASCII letter.
+    fn is_latin(&self) -> bool {
+        let mut letters = 0u32;
+        for level0 in self.state_keymap.data[..self.state_keymap.num_keys]
+            .iter()
+            .flatten()
+        {
+            if level0.is_ascii_lowercase() {
+                letters |= 1 << (*level0 as u8 - b'a');
+            }
+        }
+        letters == (1 << 26) - 1
+    }
+
+    /// The key at the level `mods` select, by the key's type.
+    fn shortcut_key(&self, layout: usize, evdev_code: u32, mods: u8) -> ShortcutKey {
+        let level = self
+            .key_types
+            .get(evdev_code)
+            .map_or(0, |key_type| key_type.level(mods)) as usize;
+        #[cfg(feature = "xkb")]
+        {
+            let keysym = self.level_keysym_map.get(level, evdev_code);
+            ShortcutKey {
+                layout,
+                ch: keysym.to_char(),
+                keysym,
+            }
+        }
+        #[cfg(not(feature = "xkb"))]
+        ShortcutKey {
+            layout,
+            ch: self.state_keymap.get(level, evdev_code),
+        }
+    }
+}
+
+impl WKB {
+    /// Resolve a key for shortcut bindings such as Ctrl+C under the current
+    /// state.
+    ///
+    /// On a Latin layout this is the key's own keysym and character. When
+    /// the active layout is not Latin, keys typing non-ASCII characters
+    /// resolve on the [shortcut layout](Self::shortcut_layout) instead, at
+    /// the level the same modifiers select there, so Ctrl+С on a Russian
+    /// layout binds as Ctrl+C. Keys such as digits that already type ASCII
+    /// keep the active layout. [`ShortcutKey::layout`] tells which was used.
+    pub fn shortcut_key(&self, evdev_code: u32) -> ShortcutKey {
+        let mods = self.effective_mods() as u8;
+        let active = self
+            .layout()
+            .shortcut_key(self.current_layout_idx, evdev_code, mods);
+        if active.ch.is_none_or(|ch| ch.is_ascii()) || self.layout().is_latin() {
+            return active;
+        }
+        self.shortcut_layout()
+            .filter(|&layout| layout != self.current_layout_idx)
+            .map(|layout| self.keymap.layouts[layout].shortcut_key(layout, evdev_code, mods))
+            .filter(|key| key.ch.is_some())
+            .unwrap_or(active)
+    }
+
+    /// The layout [`Self::shortcut_key`] falls back to: the one set with
+    /// [`Self::set_shortcut_layout`], or else the first whose base level is
+    /// Latin.
+    pub fn shortcut_layout(&self) -> Option<usize> {
+        self.shortcut_layout
+            .or_else(|| self.keymap.layouts.iter().position(KBLayout::is_latin))
+    }
+
+    /// Choose the layout shortcuts fall back to, or `None` to use the first
+    /// Latin layout.
+    pub fn set_shortcut_layout(&mut self, layout: Option<usize>) -> Result<(), WkbError> {
+        if let Some(layout) = layout.filter(|&layout| layout >= self.num_layouts()) {
+            return Err(WkbError::InvalidLayout(layout));
+        }
+        self.shortcut_layout = layout;
+        Ok(())
+    }
+}
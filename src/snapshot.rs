@@ -0,0 +1,100 @@
+//! Saving and restoring the mutable state of a [`WKB`].
+
+use crate::composer::ComposeCursor;
+use crate::groups::GroupsSnapshot;
+use crate::modifiers::ModifiersSnapshot;
+use crate::{WkbError, WKB};
+
+/// The key, modifier, group and compose state of a [`WKB`], taken by
+/// [`WKB::snapshot`] and installed by [`WKB::restore`].
+///
+/// Serialize it with any serde format to hand a session over to another
+/// process or to keep test fixtures. It holds state only: the keymap,
+/// remapping, installed compose tables and settings such as
+/// [`WKB::set_text_mode`] are not included, so restore it into a state
+/// built from the same keymap and set up the same way.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StateSnapshot {
+    version: u32,
+    layout: usize,
+    /// Per-key modifier state, one per layout.
+    modifiers: Vec<ModifiersSnapshot>,
+    groups: GroupsSnapshot,
+    /// Compose trie position and preedit, one per layout.
+    compose: Vec<(u32, String)>,
+}
+
+impl StateSnapshot {
+    /// The format version written by [`WKB::snapshot`]. [`WKB::restore`]
+    /// rejects others.
+    pub const VERSION: u32 = 1;
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+impl WKB {
+    /// Capture the current state: pressed keys, per-key modifier state,
+    /// latched and locked modifiers and groups, and compose progress.
+    pub fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            version: StateSnapshot::VERSION,
+            layout: self.current_layout_idx,
+            modifiers: self.modifiers.iter().map(|mods| mods.snapshot()).collect(),
+            groups: self.groups.snapshot(),
+            compose: self
+                .compose_cursors
+                .iter()
+                .map(ComposeCursor::snapshot)
+                .collect(),
+        }
+    }
+
+    /// Install a [`StateSnapshot`], replacing the current state.
+    ///
+    /// Nothing changes on error. [`WkbError::UnsupportedSnapshot`] reports
+    /// another format version, and [`WkbError::SnapshotMismatch`] a snapshot
+    /// whose layouts, modifier keys, group keys or compose tables differ
+    /// from this state's, including modifier keys that press, latch or lock
+    /// differently.
+    pub fn restore(&mut self, snapshot: &StateSnapshot) -> Result<(), WkbError> {
+        if snapshot.version != StateSnapshot::VERSION {
+            return Err(WkbError::UnsupportedSnapshot(snapshot.version));
+        }
+        if snapshot.layout >= self.num_layouts()
+            || snapshot.modifiers.len() != self.num_layouts()
+            || snapshot.compose.len() != self.num_layouts()
+        {
+            return Err(WkbError::SnapshotMismatch);
+        }
+        let compose_cursors = snapshot
+            .compose
+            .iter()
+            .enumerate()
+            .map(|(idx, (cur, buf))| {
+                let composer = self.composers[idx]
+                    .as_deref()
+                    .unwrap_or(&self.keymap.layouts[idx].composer);
+                ComposeCursor::restore(composer, *cur, buf)
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(WkbError::SnapshotMismatch)?;
+
+        let mut modifiers = self.modifiers.clone();
+        let mut groups = self.groups.clone();
+        let restored = modifiers
+            .iter_mut()
+            .zip(&snapshot.modifiers)
+            .all(|(mods, snapshot)| mods.restore(snapshot))
+            && groups.restore(&snapshot.groups);
+        if !restored {
+            return Err(WkbError::SnapshotMismatch);
+        }
+        self.modifiers = modifiers;
+        self.groups = groups;
+        self.compose_cursors = compose_cursors;
+        self.current_layout_idx = snapshot.layout;
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+//! Synthesizing the key events that type a string, for virtual keyboards
+//! and automation tools.
+
+use crate::modifiers::{ModKind, ModType, Modifier, MOD_SHIFT};
+#[cfg(feature = "xkb")]
+use crate::Keysym;
+use crate::{KeyDirection, Keystroke, WKB};
+
+/// A step of a [`TypingPlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypingEvent {
+    /// Press or release a key.
+    Key {
+        evdev_code: u32,
+        direction: KeyDirection,
+    },
+    /// Switch to the layout at this index, with [`WKB::set_layout`] or the
+    /// compositor's group.
+    SetLayout(usize),
+}
+
+/// The events typing a string, from [`WKB::type_text`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TypingPlan {
+    pub events: Vec<TypingEvent>,
+    /// Characters that cannot be typed, with their byte offset in the text.
+    /// No events are generated for them.
+    pub untypable: Vec<(usize, char)>,
+    /// The keymap to type with, when [`WKB::type_text_with_extra_keys`]
+    /// added keys for characters the keymap cannot type.
+    pub keymap: Option<String>,
+}
+
+impl WKB {
+    /// Plan the key events that type `text` from the current state, like
+    /// wtype. Modifier keys are held around the keys they modify, other
+    /// layouts are switched to when the current one cannot type a character,
+    /// and dead-key and Compose sequences are used for characters without a
+    /// key. The state ends on the layout it started on.
+    ///
+    /// Every way of typing a character is checked against a copy of this
+    /// state, so locked modifiers and pending compose sequences are taken
+    /// into account.
+    pub fn type_text(&self, text: &str) -> TypingPlan {
+        self.plan_typing(text, false)
+    }
+
+    /// Like [`Self::type_text`], also giving characters the keymap cannot
+    /// type keys of their own: unused keycodes in a copy of the keymap,
+    /// returned as XKB text in [`TypingPlan::keymap`]. The events are meant
+    /// for that keymap, such as one sent with `zwp_virtual_keyboard_v1`.
+    #[cfg(feature = "xkb")]
+    pub fn type_text_with_extra_keys(&self, text: &str) -> TypingPlan {
+        self.plan_typing(text, true)
+    }
+
+    fn plan_typing(&self, text: &str, extra_keys: bool) -> TypingPlan {
+        let mut plan = TypingPlan::default();
+        let mut state = self.clone();
+        #[cfg(feature = "xkb")]
+        let mut spare = self.keymap.unused_keys();
+        #[cfg(feature = "xkb")]
+        let mut extra: Vec<(u32, Keysym)> = Vec::new();
+        #[cfg(not(feature = "xkb"))]
+        let _ = extra_keys;
+
+        for (offset, ch) in text.char_indices() {
+            if let Some((next, events)) = state.type_char(ch) {
+                state = next;
+                plan.events.extend(events);
+                continue;
+            }
+            #[cfg(feature = "xkb")]
+            if extra_keys {
+                let sym = Keysym::from_char(ch);
+                let key = extra
+                    .iter()
+                    .find(|&&(_, extra_sym)| extra_sym == sym)
+                    .map(|&(key, _)| key)
+                    .or_else(|| (sym != Keysym::NO_SYMBOL).then(|| spare.pop()).flatten());
+                if let Some(evdev_code) = key {
+                    if !extra.iter().any(|&(code, _)| code == evdev_code) {
+                        extra.push((evdev_code, sym));
+                    }
+                    plan.events.extend(tap(evdev_code));
+                    continue;
+                }
+            }
+            plan.untypable.push((offset, ch));
+        }
+
+        if state.current_layout_idx != self.current_layout_idx {
+            plan.events
+                .push(TypingEvent::SetLayout(self.current_layout_idx));
+        }
+        #[cfg(feature = "xkb")]
+        if !extra.is_empty() {
+            plan.keymap = Some(self.keymap.generate_xkb_string_with_keys(&extra));
+        }
+        plan
+    }
+
+    /// The first way to type `ch`, current layout first, with the state
+    /// after typing it.
+    fn type_char(&self, ch: char) -> Option<(WKB, Vec<TypingEvent>)> {
+        let layouts = std::iter::once(self.current_layout_idx)
+            .chain((0..self.num_layouts()).filter(|&idx| idx != self.current_layout_idx));
+        for layout_idx in layouts {
+            let ways = self.layout_key_sequences(layout_idx, ch);
+            // Caps Lock and similar locks invert Shift, so also try each way
+            // with Shift toggled.
+            let toggled = ways.iter().map(|way| {
+                way.iter()
+                    .map(|keystroke| Keystroke {
+                        mods: keystroke.mods ^ MOD_SHIFT,
+                        ..*keystroke
+                    })
+                    .collect::<Vec<_>>()
+            });
+            for way in ways.iter().cloned().chain(toggled) {
+                let mut state = self.clone();
+                let mut events = Vec::new();
+                if layout_idx != state.current_layout_idx {
+                    state.set_layout(layout_idx).ok()?;
+                    events.push(TypingEvent::SetLayout(layout_idx));
+                }
+                let Some(typed) = state.type_keystrokes(&way, &mut events) else {
+                    continue;
+                };
+                if typed == ch.to_string() {
+                    return Some((state, events));
+                }
+            }
+        }
+        None
+    }
+
+    /// Press `keystrokes` with their modifiers, returning the committed
+    /// text, or `None` when a modifier has no key.
+    fn type_keystrokes(
+        &mut self,
+        keystrokes: &[Keystroke],
+        events: &mut Vec<TypingEvent>,
+    ) -> Option<String> {
+        let mut typed = String::new();
+        for keystroke in keystrokes {
+            let modifier_keys = self.modifier_keys(keystroke.mods)?;
+            let presses = modifier_keys
+                .iter()
+                .chain([&keystroke.evdev_code])
+                .map(|&code| (code, KeyDirection::Down));
+            let releases = modifier_keys
+                .iter()
+                .chain([&keystroke.evdev_code])
+                .rev()
+                .map(|&code| (code, KeyDirection::Up));
+            for (evdev_code, direction) in presses.chain(releases) {
+                let result = self.key_event(evdev_code, direction);
+                if let Some(text) = result.commit_text() {
+                    typed.push_str(&text);
+                }
+                events.push(TypingEvent::Key {
+                    evdev_code,
+                    direction,
+                });
+            }
+        }
+        Some(typed)
+    }
+
+    /// Keys to hold for the Shift, Level3 and Level5 bits of `mods` on the
+    /// current layout.
+    fn modifier_keys(&self, mods: u32) -> Option<Vec<u32>> {
+        let masks = &self.mods().masks;
+        [
+            (MOD_SHIFT, ModType::Level2),
+            (masks.level3, ModType::Level3),
+            (masks.level5, ModType::Level5),
+        ]
+        .into_iter()
+        .filter(|&(mask, _)| mask != 0 && mods & mask != 0)
+        .map(|(_, mod_type)| self.modifier_key(mod_type))
+        .collect()
+    }
+
+    /// A key that sets `mod_type` while held.
+    fn modifier_key(&self, mod_type: ModType) -> Option<u32> {
+        self.mods()
+            .iter()
+            .find(|(_, modifier)| match modifier {
+                Modifier::Single(modifier) => {
+                    modifier.has_mod_type(mod_type)
+                        && matches!(modifier.kind, ModKind::Press { .. } | ModKind::Latch { .. })
+                }
+                Modifier::Leveled(_) => false,
+            })
+            .map(|(&code, _)| code)
+    }
+}
+
+#[cfg(feature = "xkb")]
+fn tap(evdev_code: u32) -> [TypingEvent; 2] {
+    [KeyDirection::Down, KeyDirection::Up].map(|direction| TypingEvent::Key {
+        evdev_code,
+        direction,
+    })
+}
@@ -0,0 +1,463 @@
+//! Compat map compilation: `interpret` statements and the modifier and group
+//! actions they bind to keys.
+use super::diagnostics::Section;
+use super::parse_xkb::Stream;
+use super::parser::*;
+use super::symbols::{
+    compile_stream, expr_resolve_group, expr_resolve_mod_mask, handle_vmod_def, init_vmods,
+    invalid_value, merge_mod_sets, named_bool, wrong_statement, xkb_mod_name_to_index,
+    CompileInput,
+};
+
+/// Default flags per action type, set with statements such as
+/// `setMods.clearLocks = True;`. Like xkbcommon, they carry over into the
+/// files included after them.
+#[derive(Clone, Copy, Default)]
+struct ActionDefaults([ActionFlags; 6]);
+
+struct CompatInfo {
+    include_depth: u32,
+    interprets: Vec<XkbInterpret>,
+    default_interpret: XkbInterpret,
+    action_defaults: ActionDefaults,
+    mods: XkbModSet,
+}
+
+fn default_interpret() -> XkbInterpret {
+    XkbInterpret {
+        sym: XKB_KEY_NO_SYMBOL,
+        match_op: MatchOperation::AnyOfOrNone,
+        mods: MOD_REAL_MASK_ALL,
+        level_one_only: false,
+        virtual_mod: None,
+        action: XkbAction::None,
+    }
+}
+
+impl CompatInfo {
+    fn new(include_depth: u32, mods: &XkbModSet, action_defaults: ActionDefaults) -> Self {
+        let mut info = Self {
+            include_depth,
+            interprets: Vec::new(),
+            default_interpret: default_interpret(),
+            action_defaults,
+            mods: XkbModSet::default(),
+        };
+        init_vmods(&mut info.mods, mods, include_depth > 0);
+        info
+    }
+    fn add_interpret(&mut self, new: XkbInterpret, merge: MergeMode) {
+        let existing = self
+            .interprets
+            .iter_mut()
+            .find(|old| old.sym == new.sym && old.match_op == new.match_op && old.mods == new.mods);
+        match existing {
+            Some(old) if merge != MergeMode::Augment => *old = new,
+            Some(_) => {}
+            None => self.interprets.push(new),
+        }
+    }
+    fn merge_from(&mut self, from: &mut Self, merge: MergeMode) {
+        merge_mod_sets(&mut self.mods, &from.mods, merge);
+        self.action_defaults = from.action_defaults;
+        if self.interprets.is_empty() {
+            self.interprets = std::mem::take(&mut from.interprets);
+        } else {
+            for interpret in from.interprets.drain(..) {
+                self.add_interpret(interpret, merge);
+            }
+        }
+    }
+    fn include(&mut self, ki: &mut XkbKeymap, includes: &mut [IncludeStmt]) -> bool {
+        if exceeds_include_max_depth(&mut ki.ctx, self.include_depth) {
+            return false;
+        }
+        let depth = self.include_depth + 1;
+        let mut included = Self::new(depth, &self.mods, self.action_defaults);
+        for stmt in includes.iter() {
+            let Some(file) = process_include_stream(&mut ki.ctx, stmt, FileType::Compat) else {
+                return false;
+            };
+            let mut next = Self::new(depth, &included.mods, included.action_defaults);
+            if !compile_stream(ki, &mut next, &mut file.stream(), |ki, info, stmt| {
+                info.compile_statement(ki, stmt)
+            }) {
+                return false;
+            }
+            ki.ctx.diagnostics.leave();
+            included.merge_from(&mut next, stmt.merge);
+        }
+        if let Some(first) = includes.first() {
+            self.merge_from(&mut included, first.merge);
+        }
+        true
+    }
+    fn compile_statement(&mut self, ki: &mut XkbKeymap, statement: &mut Statement<'_>) -> bool {
+        match statement {
+            Statement::Include(includes) => self.include(ki, includes),
+            Statement::Interpret(def) => self.compile_interpret(ki, def),
+            Statement::Var(var) => self.compile_global(ki, var),
+            Statement::VMods(vmods) => vmods
+                .iter()
+                .all(|vmod| handle_vmod_def(&mut ki.ctx, &mut self.mods, vmod)),
+            Statement::Unknown => true,
+            _ => wrong_statement(ki),
+        }
+    }
+    fn compile_interpret(&mut self, ki: &mut XkbKeymap, def: &InterpretDef<'_>) -> bool {
+        let mut interpret = self.default_interpret;
+        interpret.sym = def.sym;
+        let predicate = match &def.predicate {
+            Some(predicate) => resolve_predicate(&ki.ctx, predicate, &self.mods),
+            None => Some((MatchOperation::AnyOfOrNone, MOD_REAL_MASK_ALL)),
+        };
+        let Some((match_op, mods)) = predicate else {
+            return false;
+        };
+        interpret.match_op = match_op;
+        interpret.mods = mods;
+        let mut body = Stream::new(def.body);
+        loop {
+            let var = match body.next_var(&mut ki.ctx) {
+                Ok(Some(var)) => var,
+                Ok(None) => break,
+                Err(()) => return false,
+            };
+            let valid = match (&var.name, &var.value) {
+                (Some(lhs), Some(value)) => {
+                    lhs.element == Element::None
+                        && self.set_interpret_field(ki, &mut interpret, lhs.field, value)
+                }
+                _ => false,
+            };
+            if !valid {
+                invalid_value(ki, &body, "invalid value for interpret field");
+                return false;
+            }
+        }
+        self.add_interpret(interpret, def.merge);
+        true
+    }
+    fn compile_global(&mut self, ki: &mut XkbKeymap, var: &VarDef) -> bool {
+        let (Some(lhs), Some(value)) = (&var.name, &var.value) else {
+            return false;
+        };
+        match lhs.element {
+            Element::Interpret => {
+                let mut interpret = self.default_interpret;
+                let valid = self.set_interpret_field(ki, &mut interpret, lhs.field, value);
+                self.default_interpret = interpret;
+                valid
+            }
+            Element::Action(action) => {
+                let flag = match lhs.field {
+                    Field::ClearLocks => ActionFlags::LOCK_CLEAR,
+                    Field::LatchToLock => ActionFlags::LATCH_TO_LOCK,
+                    _ => return true,
+                };
+                let Some(set) = resolve_bool(&ki.ctx, value) else {
+                    return false;
+                };
+                self.action_defaults.0[action as usize].set(flag, set);
+                true
+            }
+            _ => true,
+        }
+    }
+    fn set_interpret_field(
+        &self,
+        ki: &XkbKeymap,
+        interpret: &mut XkbInterpret,
+        field: Field,
+        value: &ExprKind,
+    ) -> bool {
+        match field {
+            Field::Action => match value {
+                ExprKind::Action(call) => match self.resolve_action(ki, call) {
+                    Some(action) => interpret.action = action,
+                    None => return false,
+                },
+                _ => return false,
+            },
+            Field::VirtualModifier => {
+                let Some(name) = ident(value) else {
+                    return false;
+                };
+                match xkb_mod_name_to_index(&self.mods, name, MOD_VIRT) {
+                    Some(index) => interpret.virtual_mod = Some(index),
+                    None => return false,
+                }
+            }
+            Field::UseModMapMods => {
+                let Some(name) = ident(value).map(|name| ki.ctx.atom_text(name)) else {
+                    return false;
+                };
+                interpret.level_one_only = if ["level1", "levelone"]
+                    .iter()
+                    .any(|level| name.eq_ignore_ascii_case(level))
+                {
+                    true
+                } else if ["anylevel", "any"]
+                    .iter()
+                    .any(|level| name.eq_ignore_ascii_case(level))
+                {
+                    false
+                } else {
+                    return false;
+                };
+            }
+            _ => {}
+        }
+        true
+    }
+    /// Compile a `SetMods(...)`-style call. Actions wkb does not model
+    /// compile to [`XkbAction::None`].
+    fn resolve_action(&self, ki: &XkbKeymap, call: &ActionCall) -> Option<XkbAction> {
+        let ctx = &ki.ctx;
+        let Some(action_type) = ActionType::from_name(ctx.atom_text(call.name).as_bytes()) else {
+            return Some(XkbAction::None);
+        };
+        let mut flags = self.action_defaults.0[action_type as usize];
+        let mut mods = 0;
+        let mut group = 0;
+        for arg in &call.args {
+            let (field, value) = match arg.field {
+                Some(field) => (ctx.atom_text(field), Some(&arg.value)),
+                None => (ctx.atom_text(ident(&arg.value)?), None),
+            };
+            let flag = |flags: &mut ActionFlags, flag| {
+                let set = value.map_or(Some(true), |value| resolve_bool(ctx, value))?;
+                flags.set(flag, set);
+                Some(())
+            };
+            match field.to_ascii_lowercase().as_str() {
+                "modifiers" | "mods" => {
+                    let value = value?;
+                    let lookup = ident(value).is_some_and(|name| {
+                        let name = ctx.atom_text(name);
+                        name.eq_ignore_ascii_case("modmapmods")
+                            || name.eq_ignore_ascii_case("modmap")
+                    });
+                    flags.set(ActionFlags::MODS_LOOKUP_MODMAP, lookup);
+                    mods = if lookup {
+                        0
+                    } else {
+                        expr_resolve_mod_mask(ctx, value, MOD_BOTH, &self.mods)?
+                    };
+                }
+                "group" => {
+                    let (value, _) = expr_resolve_group(ki, value?, !arg.signed)?;
+                    flags.set(ActionFlags::ABSOLUTE_SWITCH, !arg.signed);
+                    group = if arg.signed {
+                        value as i32
+                    } else {
+                        value as i32 - 1
+                    };
+                }
+                "clearlocks" => flag(&mut flags, ActionFlags::LOCK_CLEAR)?,
+                "latchtolock" => flag(&mut flags, ActionFlags::LATCH_TO_LOCK)?,
+                "affect" => {
+                    let affect = ctx.atom_text(ident(value?)?).to_ascii_lowercase();
+                    let (no_lock, no_unlock) = match affect.as_str() {
+                        "both" => (false, false),
+                        "lock" => (false, true),
+                        "unlock" => (true, false),
+                        "neither" => (true, true),
+                        _ => return None,
+                    };
+                    flags.set(ActionFlags::LOCK_NO_LOCK, no_lock);
+                    flags.set(ActionFlags::LOCK_NO_UNLOCK, no_unlock);
+                }
+                _ => {}
+            }
+        }
+        let mod_action = XkbModAction { flags, mods };
+        let group_action = XkbGroupAction { flags, group };
+        Some(match action_type {
+            ActionType::ModSet => XkbAction::ModSet(mod_action),
+            ActionType::ModLatch => XkbAction::ModLatch(mod_action),
+            ActionType::ModLock => XkbAction::ModLock(mod_action),
+            ActionType::GroupSet => XkbAction::GroupSet(group_action),
+            ActionType::GroupLatch => XkbAction::GroupLatch(group_action),
+            ActionType::GroupLock => XkbAction::GroupLock(group_action),
+        })
+    }
+}
+
+/// The atom of an expression made of a single identifier.
+fn ident(expr: &ExprKind) -> Option<u32> {
+    match expr {
+        ExprKind::Scalar(ScalarExpr {
+            terms,
+            invert: false,
+        }) if terms.len() == 1 => match terms[0] {
+            (true, Scalar::Ident(atom)) => Some(atom),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn resolve_bool(ctx: &XkbContext, expr: &ExprKind) -> Option<bool> {
+    if let Some(name) = ident(expr) {
+        return named_bool(ctx.atom_text(name));
+    }
+    match expr {
+        ExprKind::Scalar(ScalarExpr { terms, .. }) if terms.len() == 1 => match terms[0] {
+            (_, Scalar::Integer(value)) => Some(value != 0),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Resolve the `+AnyOf(...)` part of an `interpret` statement. A bare mask
+/// matches exactly; a bare `Any` matches any real modifier.
+fn resolve_predicate(
+    ctx: &XkbContext,
+    expr: &ExprKind,
+    mods: &XkbModSet,
+) -> Option<(MatchOperation, u32)> {
+    if let ExprKind::Action(call) = expr {
+        const OPERATIONS: [(MatchOperation, &str); 5] = [
+            (MatchOperation::NoneOf, "noneof"),
+            (MatchOperation::AnyOfOrNone, "anyofornone"),
+            (MatchOperation::AnyOf, "anyof"),
+            (MatchOperation::AllOf, "allof"),
+            (MatchOperation::Exactly, "exactly"),
+        ];
+        let name = ctx.atom_text(call.name);
+        let (match_op, _) = OPERATIONS
+            .iter()
+            .find(|(_, op)| name.eq_ignore_ascii_case(op))?;
+        let [arg] = call.args.as_slice() else {
+            return None;
+        };
+        let mask = expr_resolve_mod_mask(ctx, &arg.value, MOD_REAL, mods)?;
+        return Some((*match_op, mask));
+    }
+    if ident(expr).is_some_and(|name| ctx.atom_text(name).eq_ignore_ascii_case("any")) {
+        return Some((MatchOperation::AnyOf, MOD_REAL_MASK_ALL));
+    }
+    Some((
+        MatchOperation::Exactly,
+        expr_resolve_mod_mask(ctx, expr, MOD_REAL, mods)?,
+    ))
+}
+
+/// Order interprets from most to least specific, the order in which
+/// xkbcommon tries them: keysym matches first, then by match operation.
+fn sort_interprets(interprets: Vec<XkbInterpret>) -> Vec<XkbInterpret> {
+    const ORDER: [MatchOperation; 5] = [
+        MatchOperation::Exactly,
+        MatchOperation::AllOf,
+        MatchOperation::NoneOf,
+        MatchOperation::AnyOf,
+        MatchOperation::AnyOfOrNone,
+    ];
+    let mut sorted = Vec::with_capacity(interprets.len());
+    for need_symbol in [true, false] {
+        for match_op in ORDER {
+            sorted.extend(interprets.iter().filter(|interpret| {
+                (interpret.sym != XKB_KEY_NO_SYMBOL) == need_symbol
+                    && interpret.match_op == match_op
+            }));
+        }
+    }
+    sorted
+}
+
+pub(crate) fn compile_compat(input: CompileInput<'_, '_>, keymap: &mut XkbKeymap) -> bool {
+    keymap.ctx.diagnostics.section = Section::Compat;
+    let mut info = CompatInfo::new(0, &keymap.mods, ActionDefaults::default());
+    let valid = match input {
+        CompileInput::Stream(stream) => stream.is_none_or(|stream| {
+            compile_stream(keymap, &mut info, stream, |ki, info, stmt| {
+                info.compile_statement(ki, stmt)
+            })
+        }),
+        CompileInput::Includes(includes) => info.include(keymap, includes),
+    };
+    if !valid {
+        return false;
+    }
+    keymap.mods = info.mods;
+    keymap.interprets = sort_interprets(info.interprets);
+    true
+}
+
+fn find_interpret(
+    interprets: &[XkbInterpret],
+    sym: u32,
+    level: usize,
+    modmap: u32,
+) -> Option<&XkbInterpret> {
+    if sym == XKB_KEY_NO_SYMBOL {
+        return None;
+    }
+    interprets.iter().find(|interpret| {
+        if interpret.sym != sym && interpret.sym != XKB_KEY_NO_SYMBOL {
+            return false;
+        }
+        let mods = if interpret.level_one_only && level != 0 {
+            0
+        } else {
+            modmap
+        };
+        match interpret.match_op {
+            MatchOperation::NoneOf => interpret.mods & mods == 0,
+            MatchOperation::AnyOfOrNone => mods == 0 || interpret.mods & mods != 0,
+            MatchOperation::AnyOf => interpret.mods & mods != 0,
+            MatchOperation::AllOf => interpret.mods & mods == interpret.mods,
+            MatchOperation::Exactly => interpret.mods == mods,
+        }
+    })
+}
+
+/// Bind the compat map's interprets to keys, as xkbcommon does: each level
+/// takes the action of the most specific interpret matching its keysym and
+/// the key's modifier map, and the key's virtual modifiers follow the
+/// matching interprets.
+///
+/// Keys no interpret matches keep wkb's keysym-derived behaviour, so keymaps
+/// with an empty compat section still get working modifier and group keys.
+pub(crate) fn apply_interprets(keymap: &mut XkbKeymap) {
+    if keymap.interprets.is_empty() {
+        return;
+    }
+    let interprets = std::mem::take(&mut keymap.interprets);
+    for key in keymap.keys.iter_mut().skip(keymap.min_key_code as usize) {
+        let modmap = key.modmap;
+        let mut vmodmap = 0;
+        let mut matched = false;
+        for (group_idx, group) in key.groups.iter_mut().enumerate() {
+            for (level_idx, level) in group.levels.iter_mut().enumerate() {
+                let Some(interpret) = find_interpret(&interprets, level.sym, level_idx, modmap)
+                else {
+                    continue;
+                };
+                matched = true;
+                let base = group_idx == 0 && level_idx == 0;
+                if base || !interpret.level_one_only {
+                    if let Some(vmod) = interpret.virtual_mod {
+                        vmodmap |= 1 << vmod;
+                    }
+                }
+                let mut action = interpret.action;
+                if let XkbAction::ModSet(mods)
+                | XkbAction::ModLatch(mods)
+                | XkbAction::ModLock(mods) = &mut action
+                {
+                    if mods.flags.contains(ActionFlags::MODS_LOOKUP_MODMAP) {
+                        mods.mods = modmap;
+                    }
+                }
+                level.action = Some(action);
+            }
+        }
+        if matched && key.vmodmap.is_none() {
+            key.vmodmap = Some(vmodmap);
+        }
+    }
+    keymap.interprets = interprets;
+}
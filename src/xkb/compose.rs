@@ -1,19 +1,20 @@
 //! Compose-file loading and caching for layouts.
 
 use super::keymap;
-use crate::composer::Token;
-use crate::Composer;
-use arrayvec::ArrayVec;
+use super::Keysym;
+use crate::composer::{ComposeKey, Reachable};
+use crate::{ComposeOutput, ComposeTable, Composer};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
 
 struct ComposeTableData {
-    entries: Vec<keymap::ComposeEntry>,
-    filtered: Mutex<Vec<(Vec<char>, Arc<Composer>)>>,
+    table: ComposeTable,
+    filtered: Mutex<Vec<(Reachable, Arc<Composer>)>>,
 }
 
-type ComposeTable = Arc<ComposeTableData>;
-type ComposeTableCache = Vec<(PathBuf, ComposeTable)>;
+type CachedTable = Arc<ComposeTableData>;
+/// Tables by file and locale, since `include "%L"` depends on the locale.
+type ComposeTableCache = Vec<((PathBuf, String), CachedTable)>;
 
 static COMPOSE_TABLE_CACHE: OnceLock<Mutex<ComposeTableCache>> = OnceLock::new();
 
@@ -24,31 +25,80 @@ fn table_cache() -> MutexGuard<'static, ComposeTableCache> {
         .unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
-fn parse_table(path: &Path) -> (ComposeTableData, bool) {
-    let mut entries = Vec::new();
+impl ComposeTable {
+    /// Load a Compose file, following its `include` statements. `%L` in
+    /// includes refers to the Compose file of the process locale.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Self::from_string(&text))
+    }
+
+    /// Parse Compose file text. Lines that are not valid sequences are
+    /// skipped, as in Compose files.
+    pub fn from_string(text: &str) -> Self {
+        let mut table = ComposeTable::new();
+        keymap::parse_compose_str(text, &keymap::env_locale(), &mut |entry| {
+            push_entry(&mut table, entry);
+        });
+        table
+    }
+
+    /// Load the Compose file xkbcommon would use for `locale`: the user's
+    /// `XCOMPOSEFILE`, `XCompose` or `~/.XCompose` if present, otherwise the
+    /// locale's system file. `None` when no file is found.
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let path = keymap::resolve_compose_file(locale)?;
+        Some(load_table(&path, locale).table.clone())
+    }
+
+    /// Add a sequence written in keysyms, as in Compose files, with
+    /// `Multi_key` for the Compose key. Returns `false`, leaving the table
+    /// unchanged, when `keysyms` is empty.
+    pub fn insert_keysyms(&mut self, keysyms: &[Keysym], output: impl Into<ComposeOutput>) -> bool {
+        if keysyms.is_empty() {
+            return false;
+        }
+        let tokens = keysyms.iter().map(|sym| keysym_token(sym.raw())).collect();
+        self.entries.push((tokens, output.into()));
+        true
+    }
+}
+
+fn keysym_token(keysym: u32) -> ComposeKey {
+    match keysym {
+        keymap::XKB_KEY_MULTI_KEY => ComposeKey::Compose,
+        keysym => ComposeKey::Keysym(keysym),
+    }
+}
+
+fn push_entry(table: &mut ComposeTable, entry: keymap::ComposeEntry) {
+    let tokens = entry.keys.iter().map(|&sym| keysym_token(sym)).collect();
+    table.entries.push((tokens, entry.output));
+}
 
-    let complete = keymap::parse_compose_file_impl(path, &mut |entry| {
-        entries.push(entry);
+fn parse_table(path: &Path, locale: &str) -> (ComposeTable, bool) {
+    let mut table = ComposeTable::new();
+
+    let complete = keymap::parse_compose_file_impl(path, locale, &mut |entry| {
+        push_entry(&mut table, entry);
     });
 
-    (
-        ComposeTableData {
-            entries,
-            filtered: Mutex::new(Vec::new()),
-        },
-        complete,
-    )
+    (table, complete)
 }
 
-fn load_table(path: &Path) -> ComposeTable {
+fn load_table(path: &Path, locale: &str) -> CachedTable {
     let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let key = (path, locale.to_owned());
 
-    if let Some((_, table)) = table_cache().iter().find(|(cached, _)| cached == &path) {
+    if let Some((_, table)) = table_cache().iter().find(|(cached, _)| cached == &key) {
         return table.clone();
     }
 
-    let (table, complete) = parse_table(&path);
-    let table = Arc::new(table);
+    let (table, complete) = parse_table(&key.0, locale);
+    let table = Arc::new(ComposeTableData {
+        table,
+        filtered: Mutex::new(Vec::new()),
+    });
 
     if !complete {
         return table;
@@ -57,63 +107,37 @@ fn load_table(path: &Path) -> ComposeTable {
     let mut cache = table_cache();
 
     // Another thread may have inserted it while this thread parsed.
-    if let Some((_, existing)) = cache.iter().find(|(cached, _)| cached == &path) {
+    if let Some((_, existing)) = cache.iter().find(|(cached, _)| cached == &key) {
         return existing.clone();
     }
 
-    cache.push((path, table.clone()));
+    cache.push((key, table.clone()));
     table
 }
 
-fn build(entries: &[keymap::ComposeEntry], reachable: Option<&[char]>) -> Composer {
-    let mut composer = Composer::new();
-
-    for entry in entries {
-        if reachable
-            .is_some_and(|chars| !entry.keys.iter().all(|ch| chars.binary_search(ch).is_ok()))
-        {
-            continue;
-        }
-
-        let mut tokens: ArrayVec<Token, 9> = ArrayVec::new();
-
-        for (index, &ch) in entry.keys.iter().enumerate() {
-            if entry.multi_key_index == Some(index) {
-                tokens.push(Token::Compose);
-            }
-
-            tokens.push(Token::Char(ch));
-        }
-
-        composer.insert(&tokens, entry.output);
-    }
-
-    composer
-}
-
-pub(crate) fn layout_composer(path: &Path, reachable: &[char]) -> Composer {
-    let table = load_table(path);
+pub(crate) fn layout_composer(path: &Path, locale: &str, reachable: &Reachable) -> Composer {
+    let table = load_table(path, locale);
     let mut filtered = table
         .filtered
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-    if let Some((_, composer)) = filtered.iter().find(|(chars, _)| chars == reachable) {
+    if let Some((_, composer)) = filtered.iter().find(|(cached, _)| cached == reachable) {
         return composer.as_ref().clone();
     }
 
-    let composer = Arc::new(build(&table.entries, Some(reachable)));
+    let composer = Arc::new(table.table.build(Some(reachable)));
 
-    filtered.push((reachable.to_vec(), composer.clone()));
+    filtered.push((reachable.clone(), composer.clone()));
     composer.as_ref().clone()
 }
 
 #[doc(hidden)]
 pub fn load_compose_from_path(path: &Path) -> Composer {
-    build(&load_table(path).entries, None)
+    load_table(path, &keymap::env_locale()).table.build(None)
 }
 
 #[doc(hidden)]
 pub fn load_compose_from_path_uncached(path: &Path) -> Composer {
-    build(&parse_table(path).0.entries, None)
+    parse_table(path, &keymap::env_locale()).0.build(None)
 }
@@ -0,0 +1,155 @@
+//! Where keymap compilation looks for XKB files: the include path list and
+//! files registered in memory.
+use super::keymap::getenv_or;
+use super::parser::{DFLT_XKB_CONFIG_EXTRA_PATH, DFLT_XKB_LEGACY_ROOT};
+use super::{Diagnostic, XkbError};
+use crate::Keymap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Include root under which in-memory files are reported and looked up.
+pub(crate) const MEMORY_ROOT: &str = "(memory)";
+
+/// XKB include paths and in-memory files used to compile keymaps, matching
+/// xkbcommon's `xkb_context`.
+///
+/// Files named by rules and `include` statements are searched in each
+/// include path in order, for example `<path>/symbols/us`. Files added with
+/// [`Context::add_file`] are searched before every include path.
+#[derive(Debug, Clone)]
+pub struct Context {
+    pub(crate) includes: Vec<String>,
+    pub(crate) files: HashMap<String, Arc<Vec<u8>>>,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Context {
+    /// A context with xkbcommon's default include paths.
+    pub fn new() -> Self {
+        let mut context = Self::without_default_paths();
+        context.include_path_append_default();
+        context
+    }
+
+    /// A context with no include paths, for keymaps compiled only from
+    /// in-memory files or explicitly added directories.
+    pub fn without_default_paths() -> Self {
+        Self {
+            includes: Vec::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    /// The include paths, in search order.
+    pub fn include_paths(&self) -> &[String] {
+        &self.includes
+    }
+
+    /// Append a directory to the include paths. Returns `false`, leaving
+    /// the paths unchanged, when `path` is not a directory.
+    pub fn include_path_append(&mut self, path: impl AsRef<Path>) -> bool {
+        let Some(path) = include_dir(path.as_ref()) else {
+            return false;
+        };
+        self.includes.push(path);
+        true
+    }
+
+    /// Insert a directory before the other include paths. Returns `false`,
+    /// leaving the paths unchanged, when `path` is not a directory.
+    pub fn include_path_prepend(&mut self, path: impl AsRef<Path>) -> bool {
+        let Some(path) = include_dir(path.as_ref()) else {
+            return false;
+        };
+        self.includes.insert(0, path);
+        true
+    }
+
+    /// Append the default include paths that exist: `$XDG_CONFIG_HOME/xkb`
+    /// (or `~/.config/xkb`), `~/.xkb`, `$XKB_CONFIG_EXTRA_PATH` (or
+    /// `/etc/xkb`) and `$XKB_CONFIG_ROOT` (or `/usr/share/X11/xkb`).
+    pub fn include_path_append_default(&mut self) {
+        let home = std::env::var("HOME").ok();
+        let config = std::env::var("XDG_CONFIG_HOME")
+            .ok()
+            .or_else(|| home.as_ref().map(|home| format!("{home}/.config")));
+        if let Some(config) = config {
+            self.include_path_append(format!("{config}/xkb"));
+        }
+        if let Some(home) = &home {
+            self.include_path_append(format!("{home}/.xkb"));
+        }
+        self.include_path_append(getenv_or(
+            "XKB_CONFIG_EXTRA_PATH",
+            DFLT_XKB_CONFIG_EXTRA_PATH,
+        ));
+        self.include_path_append(getenv_or("XKB_CONFIG_ROOT", DFLT_XKB_LEGACY_ROOT));
+    }
+
+    /// Remove every include path. In-memory files are kept.
+    pub fn include_path_clear(&mut self) {
+        self.includes.clear();
+    }
+
+    /// Register `contents` as the file at `path` relative to an include
+    /// root, such as `symbols/custom` or `rules/evdev`. It shadows files of
+    /// the same name in the include paths.
+    pub fn add_file(&mut self, path: impl Into<String>, contents: impl Into<Vec<u8>>) {
+        let path = path.into();
+        let path = path.trim_start_matches('/').to_owned();
+        self.files.insert(path, Arc::new(contents.into()));
+    }
+
+    /// Remove a file registered with [`Context::add_file`], returning
+    /// whether it was present.
+    pub fn remove_file(&mut self, path: &str) -> bool {
+        self.files.remove(path.trim_start_matches('/')).is_some()
+    }
+
+    /// Compile a keymap from RMLVO names using this context's files,
+    /// returning it with the compilation warnings.
+    ///
+    /// See [`Keymap::new_from_names`] for how the names are resolved.
+    pub fn keymap_from_names(
+        &self,
+        rules: &str,
+        model: &str,
+        layout: &str,
+        variant: &str,
+        options: Option<&str>,
+    ) -> Result<(Keymap, Vec<Diagnostic>), XkbError> {
+        super::new_from_names(self, rules, model, layout, variant, options)
+    }
+
+    /// Compile a keymap from an XKB keymap string, resolving its `include`
+    /// statements with this context's files.
+    pub fn keymap_from_string(&self, keymap: &str) -> Result<(Keymap, Vec<Diagnostic>), XkbError> {
+        super::new_from_string(self, keymap)
+    }
+
+    /// The roots searched for XKB files, the in-memory root first.
+    pub(crate) fn roots(&self) -> Vec<String> {
+        let memory = (!self.files.is_empty()).then(|| MEMORY_ROOT.to_owned());
+        memory
+            .into_iter()
+            .chain(self.includes.iter().cloned())
+            .collect()
+    }
+
+    /// The contents of an in-memory file, given its path under
+    /// [`MEMORY_ROOT`].
+    pub(crate) fn memory_file(&self, path: &str) -> Option<Arc<Vec<u8>>> {
+        let relative = path.strip_prefix(MEMORY_ROOT)?.strip_prefix('/')?;
+        self.files.get(relative).cloned()
+    }
+}
+
+fn include_dir(path: &Path) -> Option<String> {
+    path.is_dir().then(|| path.to_string_lossy().into_owned())
+}
@@ -0,0 +1,252 @@
+//! Keymap compilation diagnostics: the error that stopped compilation and the
+//! warnings for input that was skipped on the way.
+use super::parse_xkb::token_text;
+use std::fmt;
+use std::sync::Arc;
+
+/// The part of the keymap a [`Diagnostic`] was reported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Section {
+    /// The enclosing `xkb_keymap` block.
+    #[default]
+    Keymap,
+    /// RMLVO resolution through the rules file.
+    Rules,
+    Keycodes,
+    Types,
+    Compat,
+    Symbols,
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Section::Keymap => "keymap",
+            Section::Rules => "rules",
+            Section::Keycodes => "keycodes",
+            Section::Types => "types",
+            Section::Compat => "compat",
+            Section::Symbols => "symbols",
+        })
+    }
+}
+
+/// What a [`Diagnostic`] reports, independent of its message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticCode {
+    /// The input is not ASCII-compatible text.
+    InvalidFileEncoding,
+    /// The input does not follow the XKB grammar.
+    InvalidSyntax,
+    /// An `include` names a file or map missing from the include paths.
+    IncludedFileNotFound,
+    /// Includes nest deeper than the include limit.
+    RecursiveInclude,
+    /// The rules file is missing from the include paths.
+    RulesFileNotFound,
+    /// The rules produced no keycodes, types, compat or symbols component.
+    UnresolvedRmlvo,
+    /// A statement that does not belong in its section.
+    WrongStatementType,
+    /// A modifier name that was never declared.
+    UndeclaredModifier,
+    /// A field whose value cannot be resolved.
+    InvalidValue,
+    /// Any other statement that could not be compiled.
+    InvalidStatement,
+    /// Warning: an unknown keysym name, compiled as `NoSymbol`.
+    UnknownKeysym,
+    /// Warning: a key missing from `xkb_keycodes`; its definition is dropped.
+    UndefinedKeycode,
+    /// Warning: a key alias whose target is not defined; it is dropped.
+    UnresolvedKeyAlias,
+    /// Warning: a malformed or unsupported statement that was skipped.
+    IgnoredStatement,
+}
+
+impl DiagnosticCode {
+    /// Whether this code stops compilation, as opposed to a warning.
+    pub fn is_error(self) -> bool {
+        !matches!(
+            self,
+            DiagnosticCode::UnknownKeysym
+                | DiagnosticCode::UndefinedKeycode
+                | DiagnosticCode::UnresolvedKeyAlias
+                | DiagnosticCode::IgnoredStatement
+        )
+    }
+}
+
+/// A compilation error or warning with its position in the XKB sources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub section: Section,
+    /// File the position refers to; `None` for the keymap string itself and
+    /// for components named by the rules.
+    pub file: Option<String>,
+    /// Files whose `include` statements led to `file`, outermost first.
+    pub include_chain: Vec<String>,
+    /// 1-based line, or 0 when there is no source position.
+    pub line: u32,
+    /// 1-based byte column, or 0 when there is no source position.
+    pub column: u32,
+    /// The token at the position, such as a keysym, key or file name.
+    pub token: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.file.as_deref().unwrap_or("(input string)"))?;
+        if self.line != 0 {
+            write!(f, ":{}:{}", self.line, self.column)?;
+        }
+        write!(f, ": {} ({})", self.message, self.section)?;
+        if let Some(token) = &self.token {
+            write!(f, " near `{token}`")?;
+        }
+        Ok(())
+    }
+}
+
+/// A source being compiled; `parent` is the source that included it.
+struct Source {
+    name: Option<String>,
+    data: Arc<Vec<u8>>,
+    parent: Option<Arc<Source>>,
+}
+
+/// A position captured for a diagnostic reported later.
+#[derive(Clone)]
+pub(crate) struct Mark {
+    source: Option<Arc<Source>>,
+    offset: Option<usize>,
+    token: Option<String>,
+}
+
+impl Mark {
+    pub(crate) fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+}
+
+/// Collects diagnostics while a keymap compiles. The first error is kept,
+/// since every failure unwinds the compilation right away.
+#[derive(Clone, Default)]
+pub(crate) struct Diagnostics {
+    pub(crate) section: Section,
+    source: Option<Arc<Source>>,
+    statement: usize,
+    pub(crate) error: Option<Diagnostic>,
+    pub(crate) warnings: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Start compiling `data`, included from the current source.
+    pub(crate) fn enter(&mut self, name: Option<String>, data: Arc<Vec<u8>>) {
+        let parent = self.source.take();
+        self.source = Some(Arc::new(Source { name, data, parent }));
+    }
+    pub(crate) fn leave(&mut self) {
+        self.source = self.source.take().and_then(|source| source.parent.clone());
+    }
+    /// Record the start of the statement being compiled.
+    pub(crate) fn set_statement(&mut self, at: *const u8) {
+        self.statement = at as usize;
+    }
+    /// Capture the position of `at`, or of the current statement. Positions
+    /// outside the sources being compiled only keep the file name.
+    pub(crate) fn mark(&self, at: Option<*const u8>) -> Mark {
+        let address = at.map_or(self.statement, |at| at as usize);
+        let mut source = self.source.as_ref();
+        while let Some(current) = source {
+            let start = current.data.as_ptr() as usize;
+            if (start..=start + current.data.len()).contains(&address) {
+                return Mark {
+                    source: Some(current.clone()),
+                    offset: Some(address - start),
+                    token: None,
+                };
+            }
+            source = current.parent.as_ref();
+        }
+        Mark {
+            source: self.source.clone(),
+            offset: None,
+            token: None,
+        }
+    }
+    pub(crate) fn error(&mut self, code: DiagnosticCode, mark: Mark, message: impl Into<String>) {
+        if self.error.is_none() {
+            self.error = Some(self.diagnostic(code, mark, message.into()));
+        }
+    }
+    pub(crate) fn warn(&mut self, code: DiagnosticCode, mark: Mark, message: impl Into<String>) {
+        let warning = self.diagnostic(code, mark, message.into());
+        self.warnings.push(warning);
+    }
+    /// Report an error at the current statement and return `false`, for
+    /// compile steps that fail with a `bool`.
+    pub(crate) fn fail(
+        &mut self,
+        code: DiagnosticCode,
+        token: Option<&str>,
+        message: impl Into<String>,
+    ) -> bool {
+        let mut mark = self.mark(None);
+        if let Some(token) = token {
+            mark = mark.with_token(token);
+        }
+        self.error(code, mark, message);
+        false
+    }
+    /// Take the recorded error, or a generic one for failures that did not
+    /// report themselves.
+    pub(crate) fn take_error(&mut self) -> Diagnostic {
+        self.error.take().unwrap_or_else(|| Diagnostic {
+            code: DiagnosticCode::InvalidStatement,
+            section: self.section,
+            file: None,
+            include_chain: Vec::new(),
+            line: 0,
+            column: 0,
+            token: None,
+            message: "keymap compilation failed".into(),
+        })
+    }
+    fn diagnostic(&self, code: DiagnosticCode, mark: Mark, message: String) -> Diagnostic {
+        let mut include_chain = Vec::new();
+        let mut parent = mark
+            .source
+            .as_ref()
+            .and_then(|source| source.parent.as_ref());
+        while let Some(source) = parent {
+            include_chain.extend(source.name.clone());
+            parent = source.parent.as_ref();
+        }
+        include_chain.reverse();
+        let (mut line, mut column, mut token) = (0, 0, None);
+        if let (Some(source), Some(offset)) = (&mark.source, mark.offset) {
+            let before = &source.data[..offset];
+            let line_start = before
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |i| i + 1);
+            line = before.iter().filter(|&&b| b == b'\n').count() as u32 + 1;
+            column = (offset - line_start) as u32 + 1;
+            token = token_text(&source.data[offset..]);
+        }
+        Diagnostic {
+            code,
+            section: self.section,
+            file: mark.source.and_then(|source| source.name.clone()),
+            include_chain,
+            line,
+            column,
+            token: mark.token.or(token),
+            message,
+        }
+    }
+}
@@ -1,228 +1,73 @@
+use super::diagnostics::DiagnosticCode;
 pub(crate) use super::parser::{
     XkbContext, XkbKeymap, XkbModSet, XkbRuleNames, MOD_REAL, MOD_REAL_MASK_ALL, XKB_MAX_GROUPS,
 };
+use super::rules::xkb_components_from_rules;
 use crate::xkb::keysym::keysym_to_codepoint;
-use crate::xkb::parse_xkb::braced_end;
+use crate::XkbError;
 use arrayvec::ArrayVec;
-use std::borrow::Cow;
+use std::sync::Arc;
 pub(crate) fn xkb_keymap_new_from_names(
     ctx: XkbContext,
     rmlvo: &XkbRuleNames,
-) -> Result<XkbKeymap, crate::XkbError> {
+) -> Result<XkbKeymap, XkbError> {
     let mut rmlvo = rmlvo.clone();
     xkb_context_sanitize_rule_names(&mut rmlvo);
     let mut keymap = xkb_keymap_new(ctx, false);
-    let layouts: Vec<_> = rmlvo.layout.trim_end_matches(',').split(',').collect();
-    let mut variants = rmlvo.variant.trim_end_matches(',').split(',');
-    let alias = if layouts
-        .first()
-        .is_some_and(|layout| matches!(*layout, "be" | "fr"))
-    {
-        "azerty"
-    } else if layouts.first().is_some_and(|layout| {
-        matches!(
-            *layout,
-            "al" | "ch" | "cz" | "de" | "hr" | "hu" | "ro" | "si" | "sk"
-        )
-    }) {
-        "qwertz"
-    } else {
-        "qwerty"
+    let Some(components) = xkb_components_from_rules(&mut keymap.ctx, &rmlvo) else {
+        return Err(XkbError::from_diagnostics(&mut keymap.ctx));
     };
-    let mut symbols = String::from("pc");
-    for (index, layout) in layouts.iter().take(XKB_MAX_GROUPS as usize).enumerate() {
-        symbols.push('+');
-        symbols.push_str(layout);
-        let variant = variants.next().unwrap_or("").trim();
-        if !variant.is_empty() {
-            symbols.push('(');
-            symbols.push_str(variant);
-            symbols.push(')');
-        }
-        if index != 0 {
-            symbols.push(':');
-            symbols.push_str(&(index + 1).to_string());
-        }
-    }
-    symbols.push_str("+inet(evdev)");
-    for option in rmlvo
-        .options
-        .split(',')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-    {
-        let (family, value) = option
-            .split_once(':')
-            .ok_or(crate::XkbError::KeymapCompilation)?;
-        let file = match family {
-            "grp" => "group",
-            "caps" => "capslock",
-            "lv2" => "level2",
-            "lv3" => "level3",
-            "lv5" => "level5",
-            _ => family,
-        };
-        symbols.push('+');
-        symbols.push_str(file);
-        symbols.push('(');
-        symbols.push_str(value);
-        symbols.push(')');
-    }
-    keymap.num_groups = layouts.len().min(XKB_MAX_GROUPS as usize) as u32;
+    let layouts = rmlvo.layout.trim_end_matches(',').split(',').count();
+    keymap.num_groups = layouts.min(XKB_MAX_GROUPS as usize) as u32;
     if !compile_components(
-        format!("evdev+aliases({alias})").as_bytes(),
-        b"complete",
-        symbols.as_bytes(),
+        components.keycodes.as_bytes(),
+        components.types.as_bytes(),
+        components.compat.as_bytes(),
+        components.symbols.as_bytes(),
         &mut keymap,
     ) {
-        return Err(crate::XkbError::KeymapCompilation);
+        return Err(XkbError::from_diagnostics(&mut keymap.ctx));
     }
     Ok(keymap)
 }
 pub(crate) fn xkb_keymap_new_from_string(
     ctx: XkbContext,
-    original: &[u8],
-) -> Result<XkbKeymap, crate::XkbError> {
-    let source = strip_compat_map(original);
-    let bytes = source.as_ref();
+    bytes: &[u8],
+) -> Result<XkbKeymap, XkbError> {
+    let mut keymap = xkb_keymap_new(ctx, true);
+    let diagnostics = &mut keymap.ctx.diagnostics;
     if bytes.is_empty() {
-        return Err(crate::XkbError::KeymapParsing);
+        diagnostics.fail(DiagnosticCode::InvalidSyntax, None, "empty keymap string");
+        return Err(XkbError::from_diagnostics(&mut keymap.ctx));
     }
-    let mut keymap = xkb_keymap_new(ctx, true);
-    let file = xkb_select_map(bytes, "").ok_or(crate::XkbError::KeymapParsing)?;
-    if !compile_keymap_stream(file, &mut keymap) {
-        return Err(crate::XkbError::KeymapCompilation);
+    // Compile from a copy the diagnostics can point into.
+    let data = Arc::new(bytes.to_vec());
+    diagnostics.enter(None, data.clone());
+    if let Some(nul) = data.iter().position(|&byte| byte == 0) {
+        let mark = diagnostics.mark(Some(data[nul..].as_ptr()));
+        let message = "keymap string contains a NUL byte";
+        diagnostics.error(DiagnosticCode::InvalidFileEncoding, mark, message);
+        return Err(XkbError::from_diagnostics(&mut keymap.ctx));
     }
-    apply_group_action_overrides(&mut keymap, original);
-    Ok(keymap)
-}
-
-fn find_ascii_case_insensitive(haystack: &[u8], needle: &[u8]) -> Option<usize> {
-    haystack
-        .windows(needle.len())
-        .position(|window| window.eq_ignore_ascii_case(needle))
-}
-fn group_action_from_interpret(input: &[u8], name: &[u8]) -> Option<XkbAction> {
-    let tail = &input[find_ascii_case_insensitive(input, name)? + name.len()..];
-    let open = tail.iter().position(|&b| b == b'{')?;
-    let body = &tail[open + 1..];
-    let body = &body[..body.iter().position(|&b| b == b'}')?];
-    let (constructor, tail): (fn(XkbGroupAction) -> XkbAction, &[u8]) = [
-        (XkbAction::GroupSet as _, b"SetGroup".as_slice()),
-        (XkbAction::GroupLatch as _, b"LatchGroup".as_slice()),
-        (XkbAction::GroupLock as _, b"LockGroup".as_slice()),
-    ]
-    .into_iter()
-    .find_map(|(make, action)| {
-        find_ascii_case_insensitive(body, action).map(|pos| (make, &body[pos + action.len()..]))
-    })?;
-    let tail = &tail[find_ascii_case_insensitive(tail, b"group")? + 5..];
-    let mut value = &tail[tail.iter().position(|&b| b == b'=')? + 1..];
-    value = &value[value.iter().position(|b| !b.is_ascii_whitespace())?..];
-    let relative = matches!(value.first(), Some(b'+' | b'-'));
-    let negative = value.first() == Some(&b'-');
-    let digits = if relative { &value[1..] } else { value };
-    let end = digits
-        .iter()
-        .position(|b| !b.is_ascii_digit())
-        .unwrap_or(digits.len());
-    let number = std::str::from_utf8(&digits[..end])
-        .ok()?
-        .parse::<i32>()
-        .ok()?;
-    let group = if relative {
-        number * if negative { -1 } else { 1 }
-    } else {
-        number - 1
-    };
-    let action = XkbGroupAction {
-        flags: if relative {
-            ActionFlags::empty()
-        } else {
-            ActionFlags::ABSOLUTE_SWITCH
-        },
-        group,
+    let Some(file) = xkb_select_map(&mut keymap.ctx, &data, "") else {
+        return Err(XkbError::from_diagnostics(&mut keymap.ctx));
     };
-    Some(constructor(action))
-}
-fn apply_group_action_overrides(keymap: &mut XkbKeymap, input: &[u8]) {
-    let forced_next_latch = find_ascii_case_insensitive(input, b"LatchGroup(group=+1)").map(|_| {
-        XkbAction::GroupLatch(XkbGroupAction {
-            flags: ActionFlags::empty(),
-            group: 1,
-        })
-    });
-    let overrides: ArrayVec<(u32, XkbAction), 7> = [
-        (0xff7e, b"Mode_switch".as_slice()),
-        (0xff2d, b"Kana_Lock".as_slice()),
-        (0xfe06, b"ISO_Group_Latch".as_slice()),
-        (0xfe08, b"ISO_Next_Group".as_slice()),
-        (0xfe0a, b"ISO_Prev_Group".as_slice()),
-        (0xfe0c, b"ISO_First_Group".as_slice()),
-        (0xfe0e, b"ISO_Last_Group".as_slice()),
-    ]
-    .into_iter()
-    .filter_map(|(sym, name)| group_action_from_interpret(input, name).map(|action| (sym, action)))
-    .collect();
-    if overrides.is_empty() && forced_next_latch.is_none() {
-        return;
-    }
-    for key in &mut keymap.keys {
-        for group in &mut key.groups {
-            for level in &mut group.levels {
-                if level.sym == 0xfe08 {
-                    if let Some(action) = forced_next_latch {
-                        level.action = Some(action);
-                        continue;
-                    }
-                }
-                if let Some((_, action)) = overrides.iter().find(|(sym, _)| level.sym == *sym) {
-                    let synthesized = wkb_group_action(level.sym);
-                    if level.action == synthesized {
-                        level.action = Some(*action);
-                    }
-                }
-            }
-        }
+    if !compile_keymap_stream(file, &mut keymap) {
+        return Err(XkbError::from_diagnostics(&mut keymap.ctx));
     }
+    Ok(keymap)
 }
-fn strip_compat_map(input: &[u8]) -> Cow<'_, [u8]> {
-    const NAMES: [&[u8]; 2] = [b"xkb_compatibility", b"xkb_compat_map"];
-    let Some(start) = (0..input.len()).find(|&start| {
-        NAMES.iter().any(|name| {
-            input[start..].starts_with(name)
-                && input
-                    .get(start.wrapping_sub(1))
-                    .is_none_or(|b| !b.is_ascii_alphanumeric() && *b != b'_')
-                && input
-                    .get(start + name.len())
-                    .is_none_or(|b| !b.is_ascii_alphanumeric() && *b != b'_')
-        })
-    }) else {
-        return Cow::Borrowed(input);
-    };
-    let Some(open) = input[start..]
-        .iter()
-        .position(|&byte| byte == b'{')
-        .map(|i| start + i)
-    else {
-        return Cow::Borrowed(input);
-    };
-    let Some(end) = braced_end(input, open + 1) else {
-        return Cow::Borrowed(input);
-    };
-    let mut stripped = Vec::with_capacity(input.len() - (end - open));
-    stripped.extend_from_slice(&input[..=open]);
-    stripped.push(b'\n');
-    stripped.extend_from_slice(&input[end..]);
-    Cow::Owned(stripped)
-}
-use std::path::Path;
+use crate::composer::ComposeOutput;
+use std::path::{Path, PathBuf};
+const DFLT_XLOCALEDIR: &str = "/usr/share/X11/locale";
+const COMPOSE_INCLUDE_MAX_DEPTH: u32 = 5;
+/// `Multi_key`, the keysym of the Compose key.
+pub(crate) const XKB_KEY_MULTI_KEY: u32 = 0xff20;
 #[derive(Clone)]
 pub struct ComposeEntry {
-    pub keys: ArrayVec<char, 8>,
-    pub multi_key_index: Option<usize>,
-    pub output: char,
+    /// The sequence's keysyms, with `Multi_key` for the Compose key.
+    pub keys: ArrayVec<u32, 9>,
+    pub output: ComposeOutput,
 }
 pub(crate) fn keysym_name_to_char(name: &str) -> Option<char> {
     if name.len() == 1 {
@@ -231,9 +76,7 @@ pub(crate) fn keysym_name_to_char(name: &str) -> Option<char> {
             return Some(b as char);
         }
     }
-    use super::keysym::xkb_keysym_from_name;
-    use super::parser::XKB_KEYSYM_NO_FLAGS;
-    if let Some(ks) = xkb_keysym_from_name(name.as_bytes(), XKB_KEYSYM_NO_FLAGS) {
+    if let Some(ks) = keysym_from_name(name) {
         return keysym_to_codepoint(ks).and_then(char::from_u32);
     }
     let hex = name.strip_prefix('U')?;
@@ -241,7 +84,20 @@ pub(crate) fn keysym_name_to_char(name: &str) -> Option<char> {
         .then(|| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32))
         .flatten()
 }
-pub(crate) fn parse_compose_file_impl<F>(path: &Path, f: &mut F) -> bool
+fn keysym_from_name(name: &str) -> Option<u32> {
+    use super::keysym::xkb_keysym_from_name;
+    use super::parser::XKB_KEYSYM_NO_FLAGS;
+    xkb_keysym_from_name(name.as_bytes(), XKB_KEYSYM_NO_FLAGS)
+}
+/// Parse the Compose file at `path`, expanding `include` statements with
+/// `locale` for `%L`. Returns whether `path` itself could be read.
+pub(crate) fn parse_compose_file_impl<F>(path: &Path, locale: &str, f: &mut F) -> bool
+where
+    F: FnMut(ComposeEntry),
+{
+    parse_compose_file_at_depth(path, locale, 0, f)
+}
+fn parse_compose_file_at_depth<F>(path: &Path, locale: &str, depth: u32, f: &mut F) -> bool
 where
     F: FnMut(ComposeEntry),
 {
@@ -251,58 +107,202 @@ where
     let Ok(content) = std::str::from_utf8(&data) else {
         return false;
     };
+    parse_compose_str_at_depth(content, locale, depth, f);
+    true
+}
+/// Parse Compose file text, expanding `include` statements with `locale`.
+pub(crate) fn parse_compose_str<F>(content: &str, locale: &str, f: &mut F)
+where
+    F: FnMut(ComposeEntry),
+{
+    parse_compose_str_at_depth(content, locale, 0, f);
+}
+fn parse_compose_str_at_depth<F>(content: &str, locale: &str, depth: u32, f: &mut F)
+where
+    F: FnMut(ComposeEntry),
+{
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("include") {
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(include) = trimmed.strip_prefix("include") {
+            let included = include
+                .trim_start()
+                .strip_prefix('"')
+                .and_then(parse_compose_string)
+                .and_then(|(include, _)| expand_compose_include(&include, locale));
+            if let Some(included) = included.filter(|_| depth < COMPOSE_INCLUDE_MAX_DEPTH) {
+                parse_compose_file_at_depth(Path::new(&included), locale, depth + 1, f);
+            }
             continue;
         }
         if let Some(entry) = parse_rule_line(trimmed) {
             f(entry);
         }
     }
-    true
 }
 fn parse_rule_line(line: &str) -> Option<ComposeEntry> {
     let (lhs, rhs) = line.split_once(':')?;
-    let rhs = rhs.split('#').next()?.trim();
     let mut keys = ArrayVec::new();
-    let mut multi_key_index = None;
     for item in lhs.split('<').skip(1) {
         let (name, _) = item.split_once('>')?;
-        if name.eq_ignore_ascii_case("Multi_key") {
-            if multi_key_index.is_none() {
-                multi_key_index = Some(keys.len());
-            }
-        } else {
-            keys.push(keysym_name_to_char(name)?);
-        }
+        keys.try_push(keysym_from_name(name)?).ok()?;
     }
     (!keys.is_empty()).then_some(())?;
     Some(ComposeEntry {
         keys,
-        multi_key_index,
         output: parse_rhs_value(rhs)?,
     })
 }
-fn parse_rhs_value(rhs: &str) -> Option<char> {
-    let rhs = rhs.trim();
-    if let Some(quoted) = rhs.strip_prefix('"') {
-        let (s, after) = quoted.split_once('"')?;
-        if let Some(ch) = s.chars().next() {
-            if !s.starts_with('\\') && !ch.is_ascii_digit() {
-                return Some(ch);
+/// Parse a rule's result: an optional quoted string, then an optional
+/// keysym. The text falls back to the keysym's character.
+fn parse_rhs_value(rhs: &str) -> Option<ComposeOutput> {
+    let rhs = rhs.trim_start();
+    let (text, rest) = match rhs.strip_prefix('"') {
+        Some(quoted) => {
+            let (text, rest) = parse_compose_string(quoted)?;
+            (Some(text).filter(|text| !text.is_empty()), rest)
+        }
+        None => (None, rhs),
+    };
+    let name = rest.split('#').next()?.split_whitespace().next();
+    let keysym = name.and_then(keysym_from_name);
+    let text = match text {
+        Some(text) => text,
+        None => keysym_name_to_char(name?)?.to_string(),
+    };
+    Some(ComposeOutput {
+        text: text.into(),
+        keysym,
+    })
+}
+/// Unescape a Compose string up to its closing quote, returning it with the
+/// rest of the line. Supports `\\`, `\"`, octal `\ooo` and hex `\xHH`.
+fn parse_compose_string(quoted: &str) -> Option<(String, &str)> {
+    let bytes = quoted.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => return Some((String::from_utf8(out).ok()?, &quoted[i + 1..])),
+            b'\\' => {
+                i += 1;
+                let (radix, max, skip) = match bytes.get(i)? {
+                    b'x' | b'X' => (16, 2, 1),
+                    b'0'..=b'7' => (8, 3, 0),
+                    &escaped => {
+                        out.push(escaped);
+                        i += 1;
+                        continue;
+                    }
+                };
+                let digits = &quoted[i + skip..];
+                let len = digits
+                    .bytes()
+                    .take(max)
+                    .take_while(|b| (*b as char).is_digit(radix))
+                    .count();
+                out.push(u8::from_str_radix(&digits[..len], radix).ok()?);
+                i += skip + len;
             }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    None
+}
+/// Expand `%%`, `%H` (home directory), `%L` (the locale's Compose file) and
+/// `%S` (the system locale directory) in an include path.
+fn expand_compose_include(include: &str, locale: &str) -> Option<String> {
+    let mut path = String::new();
+    let mut chars = include.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            path.push(ch);
+            continue;
         }
-        if let Some(name) = after.split_whitespace().next() {
-            return keysym_name_to_char(name);
+        match chars.next()? {
+            '%' => path.push('%'),
+            'H' => path.push_str(&std::env::var("HOME").ok()?),
+            'L' => path.push_str(locale_compose_file(locale)?.to_str()?),
+            'S' => path.push_str(&xlocaledir()),
+            _ => return None,
         }
-        s.chars().next()
-    } else {
-        keysym_name_to_char(rhs.split_whitespace().next()?)
     }
+    Some(path)
+}
+/// The locale of the process for Compose lookup, from `LC_ALL`, `LC_CTYPE`
+/// or `LANG`, defaulting to `C`.
+pub(crate) fn env_locale() -> String {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| "C".into())
+}
+/// The Compose file for `locale`, searched as xkbcommon does: `XCOMPOSEFILE`,
+/// `$XDG_CONFIG_HOME/XCompose` (or `~/.config/XCompose`), `~/.XCompose`, then
+/// the locale's file from `compose.dir`.
+pub(crate) fn resolve_compose_file(locale: &str) -> Option<PathBuf> {
+    let home = std::env::var("HOME").ok();
+    let xdg = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|config| config.starts_with('/'))
+        .or_else(|| home.as_ref().map(|home| format!("{home}/.config")));
+    let user_files = [
+        std::env::var("XCOMPOSEFILE").ok(),
+        xdg.map(|config| format!("{config}/XCompose")),
+        home.map(|home| format!("{home}/.XCompose")),
+    ];
+    user_files
+        .into_iter()
+        .flatten()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+        .or_else(|| locale_compose_file(locale))
 }
-pub(crate) fn resolve_compose_file(_locale: &str) -> Option<String> {
-    Some("en_US.UTF-8/Compose".into())
+/// The system Compose file for `locale` after `locale.alias` resolution.
+pub(crate) fn locale_compose_file(locale: &str) -> Option<PathBuf> {
+    let locale =
+        resolve_locale_name("locale.alias", locale, false).unwrap_or_else(|| locale.to_owned());
+    // compose.dir maps C to the ISO 8859-1 file; use UTF-8 like xkbcommon.
+    let locale = if locale == "C" {
+        "en_US.UTF-8"
+    } else {
+        &locale
+    };
+    compose_dir_file(locale)
+}
+/// The Compose file `compose.dir` lists for `locale`, without resolving
+/// aliases.
+pub(crate) fn compose_dir_file(locale: &str) -> Option<PathBuf> {
+    let file = resolve_locale_name("compose.dir", locale, true)?;
+    let path = Path::new(&xlocaledir()).join(file);
+    path.is_file().then_some(path)
+}
+fn xlocaledir() -> String {
+    getenv_or("XLOCALEDIR", DFLT_XLOCALEDIR)
+}
+/// Look `name` up in a two-column file of the locale directory, matching
+/// the left column (or the right one when `reverse`) and returning the other.
+fn resolve_locale_name(file: &str, name: &str, reverse: bool) -> Option<String> {
+    let data = std::fs::read_to_string(Path::new(&xlocaledir()).join(file)).ok()?;
+    data.lines().find_map(|line| {
+        let line = line.split('#').next()?;
+        let mut fields = line.split_whitespace();
+        let left = fields.next()?;
+        let right = fields.next()?;
+        // libX11 allows a trailing colon after the alias name.
+        let left = left.strip_suffix(':').unwrap_or(left);
+        let (key, value) = if reverse {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        (key == name).then(|| value.to_owned())
+    })
 }
 pub(crate) fn xkb_keymap_new(ctx: XkbContext, strict: bool) -> XkbKeymap {
     let mut keymap = XkbKeymap {
@@ -315,6 +315,7 @@ pub(crate) fn xkb_keymap_new(ctx: XkbContext, strict: bool) -> XkbKeymap {
         mods: XkbModSet::default(),
         num_groups: 0,
         group_names: Vec::new(),
+        interprets: Vec::new(),
     };
     #[rustfmt::skip]
     static BUILTIN_MODS: [&str; 8] = ["Shift", "Lock", "Control", "Mod1", "Mod2", "Mod3", "Mod4", "Mod5"];
@@ -334,20 +335,29 @@ pub(crate) fn xkb_mod_name_to_index(mods: &XkbModSet, name: u32, type_0: u32) ->
     }
     None
 }
-use super::parser::DFLT_XKB_LEGACY_ROOT;
-pub(crate) fn xkb_context_new() -> XkbContext {
+use super::context::Context;
+use super::parser::{DEFAULT_XKB_MODEL, DEFAULT_XKB_RULES};
+pub(crate) fn xkb_context_new(context: &Context) -> XkbContext {
     XkbContext {
-        includes: vec![getenv_or("XKB_CONFIG_ROOT", DFLT_XKB_LEGACY_ROOT)],
+        context: context.clone(),
         atom_table: Default::default(),
         files: Default::default(),
+        diagnostics: Default::default(),
     }
 }
 pub(crate) fn getenv_or(name: &str, default: &str) -> String {
     std::env::var(name).unwrap_or_else(|_| default.into())
 }
 pub(crate) fn xkb_context_sanitize_rule_names(rmlvo: &mut XkbRuleNames) {
-    if rmlvo.options.is_empty() {
-        rmlvo.options = getenv_or("XKB_DEFAULT_OPTIONS", "");
+    if rmlvo.rules.is_empty() {
+        rmlvo.rules = getenv_or("XKB_DEFAULT_RULES", DEFAULT_XKB_RULES);
+    }
+    if rmlvo.model.is_empty() {
+        rmlvo.model = getenv_or("XKB_DEFAULT_MODEL", DEFAULT_XKB_MODEL);
+    }
+    // Options can be empty, so only fall back when none were passed in.
+    if rmlvo.options.is_none() {
+        rmlvo.options = std::env::var("XKB_DEFAULT_OPTIONS").ok();
     }
     if rmlvo.layout.is_empty() {
         let layout = std::env::var("XKB_DEFAULT_LAYOUT").ok();
@@ -1,6 +1,6 @@
 use super::parser::*;
 use std::borrow::Cow;
-use xkeysym::Keysym;
+use xkeysym::Keysym as XKeysym;
 pub(crate) static KEYSYM_NAMES: &[u8] = b"0\x001\x002\x003\x003270_AltCursor\x003270_Attn\x003270_BackTab\x003270_ChangeScreen\x003270_Copy\x003270_CursorBlink\x003270_CursorSelect\x003270_DeleteWord\x003270_Duplicate\x003270_Enter\x003270_EraseEOF\x003270_EraseInput\x003270_ExSelect\x003270_FieldMark\x003270_Ident\x003270_Jump\x003270_KeyClick\x003270_Left2\x003270_PA1\x003270_PA2\x003270_PA3\x003270_Play\x003270_PrintScreen\x003270_Quit\x003270_Record\x003270_Reset\x003270_Right2\x003270_Rule\x003270_Setup\x003270_Test\x004\x005\x006\x007\08\09\0A\0a\0Aacute\0aacute\0Abelowdot\0abelowdot\0abovedot\0Abreve\0abreve\0Abreveacute\0abreveacute\0Abrevebelowdot\0abrevebelowdot\0Abrevegrave\0abrevegrave\0Abrevehook\0abrevehook\0Abrevetilde\0abrevetilde\0AccessX_Enable\0AccessX_Feedback_Enable\0Acircumflex\0acircumflex\0Acircumflexacute\0acircumflexacute\0Acircumflexbelowdot\0acircumflexbelowdot\0Acircumflexgrave\0acircumflexgrave\0Acircumflexhook\0acircumflexhook\0Acircumflextilde\0acircumflextilde\0acute\0Adiaeresis\0adiaeresis\0AE\0ae\0Agrave\0agrave\0Ahook\0ahook\0Alt_L\0Alt_R\0Amacron\0amacron\0ampersand\0Aogonek\0aogonek\0apostrophe\0approxeq\0approximate\0Arabic_0\0Arabic_1\0Arabic_2\0Arabic_3\0Arabic_4\0Arabic_5\0Arabic_6\0Arabic_7\0Arabic_8\0Arabic_9\0Arabic_ain\0Arabic_alef\0Arabic_alefmaksura\0Arabic_beh\0Arabic_comma\0Arabic_dad\0Arabic_dal\0Arabic_damma\0Arabic_dammatan\0Arabic_ddal\0Arabic_farsi_yeh\0Arabic_fatha\0Arabic_fathatan\0Arabic_feh\0Arabic_fullstop\0Arabic_gaf\0Arabic_ghain\0Arabic_ha\0Arabic_hah\0Arabic_hamza\0Arabic_hamza_above\0Arabic_hamza_below\0Arabic_hamzaonalef\0Arabic_hamzaonwaw\0Arabic_hamzaonyeh\0Arabic_hamzaunderalef\0Arabic_heh\0Arabic_heh_doachashmee\0Arabic_heh_goal\0Arabic_jeem\0Arabic_jeh\0Arabic_kaf\0Arabic_kasra\0Arabic_kasratan\0Arabic_keheh\0Arabic_khah\0Arabic_lam\0Arabic_madda_above\0Arabic_maddaonalef\0Arabic_meem\0Arabic_noon\0Arabic_noon_ghunna\0Arabic_peh\0Arabic_percent\0Arabic_qaf\0Arabic_question_mark\0Arabic_ra\0Arabic_rreh\0Arabic_sad\0Arabic_seen\0Arabic_semicolon\0Arabic_shadda\0Arabic_sheen\0Arabic_sukun\0Arabic_superscript_alef\0Arabic_switch\0Arabic_tah\0Arabic_tatweel\0Arabic_tcheh\0Arabic_teh\0Arabic_tehmarbuta\0Arabic_thal\0Arabic_theh\0Arabic_tteh\0Arabic_veh\0Arabic_waw\0Arabic_yeh\0Arabic_yeh_baree\0Arabic_zah\0Arabic_zain\0Aring\0aring\0Armenian_accent\0Armenian_amanak\0Armenian_apostrophe\0Armenian_AT\0Armenian_at\0Armenian_AYB\0Armenian_ayb\0Armenian_BEN\0Armenian_ben\0Armenian_but\0Armenian_CHA\0Armenian_cha\0Armenian_DA\0Armenian_da\0Armenian_DZA\0Armenian_dza\0Armenian_E\0Armenian_e\0Armenian_exclam\0Armenian_FE\0Armenian_fe\0Armenian_full_stop\0Armenian_GHAT\0Armenian_ghat\0Armenian_GIM\0Armenian_gim\0Armenian_HI\0Armenian_hi\0Armenian_HO\0Armenian_ho\0Armenian_hyphen\0Armenian_INI\0Armenian_ini\0Armenian_JE\0Armenian_je\0Armenian_KE\0Armenian_ke\0Armenian_KEN\0Armenian_ken\0Armenian_KHE\0Armenian_khe\0Armenian_ligature_ew\0Armenian_LYUN\0Armenian_lyun\0Armenian_MEN\0Armenian_men\0Armenian_NU\0Armenian_nu\0Armenian_O\0Armenian_o\0Armenian_paruyk\0Armenian_PE\0Armenian_pe\0Armenian_PYUR\0Armenian_pyur\0Armenian_question\0Armenian_RA\0Armenian_ra\0Armenian_RE\0Armenian_re\0Armenian_SE\0Armenian_se\0Armenian_separation_mark\0Armenian_SHA\0Armenian_sha\0Armenian_shesht\0Armenian_TCHE\0Armenian_tche\0Armenian_TO\0Armenian_to\0Armenian_TSA\0Armenian_tsa\0Armenian_TSO\0Armenian_tso\0Armenian_TYUN\0Armenian_tyun\0Armenian_verjaket\0Armenian_VEV\0Armenian_vev\0Armenian_VO\0Armenian_vo\0Armenian_VYUN\0Armenian_vyun\0Armenian_YECH\0Armenian_yech\0Armenian_yentamna\0Armenian_ZA\0Armenian_za\0Armenian_ZHE\0Armenian_zhe\0asciicircum\0asciitilde\0asterisk\0at\0Atilde\0atilde\0AudibleBell_Enable\0B\0b\0Babovedot\0babovedot\0backslash\0BackSpace\0BackTab\0ballotcross\0bar\0because\0Begin\0blank\0block\0botintegral\0botleftparens\0botleftsqbracket\0botleftsummation\0botrightparens\0botrightsqbracket\0botrightsummation\0bott\0botvertsummationconnector\0BounceKeys_Enable\0braceleft\0braceright\0bracketleft\0bracketright\0braille_blank\0braille_dot_1\0braille_dot_10\0braille_dot_2\0braille_dot_3\0braille_dot_4\0braille_dot_5\0braille_dot_6\0braille_dot_7\0braille_dot_8\0braille_dot_9\0braille_dots_1\0braille_dots_12\0braille_dots_123\0braille_dots_1234\0braille_dots_12345\0braille_dots_123456\0braille_dots_1234567\0braille_dots_12345678\0braille_dots_1234568\0braille_dots_123457\0braille_dots_1234578\0braille_dots_123458\0braille_dots_12346\0braille_dots_123467\0braille_dots_1234678\0braille_dots_123468\0braille_dots_12347\0braille_dots_123478\0braille_dots_12348\0braille_dots_1235\0braille_dots_12356\0braille_dots_123567\0braille_dots_1235678\0braille_dots_123568\0braille_dots_12357\0braille_dots_123578\0braille_dots_12358\0braille_dots_1236\0braille_dots_12367\0braille_dots_123678\0braille_dots_12368\0braille_dots_1237\0braille_dots_12378\0braille_dots_1238\0braille_dots_124\0braille_dots_1245\0braille_dots_12456\0braille_dots_124567\0braille_dots_1245678\0braille_dots_124568\0braille_dots_12457\0braille_dots_124578\0braille_dots_12458\0braille_dots_1246\0braille_dots_12467\0braille_dots_124678\0braille_dots_12468\0braille_dots_1247\0braille_dots_12478\0braille_dots_1248\0braille_dots_125\0braille_dots_1256\0braille_dots_12567\0braille_dots_125678\0braille_dots_12568\0braille_dots_1257\0braille_dots_12578\0braille_dots_1258\0braille_dots_126\0braille_dots_1267\0braille_dots_12678\0braille_dots_1268\0braille_dots_127\0braille_dots_1278\0braille_dots_128\0braille_dots_13\0braille_dots_134\0braille_dots_1345\0braille_dots_13456\0braille_dots_134567\0braille_dots_1345678\0braille_dots_134568\0braille_dots_13457\0braille_dots_134578\0braille_dots_13458\0braille_dots_1346\0braille_dots_13467\0braille_dots_134678\0braille_dots_13468\0braille_dots_1347\0braille_dots_13478\0braille_dots_1348\0braille_dots_135\0braille_dots_1356\0braille_dots_13567\0braille_dots_135678\0braille_dots_13568\0braille_dots_1357\0braille_dots_13578\0braille_dots_1358\0braille_dots_136\0braille_dots_1367\0braille_dots_13678\0braille_dots_1368\0braille_dots_137\0braille_dots_1378\0braille_dots_138\0braille_dots_14\0braille_dots_145\0braille_dots_1456\0braille_dots_14567\0braille_dots_145678\0braille_dots_14568\0braille_dots_1457\0braille_dots_14578\0braille_dots_1458\0braille_dots_146\0braille_dots_1467\0braille_dots_14678\0braille_dots_1468\0braille_dots_147\0braille_dots_1478\0braille_dots_148\0braille_dots_15\0braille_dots_156\0braille_dots_1567\0braille_dots_15678\0braille_dots_1568\0braille_dots_157\0braille_dots_1578\0braille_dots_158\0braille_dots_16\0braille_dots_167\0braille_dots_1678\0braille_dots_168\0braille_dots_17\0braille_dots_178\0braille_dots_18\0braille_dots_2\0braille_dots_23\0braille_dots_234\0braille_dots_2345\0braille_dots_23456\0braille_dots_234567\0braille_dots_2345678\0braille_dots_234568\0braille_dots_23457\0braille_dots_234578\0braille_dots_23458\0braille_dots_2346\0braille_dots_23467\0braille_dots_234678\0braille_dots_23468\0braille_dots_2347\0braille_dots_23478\0braille_dots_2348\0braille_dots_235\0braille_dots_2356\0braille_dots_23567\0braille_dots_235678\0braille_dots_23568\0braille_dots_2357\0braille_dots_23578\0braille_dots_2358\0braille_dots_236\0braille_dots_2367\0braille_dots_23678\0braille_dots_2368\0braille_dots_237\0braille_dots_2378\0braille_dots_238\0braille_dots_24\0braille_dots_245\0braille_dots_2456\0braille_dots_24567\0braille_dots_245678\0braille_dots_24568\0braille_dots_2457\0braille_dots_24578\0braille_dots_2458\0braille_dots_246\0braille_dots_2467\0braille_dots_24678\0braille_dots_2468\0braille_dots_247\0braille_dots_2478\0braille_dots_248\0braille_dots_25\0braille_dots_256\0braille_dots_2567\0braille_dots_25678\0braille_dots_2568\0braille_dots_257\0braille_dots_2578\0braille_dots_258\0braille_dots_26\0braille_dots_267\0braille_dots_2678\0braille_dots_268\0braille_dots_27\0braille_dots_278\0braille_dots_28\0braille_dots_3\0braille_dots_34\0braille_dots_345\0braille_dots_3456\0braille_dots_34567\0braille_dots_345678\0braille_dots_34568\0braille_dots_3457\0braille_dots_34578\0braille_dots_3458\0braille_dots_346\0braille_dots_3467\0braille_dots_34678\0braille_dots_3468\0braille_dots_347\0braille_dots_3478\0braille_dots_348\0braille_dots_35\0braille_dots_356\0braille_dots_3567\0braille_dots_35678\0braille_dots_3568\0braille_dots_357\0braille_dots_3578\0braille_dots_358\0braille_dots_36\0braille_dots_367\0braille_dots_3678\0braille_dots_368\0braille_dots_37\0braille_dots_378\0braille_dots_38\0braille_dots_4\0braille_dots_45\0braille_dots_456\0braille_dots_4567\0braille_dots_45678\0braille_dots_4568\0braille_dots_457\0braille_dots_4578\0braille_dots_458\0braille_dots_46\0braille_dots_467\0braille_dots_4678\0braille_dots_468\0braille_dots_47\0braille_dots_478\0braille_dots_48\0braille_dots_5\0braille_dots_56\0braille_dots_567\0braille_dots_5678\0braille_dots_568\0braille_dots_57\0braille_dots_578\0braille_dots_58\0braille_dots_6\0braille_dots_67\0braille_dots_678\0braille_dots_68\0braille_dots_7\0braille_dots_78\0braille_dots_8\0Break\0breve\0brokenbar\0Byelorussian_SHORTU\0Byelorussian_shortu\0C\0c\0C_H\0C_h\0c_h\0Cabovedot\0cabovedot\0Cacute\0cacute\0Cancel\0Caps_Lock\0careof\0caret\0caron\0Ccaron\0ccaron\0Ccedilla\0ccedilla\0Ccircumflex\0ccircumflex\0cedilla\0cent\0CH\0Ch\0ch\0checkerboard\0checkmark\0circle\0Clear\0ClearLine\0club\0Codeinput\0colon\0ColonSign\0combining_acute\0combining_belowdot\0combining_grave\0combining_hook\0combining_tilde\0comma\0containsas\0Control_L\0Control_R\0copyright\0cr\0crossinglines\0CruzeiroSign\0cuberoot\0currency\0cursor\0Cyrillic_A\0Cyrillic_a\0Cyrillic_BE\0Cyrillic_be\0Cyrillic_CHE\0Cyrillic_che\0Cyrillic_CHE_descender\0Cyrillic_che_descender\0Cyrillic_CHE_vertstroke\0Cyrillic_che_vertstroke\0Cyrillic_DE\0Cyrillic_de\0Cyrillic_DZHE\0Cyrillic_dzhe\0Cyrillic_E\0Cyrillic_e\0Cyrillic_EF\0Cyrillic_ef\0Cyrillic_EL\0Cyrillic_el\0Cyrillic_EM\0Cyrillic_em\0Cyrillic_EN\0Cyrillic_en\0Cyrillic_EN_descender\0Cyrillic_en_descender\0Cyrillic_ER\0Cyrillic_er\0Cyrillic_ES\0Cyrillic_es\0Cyrillic_GHE\0Cyrillic_ghe\0Cyrillic_GHE_bar\0Cyrillic_ghe_bar\0Cyrillic_HA\0Cyrillic_ha\0Cyrillic_HA_descender\0Cyrillic_ha_descender\0Cyrillic_HARDSIGN\0Cyrillic_hardsign\0Cyrillic_I\0Cyrillic_i\0Cyrillic_I_macron\0Cyrillic_i_macron\0Cyrillic_IE\0Cyrillic_ie\0Cyrillic_IO\0Cyrillic_io\0Cyrillic_JE\0Cyrillic_je\0Cyrillic_KA\0Cyrillic_ka\0Cyrillic_KA_descender\0Cyrillic_ka_descender\0Cyrillic_KA_vertstroke\0Cyrillic_ka_vertstroke\0Cyrillic_LJE\0Cyrillic_lje\0Cyrillic_NJE\0Cyrillic_nje\0Cyrillic_O\0Cyrillic_o\0Cyrillic_O_bar\0Cyrillic_o_bar\0Cyrillic_PE\0Cyrillic_pe\0Cyrillic_SCHWA\0Cyrillic_schwa\0Cyrillic_SHA\0Cyrillic_sha\0Cyrillic_SHCHA\0Cyrillic_shcha\0Cyrillic_SHHA\0Cyrillic_shha\0Cyrillic_SHORTI\0Cyrillic_shorti\0Cyrillic_SOFTSIGN\0Cyrillic_softsign\0Cyrillic_TE\0Cyrillic_te\0Cyrillic_TSE\0Cyrillic_tse\0Cyrillic_U\0Cyrillic_u\0Cyrillic_U_macron\0Cyrillic_u_macron\0Cyrillic_U_straight\0Cyrillic_u_straight\0Cyrillic_U_straight_bar\0Cyrillic_u_straight_bar\0Cyrillic_VE\0Cyrillic_ve\0Cyrillic_YA\0Cyrillic_ya\0Cyrillic_YERU\0Cyrillic_yeru\0Cyrillic_YU\0Cyrillic_yu\0Cyrillic_ZE\0Cyrillic_ze\0Cyrillic_ZHE\0Cyrillic_zhe\0Cyrillic_ZHE_descender\0Cyrillic_zhe_descender\0D\0d\0Dabovedot\0dabovedot\0Dacute_accent\0dagger\0Dcaron\0dcaron\0Dcedilla_accent\0Dcircumflex_accent\0Ddiaeresis\0dead_A\0dead_a\0dead_abovecomma\0dead_abovedot\0dead_abovereversedcomma\0dead_abovering\0dead_aboveverticalline\0dead_acute\0dead_apostrophe\0dead_belowbreve\0dead_belowcircumflex\0dead_belowcomma\0dead_belowdiaeresis\0dead_belowdot\0dead_belowmacron\0dead_belowring\0dead_belowtilde\0dead_belowverticalline\0dead_breve\0dead_capital_schwa\0dead_caron\0dead_cedilla\0dead_circumflex\0dead_currency\0dead_dasia\0dead_diaeresis\0dead_doubleacute\0dead_doublegrave\0dead_E\0dead_e\0dead_grave\0dead_greek\0dead_hamza\0dead_hook\0dead_horn\0dead_I\0dead_i\0dead_invertedbreve\0dead_iota\0dead_longsolidusoverlay\0dead_lowline\0dead_macron\0dead_O\0dead_o\0dead_ogonek\0dead_perispomeni\0dead_psili\0dead_SCHWA\0dead_schwa\0dead_semivoiced_sound\0dead_small_schwa\0dead_stroke\0dead_tilde\0dead_U\0dead_u\0dead_voiced_sound\0decimalpoint\0degree\0Delete\0DeleteChar\0DeleteLine\0Dgrave_accent\0diaeresis\0diamond\0digitspace\0dintegral\0division\0dollar\0DongSign\0doubbaselinedot\0doubleacute\0doubledagger\0doublelowquotemark\0Down\0downarrow\0downcaret\0downshoe\0downstile\0downtack\0DRemove\0Dring_accent\0Dstroke\0dstroke\0Dtilde\0E\0e\0Eabovedot\0eabovedot\0Eacute\0eacute\0Ebelowdot\0ebelowdot\0Ecaron\0ecaron\0Ecircumflex\0ecircumflex\0Ecircumflexacute\0ecircumflexacute\0Ecircumflexbelowdot\0ecircumflexbelowdot\0Ecircumflexgrave\0ecircumflexgrave\0Ecircumflexhook\0ecircumflexhook\0Ecircumflextilde\0ecircumflextilde\0EcuSign\0Ediaeresis\0ediaeresis\0Egrave\0egrave\0Ehook\0ehook\0eightsubscript\0eightsuperior\0Eisu_Shift\0Eisu_toggle\0elementof\0ellipsis\0em3space\0em4space\0Emacron\0emacron\0emdash\0emfilledcircle\0emfilledrect\0emopencircle\0emopenrectangle\0emptyset\0emspace\0End\0endash\0enfilledcircbullet\0enfilledsqbullet\0ENG\0eng\0enopencircbullet\0enopensquarebullet\0enspace\0Eogonek\0eogonek\0equal\0Escape\0ETH\0Eth\0eth\0Etilde\0etilde\0EuroSign\0exclam\0exclamdown\0Execute\0Ext16bit_L\0Ext16bit_R\0EZH\0ezh\0F\0f\0F1\0F10\0F11\0F12\0F13\0F14\0F15\0F16\0F17\0F18\0F19\0F2\0F20\0F21\0F22\0F23\0F24\0F25\0F26\0F27\0F28\0F29\0F3\0F30\0F31\0F32\0F33\0F34\0F35\0F4\0F5\0F6\0F7\0F8\0F9\0Fabovedot\0fabovedot\0Farsi_0\0Farsi_1\0Farsi_2\0Farsi_3\0Farsi_4\0Farsi_5\0Farsi_6\0Farsi_7\0Farsi_8\0Farsi_9\0Farsi_yeh\0femalesymbol\0ff\0FFrancSign\0figdash\0filledlefttribullet\0filledrectbullet\0filledrighttribullet\0filledtribulletdown\0filledtribulletup\0Find\0First_Virtual_Screen\0fiveeighths\0fivesixths\0fivesubscript\0fivesuperior\0fourfifths\0foursubscript\0foursuperior\0fourthroot\0function\0G\0g\0Gabovedot\0gabovedot\0Gbreve\0gbreve\0Gcaron\0gcaron\0Gcedilla\0gcedilla\0Gcircumflex\0gcircumflex\0Georgian_an\0Georgian_ban\0Georgian_can\0Georgian_char\0Georgian_chin\0Georgian_cil\0Georgian_don\0Georgian_en\0Georgian_fi\0Georgian_gan\0Georgian_ghan\0Georgian_hae\0Georgian_har\0Georgian_he\0Georgian_hie\0Georgian_hoe\0Georgian_in\0Georgian_jhan\0Georgian_jil\0Georgian_kan\0Georgian_khar\0Georgian_las\0Georgian_man\0Georgian_nar\0Georgian_on\0Georgian_par\0Georgian_phar\0Georgian_qar\0Georgian_rae\0Georgian_san\0Georgian_shin\0Georgian_tan\0Georgian_tar\0Georgian_un\0Georgian_vin\0Georgian_we\0Georgian_xan\0Georgian_zen\0Georgian_zhar\0grave\0greater\0greaterthanequal\0Greek_accentdieresis\0Greek_ALPHA\0Greek_alpha\0Greek_ALPHAaccent\0Greek_alphaaccent\0Greek_BETA\0Greek_beta\0Greek_CHI\0Greek_chi\0Greek_DELTA\0Greek_delta\0Greek_EPSILON\0Greek_epsilon\0Greek_EPSILONaccent\0Greek_epsilonaccent\0Greek_ETA\0Greek_eta\0Greek_ETAaccent\0Greek_etaaccent\0Greek_finalsmallsigma\0Greek_GAMMA\0Greek_gamma\0Greek_horizbar\0Greek_IOTA\0Greek_iota\0Greek_IOTAaccent\0Greek_iotaaccent\0Greek_iotaaccentdieresis\0Greek_IOTAdiaeresis\0Greek_IOTAdieresis\0Greek_iotadieresis\0Greek_KAPPA\0Greek_kappa\0Greek_LAMBDA\0Greek_lambda\0Greek_LAMDA\0Greek_lamda\0Greek_MU\0Greek_mu\0Greek_NU\0Greek_nu\0Greek_OMEGA\0Greek_omega\0Greek_OMEGAaccent\0Greek_omegaaccent\0Greek_OMICRON\0Greek_omicron\0Greek_OMICRONaccent\0Greek_omicronaccent\0Greek_PHI\0Greek_phi\0Greek_PI\0Greek_pi\0Greek_PSI\0Greek_psi\0Greek_RHO\0Greek_rho\0Greek_SIGMA\0Greek_sigma\0Greek_switch\0Greek_TAU\0Greek_tau\0Greek_THETA\0Greek_theta\0Greek_UPSILON\0Greek_upsilon\0Greek_UPSILONaccent\0Greek_upsilonaccent\0Greek_upsilonaccentdieresis\0Greek_UPSILONdieresis\0Greek_upsilondieresis\0Greek_XI\0Greek_xi\0Greek_ZETA\0Greek_zeta\0guilder\0guillemetleft\0guillemetright\0guillemotleft\0guillemotright\0H\0h\0hairspace\0Hangul\0Hangul_A\0Hangul_AE\0Hangul_AraeA\0Hangul_AraeAE\0Hangul_Banja\0Hangul_Cieuc\0Hangul_Codeinput\0Hangul_Dikeud\0Hangul_E\0Hangul_End\0Hangul_EO\0Hangul_EU\0Hangul_Hanja\0Hangul_Hieuh\0Hangul_I\0Hangul_Ieung\0Hangul_J_Cieuc\0Hangul_J_Dikeud\0Hangul_J_Hieuh\0Hangul_J_Ieung\0Hangul_J_Jieuj\0Hangul_J_Khieuq\0Hangul_J_Kiyeog\0Hangul_J_KiyeogSios\0Hangul_J_KkogjiDalrinIeung\0Hangul_J_Mieum\0Hangul_J_Nieun\0Hangul_J_NieunHieuh\0Hangul_J_NieunJieuj\0Hangul_J_PanSios\0Hangul_J_Phieuf\0Hangul_J_Pieub\0Hangul_J_PieubSios\0Hangul_J_Rieul\0Hangul_J_RieulHieuh\0Hangul_J_RieulKiyeog\0Hangul_J_RieulMieum\0Hangul_J_RieulPhieuf\0Hangul_J_RieulPieub\0Hangul_J_RieulSios\0Hangul_J_RieulTieut\0Hangul_J_Sios\0Hangul_J_SsangKiyeog\0Hangul_J_SsangSios\0Hangul_J_Tieut\0Hangul_J_YeorinHieuh\0Hangul_Jamo\0Hangul_Jeonja\0Hangul_Jieuj\0Hangul_Khieuq\0Hangul_Kiyeog\0Hangul_KiyeogSios\0Hangul_KkogjiDalrinIeung\0Hangul_Mieum\0Hangul_MultipleCandidate\0Hangul_Nieun\0Hangul_NieunHieuh\0Hangul_NieunJieuj\0Hangul_O\0Hangul_OE\0Hangul_PanSios\0Hangul_Phieuf\0Hangul_Pieub\0Hangul_PieubSios\0Hangul_PostHanja\0Hangul_PreHanja\0Hangul_PreviousCandidate\0Hangul_Rieul\0Hangul_RieulHieuh\0Hangul_RieulKiyeog\0Hangul_RieulMieum\0Hangul_RieulPhieuf\0Hangul_RieulPieub\0Hangul_RieulSios\0Hangul_RieulTieut\0Hangul_RieulYeorinHieuh\0Hangul_Romaja\0Hangul_SingleCandidate\0Hangul_Sios\0Hangul_Special\0Hangul_SsangDikeud\0Hangul_SsangJieuj\0Hangul_SsangKiyeog\0Hangul_SsangPieub\0Hangul_SsangSios\0Hangul_Start\0Hangul_SunkyeongeumMieum\0Hangul_SunkyeongeumPhieuf\0Hangul_SunkyeongeumPieub\0Hangul_switch\0Hangul_Tieut\0Hangul_U\0Hangul_WA\0Hangul_WAE\0Hangul_WE\0Hangul_WEO\0Hangul_WI\0Hangul_YA\0Hangul_YAE\0Hangul_YE\0Hangul_YEO\0Hangul_YeorinHieuh\0Hangul_YI\0Hangul_YO\0Hangul_YU\0Hankaku\0Hcircumflex\0hcircumflex\0heart\0hebrew_aleph\0hebrew_ayin\0hebrew_bet\0hebrew_beth\0hebrew_chet\0hebrew_dalet\0hebrew_daleth\0hebrew_doublelowline\0hebrew_finalkaph\0hebrew_finalmem\0hebrew_finalnun\0hebrew_finalpe\0hebrew_finalzade\0hebrew_finalzadi\0hebrew_gimel\0hebrew_gimmel\0hebrew_he\0hebrew_het\0hebrew_kaph\0hebrew_kuf\0hebrew_lamed\0hebrew_mem\0hebrew_nun\0hebrew_pe\0hebrew_qoph\0hebrew_resh\0hebrew_samech\0hebrew_samekh\0hebrew_shin\0Hebrew_switch\0hebrew_taf\0hebrew_taw\0hebrew_tet\0hebrew_teth\0hebrew_waw\0hebrew_yod\0hebrew_zade\0hebrew_zadi\0hebrew_zain\0hebrew_zayin\0Help\0Henkan\0Henkan_Mode\0hexagram\0Hiragana\0Hiragana_Katakana\0Home\0horizconnector\0horizlinescan1\0horizlinescan3\0horizlinescan5\0horizlinescan7\0horizlinescan9\0hpBackTab\0hpblock\0hpClearLine\0hpDeleteChar\0hpDeleteLine\0hpguilder\0hpInsertChar\0hpInsertLine\0hpIO\0hpKP_BackTab\0hplira\0hplongminus\0hpModelock1\0hpModelock2\0hpmute_acute\0hpmute_asciicircum\0hpmute_asciitilde\0hpmute_diaeresis\0hpmute_grave\0hpReset\0hpSystem\0hpUser\0hpYdiaeresis\0Hstroke\0hstroke\0ht\0Hyper_L\0Hyper_R\0hyphen\0I\0i\0Iabovedot\0Iacute\0iacute\0Ibelowdot\0ibelowdot\0Ibreve\0ibreve\0Icircumflex\0icircumflex\0identical\0Idiaeresis\0idiaeresis\0idotless\0ifonlyif\0Igrave\0igrave\0Ihook\0ihook\0Imacron\0imacron\0implies\0includedin\0includes\0infinity\0Insert\0InsertChar\0InsertLine\0integral\0intersection\0IO\0Iogonek\0iogonek\0ISO_Center_Object\0ISO_Continuous_Underline\0ISO_Discontinuous_Underline\0ISO_Emphasize\0ISO_Enter\0ISO_Fast_Cursor_Down\0ISO_Fast_Cursor_Left\0ISO_Fast_Cursor_Right\0ISO_Fast_Cursor_Up\0ISO_First_Group\0ISO_First_Group_Lock\0ISO_Group_Latch\0ISO_Group_Lock\0ISO_Group_Shift\0ISO_Last_Group\0ISO_Last_Group_Lock\0ISO_Left_Tab\0ISO_Level2_Latch\0ISO_Level3_Latch\0ISO_Level3_Lock\0ISO_Level3_Shift\0ISO_Level5_Latch\0ISO_Level5_Lock\0ISO_Level5_Shift\0ISO_Lock\0ISO_Move_Line_Down\0ISO_Move_Line_Up\0ISO_Next_Group\0ISO_Next_Group_Lock\0ISO_Partial_Line_Down\0ISO_Partial_Line_Up\0ISO_Partial_Space_Left\0ISO_Partial_Space_Right\0ISO_Prev_Group\0ISO_Prev_Group_Lock\0ISO_Release_Both_Margins\0ISO_Release_Margin_Left\0ISO_Release_Margin_Right\0ISO_Set_Margin_Left\0ISO_Set_Margin_Right\0Itilde\0itilde\0J\0j\0Jcircumflex\0jcircumflex\0jot\0K\0k\0kana_A\0kana_a\0kana_CHI\0kana_closingbracket\0kana_comma\0kana_conjunctive\0kana_E\0kana_e\0kana_FU\0kana_fullstop\0kana_HA\0kana_HE\0kana_HI\0kana_HO\0kana_HU\0kana_I\0kana_i\0kana_KA\0kana_KE\0kana_KI\0kana_KO\0kana_KU\0Kana_Lock\0kana_MA\0kana_ME\0kana_MI\0kana_middledot\0kana_MO\0kana_MU\0kana_N\0kana_NA\0kana_NE\0kana_NI\0kana_NO\0kana_NU\0kana_O\0kana_o\0kana_openingbracket\0kana_RA\0kana_RE\0kana_RI\0kana_RO\0kana_RU\0kana_SA\0kana_SE\0kana_SHI\0Kana_Shift\0kana_SO\0kana_SU\0kana_switch\0kana_TA\0kana_TE\0kana_TI\0kana_TO\0kana_TSU\0kana_tsu\0kana_TU\0kana_tu\0kana_U\0kana_u\0kana_WA\0kana_WO\0kana_YA\0kana_ya\0kana_YO\0kana_yo\0kana_YU\0kana_yu\0Kanji\0Kanji_Bangou\0kappa\0Katakana\0Kcedilla\0kcedilla\0Korean_Won\0KP_0\0KP_1\0KP_2\0KP_3\0KP_4\0KP_5\0KP_6\0KP_7\0KP_8\0KP_9\0KP_Add\0KP_BackTab\0KP_Begin\0KP_Decimal\0KP_Delete\0KP_Divide\0KP_Down\0KP_End\0KP_Enter\0KP_Equal\0KP_F1\0KP_F2\0KP_F3\0KP_F4\0KP_Home\0KP_Insert\0KP_Left\0KP_Multiply\0KP_Next\0KP_Page_Down\0KP_Page_Up\0KP_Prior\0KP_Right\0KP_Separator\0KP_Space\0KP_Subtract\0KP_Tab\0KP_Up\0kra\0L\0l\0L1\0L10\0L2\0L3\0L4\0L5\0L6\0L7\0L8\0L9\0Lacute\0lacute\0Last_Virtual_Screen\0latincross\0Lbelowdot\0lbelowdot\0Lcaron\0lcaron\0Lcedilla\0lcedilla\0Left\0leftanglebracket\0leftarrow\0leftcaret\0leftdoublequotemark\0leftmiddlecurlybrace\0leftopentriangle\0leftpointer\0leftradical\0leftshoe\0leftsingleanglequotemark\0leftsinglequotemark\0leftt\0lefttack\0less\0lessthanequal\0lf\0Linefeed\0lira\0LiraSign\0logicaland\0logicalor\0longminus\0lowleftcorner\0lowrightcorner\0Lstroke\0lstroke\0M\0m\0Mabovedot\0mabovedot\0Macedonia_DSE\0Macedonia_dse\0Macedonia_GJE\0Macedonia_gje\0Macedonia_KJE\0Macedonia_kje\0macron\0Mae_Koho\0malesymbol\0maltesecross\0marker\0masculine\0Massyo\0Menu\0Meta_L\0Meta_R\0MillSign\0minus\0minutes\0Mode_switch\0MouseKeys_Accel_Enable\0MouseKeys_Enable\0mu\0Muhenkan\0Multi_key\0MultipleCandidate\0multiply\0musicalflat\0musicalsharp\0mute_acute\0mute_asciicircum\0mute_asciitilde\0mute_diaeresis\0mute_grave\0N\0n\0nabla\0Nacute\0nacute\0NairaSign\0Ncaron\0ncaron\0Ncedilla\0ncedilla\0NewSheqelSign\0Next\0Next_Virtual_Screen\0ninesubscript\0ninesuperior\0nl\0nobreakspace\0NoSymbol\0notapproxeq\0notelementof\0notequal\0notidentical\0notsign\0Ntilde\0ntilde\0Num_Lock\0numbersign\0numerosign\0O\0o\0Oacute\0oacute\0Obarred\0obarred\0Obelowdot\0obelowdot\0Ocaron\0ocaron\0Ocircumflex\0ocircumflex\0Ocircumflexacute\0ocircumflexacute\0Ocircumflexbelowdot\0ocircumflexbelowdot\0Ocircumflexgrave\0ocircumflexgrave\0Ocircumflexhook\0ocircumflexhook\0Ocircumflextilde\0ocircumflextilde\0Odiaeresis\0odiaeresis\0Odoubleacute\0odoubleacute\0OE\0oe\0ogonek\0Ograve\0ograve\0Ohook\0ohook\0Ohorn\0ohorn\0Ohornacute\0ohornacute\0Ohornbelowdot\0ohornbelowdot\0Ohorngrave\0ohorngrave\0Ohornhook\0ohornhook\0Ohorntilde\0ohorntilde\0Omacron\0omacron\0oneeighth\0onefifth\0onehalf\0onequarter\0onesixth\0onesubscript\0onesuperior\0onethird\0Ooblique\0ooblique\0openrectbullet\0openstar\0opentribulletdown\0opentribulletup\0ordfeminine\0ordmasculine\0osfActivate\0osfAddMode\0osfBackSpace\0osfBackTab\0osfBeginData\0osfBeginLine\0osfCancel\0osfClear\0osfCopy\0osfCut\0osfDelete\0osfDeselectAll\0osfDown\0osfEndData\0osfEndLine\0osfEscape\0osfExtend\0osfHelp\0osfInsert\0osfLeft\0osfMenu\0osfMenuBar\0osfNextField\0osfNextMenu\0osfPageDown\0osfPageLeft\0osfPageRight\0osfPageUp\0osfPaste\0osfPrevField\0osfPrevMenu\0osfPrimaryPaste\0osfQuickPaste\0osfReselect\0osfRestore\0osfRight\0osfSelect\0osfSelectAll\0osfUndo\0osfUp\0Oslash\0oslash\0Otilde\0otilde\0overbar\0Overlay1_Enable\0Overlay2_Enable\0overline\0P\0p\0Pabovedot\0pabovedot\0Page_Down\0Page_Up\0paragraph\0parenleft\0parenright\0partdifferential\0partialderivative\0Pause\0percent\0period\0periodcentered\0permille\0PesetaSign\0phonographcopyright\0plus\0plusminus\0Pointer_Accelerate\0Pointer_Button1\0Pointer_Button2\0Pointer_Button3\0Pointer_Button4\0Pointer_Button5\0Pointer_Button_Dflt\0Pointer_DblClick1\0Pointer_DblClick2\0Pointer_DblClick3\0Pointer_DblClick4\0Pointer_DblClick5\0Pointer_DblClick_Dflt\0Pointer_DfltBtnNext\0Pointer_DfltBtnPrev\0Pointer_Down\0Pointer_DownLeft\0Pointer_DownRight\0Pointer_Drag1\0Pointer_Drag2\0Pointer_Drag3\0Pointer_Drag4\0Pointer_Drag5\0Pointer_Drag_Dflt\0Pointer_EnableKeys\0Pointer_Left\0Pointer_Right\0Pointer_Up\0Pointer_UpLeft\0Pointer_UpRight\0prescription\0Prev_Virtual_Screen\0PreviousCandidate\0Print\0Prior\0prolongedsound\0punctspace\0Q\0q\0quad\0question\0questiondown\0quotedbl\0quoteleft\0quoteright\0R\0r\0R1\0R10\0R11\0R12\0R13\0R14\0R15\0R2\0R3\0R4\0R5\0R6\0R7\0R8\0R9\0Racute\0racute\0radical\0Rcaron\0rcaron\0Rcedilla\0rcedilla\0Redo\0registered\0RepeatKeys_Enable\0Reset\0Return\0Right\0rightanglebracket\0rightarrow\0rightcaret\0rightdoublequotemark\0rightmiddlecurlybrace\0rightmiddlesummation\0rightopentriangle\0rightpointer\0rightshoe\0rightsingleanglequotemark\0rightsinglequotemark\0rightt\0righttack\0Romaji\0RupeeSign\0S\0s\0Sabovedot\0sabovedot\0Sacute\0sacute\0Scaron\0scaron\0Scedilla\0scedilla\0SCHWA\0schwa\0Scircumflex\0scircumflex\0script_switch\0Scroll_Lock\0seconds\0section\0Select\0semicolon\0semivoicedsound\0Serbian_DJE\0Serbian_dje\0Serbian_DZE\0Serbian_dze\0Serbian_JE\0Serbian_je\0Serbian_LJE\0Serbian_lje\0Serbian_NJE\0Serbian_nje\0Serbian_TSHE\0Serbian_tshe\0seveneighths\0sevensubscript\0sevensuperior\0Shift_L\0Shift_Lock\0Shift_R\0signaturemark\0signifblank\0similarequal\0SingleCandidate\0singlelowquotemark\0Sinh_a\0Sinh_aa\0Sinh_aa2\0Sinh_ae\0Sinh_ae2\0Sinh_aee\0Sinh_aee2\0Sinh_ai\0Sinh_ai2\0Sinh_al\0Sinh_au\0Sinh_au2\0Sinh_ba\0Sinh_bha\0Sinh_ca\0Sinh_cha\0Sinh_dda\0Sinh_ddha\0Sinh_dha\0Sinh_dhha\0Sinh_e\0Sinh_e2\0Sinh_ee\0Sinh_ee2\0Sinh_fa\0Sinh_ga\0Sinh_gha\0Sinh_h2\0Sinh_ha\0Sinh_i\0Sinh_i2\0Sinh_ii\0Sinh_ii2\0Sinh_ja\0Sinh_jha\0Sinh_jnya\0Sinh_ka\0Sinh_kha\0Sinh_kunddaliya\0Sinh_la\0Sinh_lla\0Sinh_lu\0Sinh_lu2\0Sinh_luu\0Sinh_luu2\0Sinh_ma\0Sinh_mba\0Sinh_na\0Sinh_ndda\0Sinh_ndha\0Sinh_ng\0Sinh_ng2\0Sinh_nga\0Sinh_nja\0Sinh_nna\0Sinh_nya\0Sinh_o\0Sinh_o2\0Sinh_oo\0Sinh_oo2\0Sinh_pa\0Sinh_pha\0Sinh_ra\0Sinh_ri\0Sinh_rii\0Sinh_ru2\0Sinh_ruu2\0Sinh_sa\0Sinh_sha\0Sinh_ssha\0Sinh_tha\0Sinh_thha\0Sinh_tta\0Sinh_ttha\0Sinh_u\0Sinh_u2\0Sinh_uu\0Sinh_uu2\0Sinh_va\0Sinh_ya\0sixsubscript\0sixsuperior\0slash\0SlowKeys_Enable\0soliddiamond\0space\0squareroot\0SSHARP\0ssharp\0sterling\0StickyKeys_Enable\0stricteq\0SunAgain\0SunAltGraph\0SunAudioLowerVolume\0SunAudioMute\0SunAudioRaiseVolume\0SunCompose\0SunCopy\0SunCut\0SunF36\0SunF37\0SunFA_Acute\0SunFA_Cedilla\0SunFA_Circum\0SunFA_Diaeresis\0SunFA_Grave\0SunFA_Tilde\0SunFind\0SunFront\0SunOpen\0SunPageDown\0SunPageUp\0SunPaste\0SunPowerSwitch\0SunPowerSwitchShift\0SunPrint_Screen\0SunProps\0SunStop\0SunSys_Req\0SunUndo\0SunVideoDegauss\0SunVideoLowerBrightness\0SunVideoRaiseBrightness\0Super_L\0Super_R\0Sys_Req\0System\0T\0t\0Tab\0Tabovedot\0tabovedot\0Tcaron\0tcaron\0Tcedilla\0tcedilla\0telephone\0telephonerecorder\0Terminate_Server\0Thai_baht\0Thai_bobaimai\0Thai_chochan\0Thai_chochang\0Thai_choching\0Thai_chochoe\0Thai_dochada\0Thai_dodek\0Thai_fofa\0Thai_fofan\0Thai_hohip\0Thai_honokhuk\0Thai_khokhai\0Thai_khokhon\0Thai_khokhuat\0Thai_khokhwai\0Thai_khorakhang\0Thai_kokai\0Thai_lakkhangyao\0Thai_lekchet\0Thai_lekha\0Thai_lekhok\0Thai_lekkao\0Thai_leknung\0Thai_lekpaet\0Thai_leksam\0Thai_leksi\0Thai_leksong\0Thai_leksun\0Thai_lochula\0Thai_loling\0Thai_lu\0Thai_maichattawa\0Thai_maiek\0Thai_maihanakat\0Thai_maihanakat_maitho\0Thai_maitaikhu\0Thai_maitho\0Thai_maitri\0Thai_maiyamok\0Thai_moma\0Thai_ngongu\0Thai_nikhahit\0Thai_nonen\0Thai_nonu\0Thai_oang\0Thai_paiyannoi\0Thai_phinthu\0Thai_phophan\0Thai_phophung\0Thai_phosamphao\0Thai_popla\0Thai_rorua\0Thai_ru\0Thai_saraa\0Thai_saraaa\0Thai_saraae\0Thai_saraaimaimalai\0Thai_saraaimaimuan\0Thai_saraam\0Thai_sarae\0Thai_sarai\0Thai_saraii\0Thai_sarao\0Thai_sarau\0Thai_saraue\0Thai_sarauee\0Thai_sarauu\0Thai_sorusi\0Thai_sosala\0Thai_soso\0Thai_sosua\0Thai_thanthakhat\0Thai_thonangmontho\0Thai_thophuthao\0Thai_thothahan\0Thai_thothan\0Thai_thothong\0Thai_thothung\0Thai_topatak\0Thai_totao\0Thai_wowaen\0Thai_yoyak\0Thai_yoying\0therefore\0thinspace\0THORN\0Thorn\0thorn\0threeeighths\0threefifths\0threequarters\0threesubscript\0threesuperior\0tintegral\0topintegral\0topleftparens\0topleftradical\0topleftsqbracket\0topleftsummation\0toprightparens\0toprightsqbracket\0toprightsummation\0topt\0topvertsummationconnector\0Touroku\0trademark\0trademarkincircle\0Tslash\0tslash\0twofifths\0twosubscript\0twosuperior\0twothirds\0U\0u\0Uacute\0uacute\0Ubelowdot\0ubelowdot\0Ubreve\0ubreve\0Ucircumflex\0ucircumflex\0Udiaeresis\0udiaeresis\0Udoubleacute\0udoubleacute\0Ugrave\0ugrave\0Uhook\0uhook\0Uhorn\0uhorn\0Uhornacute\0uhornacute\0Uhornbelowdot\0uhornbelowdot\0Uhorngrave\0uhorngrave\0Uhornhook\0uhornhook\0Uhorntilde\0uhorntilde\0Ukrainian_GHE_WITH_UPTURN\0Ukrainian_ghe_with_upturn\0Ukrainian_I\0Ukrainian_i\0Ukrainian_IE\0Ukrainian_ie\0Ukrainian_YI\0Ukrainian_yi\0Ukranian_I\0Ukranian_i\0Ukranian_JE\0Ukranian_je\0Ukranian_YI\0Ukranian_yi\0Umacron\0umacron\0underbar\0underscore\0Undo\0union\0Uogonek\0uogonek\0Up\0uparrow\0upcaret\0upleftcorner\0uprightcorner\0upshoe\0upstile\0uptack\0Uring\0uring\0User\0Utilde\0utilde\0V\0v\0variation\0vertbar\0vertconnector\0voicedsound\0VoidSymbol\0vt\0W\0w\0Wacute\0wacute\0Wcircumflex\0wcircumflex\0Wdiaeresis\0wdiaeresis\0Wgrave\0wgrave\0WonSign\0X\0x\0Xabovedot\0xabovedot\0XF8610ChannelsDown\0XF8610ChannelsUp\0XF863DMode\0XF86Accessibility\0XF86AddFavorite\0XF86Addressbook\0XF86ALSToggle\0XF86ApplicationLeft\0XF86ApplicationRight\0XF86AppSelect\0XF86AspectRatio\0XF86Assistant\0XF86AttendantOff\0XF86AttendantOn\0XF86AttendantToggle\0XF86Audio\0XF86AudioBassBoost\0XF86AudioChannelMode\0XF86AudioCycleTrack\0XF86AudioDesc\0XF86AudioForward\0XF86AudioLowerVolume\0XF86AudioMedia\0XF86AudioMicMute\0XF86AudioMute\0XF86AudioNext\0XF86AudioPause\0XF86AudioPlay\0XF86AudioPreset\0XF86AudioPrev\0XF86AudioRaiseVolume\0XF86AudioRandomPlay\0XF86AudioRecord\0XF86AudioRepeat\0XF86AudioRewind\0XF86AudioStop\0XF86AutopilotEngageToggle\0XF86Away\0XF86Back\0XF86BackForward\0XF86Battery\0XF86Blue\0XF86Bluetooth\0XF86Book\0XF86Break\0XF86BrightnessAdjust\0XF86BrightnessAuto\0XF86BrightnessMax\0XF86BrightnessMin\0XF86Buttonconfig\0XF86Calculater\0XF86Calculator\0XF86Calendar\0XF86CameraAccessDisable\0XF86CameraAccessEnable\0XF86CameraAccessToggle\0XF86CameraDown\0XF86CameraFocus\0XF86CameraLeft\0XF86CameraRight\0XF86CameraUp\0XF86CameraZoomIn\0XF86CameraZoomOut\0XF86CD\0XF86ChannelDown\0XF86ChannelUp\0XF86Clear\0XF86ClearGrab\0XF86ClearvuSonar\0XF86Close\0XF86Community\0XF86ContextMenu\0XF86ContrastAdjust\0XF86ControlPanel\0XF86Copy\0XF86Cut\0XF86CycleAngle\0XF86Data\0XF86Database\0XF86Dictate\0XF86Display\0XF86DisplayOff\0XF86DisplayToggle\0XF86Documents\0XF86DoNotDisturb\0XF86DOS\0XF86DualRangeRadar\0XF86DVD\0XF86Editor\0XF86Eject\0XF86ElectronicPrivacyScreenOff\0XF86ElectronicPrivacyScreenOn\0XF86EmojiPicker\0XF86Excel\0XF86Exit\0XF86Explorer\0XF86FastReverse\0XF86Favorites\0XF86Finance\0XF86FishingChart\0XF86Fn\0XF86Fn_1\0XF86Fn_2\0XF86Fn_B\0XF86Fn_D\0XF86Fn_E\0XF86Fn_Esc\0XF86Fn_F\0XF86Fn_F1\0XF86Fn_F10\0XF86Fn_F11\0XF86Fn_F12\0XF86Fn_F2\0XF86Fn_F3\0XF86Fn_F4\0XF86Fn_F5\0XF86Fn_F6\0XF86Fn_F7\0XF86Fn_F8\0XF86Fn_F9\0XF86Fn_S\0XF86FnRightShift\0XF86Forward\0XF86FrameBack\0XF86FrameForward\0XF86FullScreen\0XF86Game\0XF86Go\0XF86GoTo\0XF86GraphicsEditor\0XF86Green\0XF86HangupPhone\0XF86Hibernate\0XF86History\0XF86HomePage\0XF86HotLinks\0XF86Images\0XF86Info\0XF86iTouch\0XF86Journal\0XF86KbdBrightnessDown\0XF86KbdBrightnessUp\0XF86KbdInputAssistAccept\0XF86KbdInputAssistCancel\0XF86KbdInputAssistNext\0XF86KbdInputAssistNextgroup\0XF86KbdInputAssistPrev\0XF86KbdInputAssistPrevgroup\0XF86KbdLcdMenu1\0XF86KbdLcdMenu2\0XF86KbdLcdMenu3\0XF86KbdLcdMenu4\0XF86KbdLcdMenu5\0XF86KbdLightOnOff\0XF86Keyboard\0XF86Launch0\0XF86Launch1\0XF86Launch2\0XF86Launch3\0XF86Launch4\0XF86Launch5\0XF86Launch6\0XF86Launch7\0XF86Launch8\0XF86Launch9\0XF86LaunchA\0XF86LaunchB\0XF86LaunchC\0XF86LaunchD\0XF86LaunchE\0XF86LaunchF\0XF86LeftDown\0XF86LeftUp\0XF86LightBulb\0XF86LightsToggle\0XF86LinkPhone\0XF86LogGrabInfo\0XF86LogOff\0XF86LogWindowTree\0XF86Macro1\0XF86Macro10\0XF86Macro11\0XF86Macro12\0XF86Macro13\0XF86Macro14\0XF86Macro15\0XF86Macro16\0XF86Macro17\0XF86Macro18\0XF86Macro19\0XF86Macro2\0XF86Macro20\0XF86Macro21\0XF86Macro22\0XF86Macro23\0XF86Macro24\0XF86Macro25\0XF86Macro26\0XF86Macro27\0XF86Macro28\0XF86Macro29\0XF86Macro3\0XF86Macro30\0XF86Macro4\0XF86Macro5\0XF86Macro6\0XF86Macro7\0XF86Macro8\0XF86Macro9\0XF86MacroPreset1\0XF86MacroPreset2\0XF86MacroPreset3\0XF86MacroPresetCycle\0XF86MacroRecordStart\0XF86MacroRecordStop\0XF86Mail\0XF86MailForward\0XF86Market\0XF86MarkWaypoint\0XF86MediaLanguageMenu\0XF86MediaPlayer\0XF86MediaPlayPause\0XF86MediaPlaySlow\0XF86MediaRepeat\0XF86MediaSelectAuxiliary\0XF86MediaSelectCable\0XF86MediaSelectCD\0XF86MediaSelectDVD\0XF86MediaSelectHome\0XF86MediaSelectPC\0XF86MediaSelectProgramGuide\0XF86MediaSelectRadio\0XF86MediaSelectSatellite\0XF86MediaSelectTape\0XF86MediaSelectTeletext\0XF86MediaSelectTuner\0XF86MediaSelectTV\0XF86MediaSelectVCR\0XF86MediaSelectVCRPlus\0XF86MediaTitleMenu\0XF86MediaTopMenu\0XF86Meeting\0XF86Memo\0XF86MenuKB\0XF86MenuPB\0XF86Messenger\0XF86ModeLock\0XF86MonBrightnessAuto\0XF86MonBrightnessCycle\0XF86MonBrightnessDown\0XF86MonBrightnessUp\0XF86Music\0XF86MyComputer\0XF86MySites\0XF86NavChart\0XF86NavInfo\0XF86New\0XF86News\0XF86Next_VMode\0XF86NextElement\0XF86NextFavorite\0XF86NotificationCenter\0XF86NumberEntryMode\0XF86Numeric0\0XF86Numeric1\0XF86Numeric11\0XF86Numeric12\0XF86Numeric2\0XF86Numeric3\0XF86Numeric4\0XF86Numeric5\0XF86Numeric6\0XF86Numeric7\0XF86Numeric8\0XF86Numeric9\0XF86NumericA\0XF86NumericB\0XF86NumericC\0XF86NumericD\0XF86NumericPound\0XF86NumericStar\0XF86OfficeHome\0XF86OK\0XF86OnScreenKeyboard\0XF86Open\0XF86OpenURL\0XF86Option\0XF86Paste\0XF86PauseRecord\0XF86PerformanceMode\0XF86Phone\0XF86PickupPhone\0XF86Pictures\0XF86PowerDown\0XF86PowerOff\0XF86Presentation\0XF86Prev_VMode\0XF86PreviousElement\0XF86PrivacyScreenToggle\0XF86Q\0XF86RadarOverlay\0XF86Red\0XF86Refresh\0XF86RefreshRateToggle\0XF86Reload\0XF86Reply\0XF86RFKill\0XF86RightDown\0XF86RightUp\0XF86RockerDown\0XF86RockerEnter\0XF86RockerUp\0XF86RootMenu\0XF86RotateWindows\0XF86RotationKB\0XF86RotationLockToggle\0XF86RotationPB\0XF86Save\0XF86ScreenSaver\0XF86Screensaver\0XF86ScrollClick\0XF86ScrollDown\0XF86ScrollUp\0XF86Search\0XF86Select\0XF86SelectiveScreenshot\0XF86Send\0XF86Shop\0XF86SidevuSonar\0XF86SingleRangeRadar\0XF86Sleep\0XF86SlowReverse\0XF86Sos\0XF86Spell\0XF86SpellCheck\0XF86SplitScreen\0XF86Sport\0XF86Standby\0XF86Start\0XF86Stop\0XF86StopRecord\0XF86Subtitle\0XF86Support\0XF86Suspend\0XF86Switch_VT_1\0XF86Switch_VT_10\0XF86Switch_VT_11\0XF86Switch_VT_12\0XF86Switch_VT_2\0XF86Switch_VT_3\0XF86Switch_VT_4\0XF86Switch_VT_5\0XF86Switch_VT_6\0XF86Switch_VT_7\0XF86Switch_VT_8\0XF86Switch_VT_9\0XF86Taskmanager\0XF86TaskPane\0XF86Terminal\0XF86Time\0XF86ToDoList\0XF86Tools\0XF86TopMenu\0XF86TouchpadOff\0XF86TouchpadOn\0XF86TouchpadToggle\0XF86TraditionalSonar\0XF86Travel\0XF86Ungrab\0XF86Unmute\0XF86User1KB\0XF86User2KB\0XF86UserPB\0XF86UWB\0XF86VendorHome\0XF86VendorLogo\0XF86Video\0XF86VideoPhone\0XF86View\0XF86VOD\0XF86VoiceCommand\0XF86Voicemail\0XF86WakeUp\0XF86WebCam\0XF86WheelButton\0XF86WLAN\0XF86Word\0XF86WPSButton\0XF86WWAN\0XF86WWW\0XF86Xfer\0XF86Yellow\0XF86ZoomIn\0XF86ZoomOut\0XF86ZoomReset\0Y\0y\0Yacute\0yacute\0Ybelowdot\0ybelowdot\0Ycircumflex\0ycircumflex\0Ydiaeresis\0ydiaeresis\0yen\0Ygrave\0ygrave\0Yhook\0yhook\0Ytilde\0ytilde\0Z\0z\0Zabovedot\0zabovedot\0Zacute\0zacute\0Zcaron\0zcaron\0Zen_Koho\0Zenkaku\0Zenkaku_Hankaku\0zerosubscript\0zerosuperior\0Zstroke\0zstroke\0\0";
 #[rustfmt::skip]
 static NAME_INDEX: &[(u32, u32)] = &[(0,0x30),(2,0x31),(4,0x32),(6,0x33),(8,0xfd10),(23,0xfd0e),(33,0xfd05),(46,0xfd19),(64,0xfd15),(74,0xfd0f),(91,0xfd1c),(109,0xfd1a),(125,0xfd01),(140,0xfd1e),(151,0xfd06),(165,0xfd07),(181,0xfd1b),(195,0xfd02),(210,0xfd13),(221,0xfd12),(231,0xfd11),(245,0xfd04),(256,0xfd0a),(265,0xfd0b),(274,0xfd0c),(283,0xfd16),(293,0xfd1d),(310,0xfd09),(320,0xfd18),(332,0xfd08),(343,0xfd03),(355,0xfd14),(365,0xfd17),(376,0xfd0d),(386,0x34),(388,0x35),(390,0x36),(392,0x37),(394,0x38),(396,0x39),(398,0x41),(848,0xc6),(402,0xc1),(416,0x1001ea0),(445,0x1c3),(459,0x1001eae),(483,0x1001eb6),(513,0x1001eb0),(537,0x1001eb2),(559,0x1001eb4),(583,0xfe70),(598,0xfe71),(622,0xc2),(646,0x1001ea4),(680,0x1001eac),(720,0x1001ea6),(754,0x1001ea8),(786,0x1001eaa),(826,0xc4),(854,0xc0),(868,0x1001ea2),(880,0xffe9),(886,0xffea),(892,0x3c0),(918,0x1a1),(966,0x1000660),(975,0x1000661),(984,0x1000662),(993,0x1000663),(1002,0x1000664),(1011,0x1000665),(1020,0x1000666),(1029,0x1000667),(1038,0x1000668),(1047,0x1000669),(1056,0x5d9),(1067,0x5c7),(1079,0x5e9),(1098,0x5c8),(1109,0x5ac),(1122,0x5d6),(1133,0x5cf),(1144,0x5ef),(1157,0x5ec),(1173,0x1000688),(1185,0x10006cc),(1202,0x5ee),(1215,0x5eb),(1231,0x5e1),(1242,0x10006d4),(1258,0x10006af),(1269,0x5da),(1282,0x5e7),(1292,0x5cd),(1303,0x5c1),(1316,0x1000654),(1335,0x1000655),(1354,0x5c3),(1373,0x5c4),(1391,0x5c6),(1409,0x5c5),(1431,0x5e7),(1442,0x10006be),(1465,0x10006c1),(1481,0x5cc),(1493,0x1000698),(1504,0x5e3),(1515,0x5f0),(1528,0x5ed),(1544,0x10006a9),(1557,0x5ce),(1569,0x5e4),(1580,0x1000653),(1599,0x5c2),(1618,0x5e5),(1630,0x5e6),(1642,0x10006ba),(1661,0x100067e),(1672,0x100066a),(1687,0x5e2),(1698,0x5bf),(1719,0x5d1),(1729,0x1000691),(1741,0x5d5),(1752,0x5d3),(1764,0x5bb),(1781,0x5f1),(1795,0x5d4),(1808,0x5f2),(1821,0x1000670),(1845,0xff7e),(1859,0x5d7),(1870,0x5e0),(1885,0x1000686),(1898,0x5ca),(1909,0x5c9),(1927,0x5d0),(1939,0x5cb),(1951,0x1000679),(1963,0x10006a4),(1974,0x5e8),(1985,0x5ea),(1996,0x10006d2),(2013,0x5d8),(2024,0x5d2),(2036,0xc5),(2100,0x1000538),(2124,0x1000531),(2150,0x1000532),(2189,0x1000549),(2215,0x1000534),(2239,0x1000541),(2265,0x1000537),(2303,0x1000556),(2346,0x1000542),(2374,0x1000533),(2400,0x1000545),(2424,0x1000540),(2464,0x100053b),(2490,0x100054b),(2514,0x1000554),(2538,0x100053f),(2564,0x100053d),(2611,0x100053c),(2639,0x1000544),(2665,0x1000546),(2689,0x1000555),(2727,0x100054a),(2751,0x1000553),(2797,0x100054c),(2821,0x1000550),(2845,0x100054d),(2894,0x1000547),(2936,0x1000543),(2964,0x1000539),(2988,0x100053e),(3014,0x1000551),(3040,0x100054f),(3086,0x100054e),(3112,0x1000548),(3136,0x1000552),(3164,0x1000535),(3210,0x1000536),(3234,0x100053a),(2048,0x100055b),(2064,0x100055c),(2080,0x100055a),(2112,0x1000568),(2137,0x1000561),(2163,0x1000562),(2176,0x100055d),(2202,0x1000579),(2227,0x1000564),(2252,0x1000571),(2276,0x1000567),(2287,0x100055c),(2315,0x1000586),(2327,0x1000589),(2360,0x1000572),(2387,0x1000563),(2412,0x1000575),(2436,0x1000570),(2448,0x100058a),(2477,0x100056b),(2502,0x100057b),(2526,0x1000584),(2551,0x100056f),(2577,0x100056d),(2590,0x1000587),(2625,0x100056c),(2652,0x1000574),(2677,0x1000576),(2700,0x1000585),(2711,0x100055e),(2739,0x100057a),(2765,0x1000583),(2779,0x100055e),(2809,0x100057c),(2833,0x1000580),(2857,0x100057d),(2869,0x100055d),(2907,0x1000577),(2920,0x100055b),(2950,0x1000573),(2976,0x1000569),(3001,0x100056e),(3027,0x1000581),(3054,0x100057f),(3068,0x1000589),(3099,0x100057e),(3124,0x1000578),(3150,0x1000582),(3178,0x1000565),(3192,0x100058a),(3222,0x1000566),(3247,0x100056a),(3295,0xc3),(3309,0xfe7a),(3328,0x42),(3332,0x1001e02),(3362,0xff08),(3372,0x1000ff74),(3404,0xff58),(3564,0xfe74),(8377,0xff6b),(8399,0x6be),(8419,0x6ae),(8439,0x43),(8594,0xfea2),(8443,0xfea5),(8447,0xfea4),(8455,0x2c5),(8475,0x1c6),(8489,0xff69),(8496,0xffe5),(8525,0x1c8),(8539,0xc7),(8557,0x2c6),(8597,0xfea1),(8633,0xff0b),(8639,0x1000ff6f),(8654,0xff37),(8670,0x10020a1),(8779,0xffe3),(8789,0xffe4),(8826,0x10020a2),(8864,0x6e1),(8886,0x6e2),(8910,0x6fe),(8936,0x10004b6),(8982,0x10004b8),(9030,0x6e4),(9054,0x6bf),(9082,0x6fc),(9104,0x6e6),(9128,0x6ec),(9152,0x6ed),(9176,0x6ee),(9200,0x10004a2),(9244,0x6f2),(9268,0x6f3),(9292,0x6e7),(9318,0x1000492),(9352,0x6e8),(9420,0x6ff),(9376,0x10004b2),(9456,0x6e9),(9514,0x6e5),(9538,0x6b3),(9478,0x10004e2),(9562,0x6b8),(9586,0x6eb),(9610,0x100049a),(9654,0x100049c),(9700,0x6b9),(9726,0x6ba),(9752,0x6ef),(9774,0x10004e8),(9804,0x6f0),(9828,0x10004d8),(9858,0x6fb),(9884,0x6fd),(9914,0x10004ba),(9942,0x6ea),(9974,0x6f8),(10010,0x6f4),(10034,0x6e3),(10060,0x6f5),(10082,0x10004ee),(10118,0x10004ae),(10158,0x10004b0),(10206,0x6f7),(10230,0x6f1),(10254,0x6f9),(10282,0x6e0),(10306,0x6fa),(10330,0x6f6),(10356,0x1000496),(8875,0x6c1),(8898,0x6c2),(8923,0x6de),(8959,0x10004b7),(9006,0x10004b9),(9042,0x6c4),(9068,0x6af),(9093,0x6dc),(9116,0x6c6),(9140,0x6cc),(9164,0x6cd),(9188,0x6ce),(9222,0x10004a3),(9256,0x6d2),(9280,0x6d3),(9305,0x6c7),(9335,0x1000493),(9364,0x6c8),(9398,0x10004b3),(9438,0x6df),(9467,0x6c9),(9496,0x10004e3),(9526,0x6c5),(9550,0x6a3),(9574,0x6a8),(9598,0x6cb),(9632,0x100049b),(9677,0x100049d),(9713,0x6a9),(9739,0x6aa),(9763,0x6cf),(9789,0x10004e9),(9816,0x6d0),(9843,0x10004d9),(9871,0x6db),(9899,0x6dd),(9928,0x10004bb),(9958,0x6ca),(9992,0x6d8),(10022,0x6d4),(10047,0x6c3),(10071,0x6d5),(10100,0x10004ef),(10138,0x10004af),(10182,0x10004b1),(10218,0x6d7),(10242,0x6d1),(10268,0x6d9),(10294,0x6c0),(10318,0x6da),(10343,0x6d6),(10379,0x1000497),(10402,0x44),(11511,0x1000ff00),(10406,0x1001e0a),(10426,0x1000fe27),(10447,0x1cf),(10461,0x1000fe2c),(10477,0x1000fe5e),(10496,0x1000fe22),(11291,0xffff),(11298,0x1000ff73),(11309,0x1000ff71),(11320,0x1000fe60),(11389,0x10020ab),(11458,0xff54),(11519,0x1000feb0),(11532,0x1d0),(11548,0x1000fe7e),(11555,0x45),(12114,0x3bd),(12195,0xd0),(12278,0x10001b7),(11559,0x3cc),(11579,0xc9),(11593,0x1001eb8),(11613,0x1cc),(11627,0xca),(11651,0x1001ebe),(11685,0x1001ec6),(11725,0x1001ec0),(11759,0x1001ec2),(11791,0x1001ec4),(11825,0x10020a0),(11833,0xcb),(11855,0xc8),(11869,0x1001eba),(11910,0xff2f),(11921,0xff30),(11970,0x3aa),(12067,0xff57),(12166,0x1ca),(12188,0xff1b),(12199,0xd0),(12207,0x1001ebc),(12221,0x20ac),(12248,0xff62),(12256,0x1000ff76),(12267,0x1000ff77),(12286,0x46),(12290,0xffbe),(12293,0xffc7),(12297,0xffc8),(12301,0xffc9),(12305,0xffca),(12309,0xffcb),(12313,0xffcc),(12317,0xffcd),(12321,0xffce),(12325,0xffcf),(12329,0xffd0),(12333,0xffbf),(12336,0xffd1),(12340,0xffd2),(12344,0xffd3),(12348,0xffd4),(12352,0xffd5),(12356,0xffd6),(12360,0xffd7),(12364,0xffd8),(12368,0xffd9),(12372,0xffda),(12376,0xffc0),(12379,0xffdb),(12383,0xffdc),(12387,0xffdd),(12391,0xffde),(12395,0xffdf),(12399,0xffe0),(12403,0xffc1),(12406,0xffc2),(12409,0xffc3),(12412,0xffc4),(12415,0xffc5),(12418,0xffc6),(12547,0x10020a3),(12421,0x1001e1e),(12441,0x10006f0),(12449,0x10006f1),(12457,0x10006f2),(12465,0x10006f3),(12473,0x10006f4),(12481,0x10006f5),(12489,0x10006f6),(12497,0x10006f7),(12505,0x10006f8),(12513,0x10006f9),(12521,0x10006cc),(12662,0xff68),(12667,0xfed0),(12796,0x47),(12800,0x2d5),(12820,0x2ab),(12834,0x10001e6),(12848,0x3ab),(12866,0x2d8),(12890,0x10010d0),(12902,0x10010d1),(12915,0x10010ea),(12928,0x10010ed),(12942,0x10010e9),(12956,0x10010ec),(12969,0x10010d3),(12982,0x10010d4),(12994,0x10010f6),(13006,0x10010d2),(13019,0x10010e6),(13033,0x10010f0),(13046,0x10010f4),(13059,0x10010f1),(13071,0x10010f2),(13084,0x10010f5),(13097,0x10010d8),(13109,0x10010ef),(13123,0x10010eb),(13136,0x10010d9),(13149,0x10010e5),(13163,0x10010da),(13176,0x10010db),(13189,0x10010dc),(13202,0x10010dd),(13214,0x10010de),(13227,0x10010e4),(13241,0x10010e7),(13254,0x10010e0),(13267,0x10010e1),(13280,0x10010e8),(13294,0x10010d7),(13307,0x10010e2),(13320,0x10010e3),(13332,0x10010d5),(13345,0x10010f3),(13357,0x10010ee),(13370,0x10010d6),(13383,0x10010df),(13449,0x7c1),(13473,0x7a1),(13509,0x7c2),(13531,0x7d7),(13551,0x7c4),(13575,0x7c5),(13603,0x7a2),(13643,0x7c7),(13663,0x7a3),(13717,0x7c3),(13756,0x7c9),(13778,0x7a4),(13837,0x7a5),(13857,0x7a5),(13895,0x7ca),(13919,0x7cb),(13945,0x7cb),(13969,0x7cc),(13987,0x7cd),(14005,0x7d9),(14029,0x7ab),(14065,0x7cf),(14093,0x7a7),(14133,0x7d6),(14153,0x7d0),(14171,0x7d8),(14191,0x7d1),(14211,0x7d2),(14248,0x7d4),(14268,0x7c8),(14292,0x7d5),(14320,0x7a8),(14388,0x7a9),(14432,0x7ce),(14450,0x7c6),(13428,0x7ae),(13461,0x7e1),(13491,0x7b1),(13520,0x7e2),(13541,0x7f7),(13563,0x7e4),(13589,0x7e5),(13623,0x7b2),(13653,0x7e7),(13679,0x7b3),(13695,0x7f3),(13729,0x7e3),(13741,0x7af),(13767,0x7e9),(13795,0x7b4),(13812,0x7b6),(13876,0x7b5),(13907,0x7ea),(13932,0x7eb),(13957,0x7eb),(13978,0x7ec),(13996,0x7ed),(14017,0x7f9),(14047,0x7bb),(14079,0x7ef),(14113,0x7b7),(14143,0x7f6),(14162,0x7f0),(14181,0x7f8),(14201,0x7f1),(14223,0x7f2),(14235,0xff7e),(14258,0x7f4),(14280,0x7e8),(14306,0x7f5),(14340,0x7b8),(14360,0x7ba),(14410,0x7b9),(14441,0x7ee),(14461,0x7e6),(14538,0x48),(14552,0xff31),(14559,0xebf),(14568,0xec0),(14578,0xef6),(14591,0xef7),(14605,0xff39),(14618,0xeba),(14631,0xff37),(14648,0xea7),(14662,0xec4),(14682,0xec3),(14692,0xed1),(14671,0xff33),(14702,0xff34),(14715,0xebe),(14728,0xed3),(14737,0xeb7),(14750,0xeea),(14765,0xeda),(14781,0xeee),(14796,0xee8),(14811,0xee9),(14826,0xeeb),(14842,0xed4),(14858,0xed6),(14878,0xef9),(14905,0xee3),(14920,0xed7),(14935,0xed9),(14955,0xed8),(14975,0xef8),(14992,0xeed),(15008,0xee4),(15023,0xee5),(15042,0xedb),(15057,0xee2),(15077,0xedc),(15098,0xedd),(15118,0xee1),(15139,0xede),(15159,0xedf),(15178,0xee0),(15198,0xee6),(15212,0xed5),(15233,0xee7),(15252,0xeec),(15267,0xefa),(15288,0xff35),(15300,0xff38),(15314,0xeb8),(15327,0xebb),(15341,0xea1),(15355,0xea3),(15373,0xef3),(15398,0xeb1),(15411,0xff3d),(15436,0xea4),(15449,0xea6),(15467,0xea5),(15485,0xec7),(15494,0xeca),(15504,0xef2),(15519,0xebd),(15533,0xeb2),(15546,0xeb4),(15563,0xff3b),(15580,0xff3a),(15596,0xff3e),(15621,0xea9),(15634,0xeb0),(15652,0xeaa),(15671,0xeab),(15689,0xeaf),(15708,0xeac),(15726,0xead),(15743,0xeae),(15761,0xeef),(15785,0xff36),(15799,0xff3c),(15822,0xeb5),(15834,0xff3f),(15849,0xea8),(15868,0xeb9),(15886,0xea2),(15905,0xeb3),(15923,0xeb6),(15940,0xff32),(15953,0xef0),(15978,0xef4),(16004,0xef1),(16043,0xebc),(16056,0xecc),(16065,0xec8),(16075,0xec9),(16086,0xece),(16096,0xecd),(16107,0xecf),(16117,0xec1),(16127,0xec2),(16138,0xec6),(16148,0xec5),(16178,0xed2),(16188,0xecb),(16198,0xed0),(16159,0xef5),(16029,0xff7e),(16208,0xff29),(16216,0x2a6),(16632,0xff7e),(16762,0xff6a),(16767,0xff23),(16774,0xff23),(16795,0xff25),(16804,0xff27),(16822,0xff50),(17187,0x2a1),(17206,0xffed),(17214,0xffee),(17229,0x49),(17495,0x100000ee),(17514,0xfe33),(17532,0xfe30),(17557,0xfe31),(17585,0xfe32),(17599,0xfe34),(17609,0xfe2f),(17630,0xfe2c),(17651,0xfe2d),(17673,0xfe2e),(17692,0xfe0c),(17708,0xfe0d),(17729,0xfe06),(17745,0xfe07),(17760,0xff7e),(17776,0xfe0e),(17791,0xfe0f),(17811,0xfe20),(17824,0xfe02),(17841,0xfe04),(17858,0xfe05),(17874,0xfe03),(17891,0xfe12),(17908,0xfe13),(17924,0xfe11),(17941,0xfe01),(17950,0xfe22),(17969,0xfe21),(17986,0xfe08),(18001,0xfe09),(18021,0xfe24),(18043,0xfe23),(18063,0xfe25),(18086,0xfe26),(18110,0xfe0a),(18125,0xfe0b),(18145,0xfe2b),(18170,0xfe29),(18194,0xfe2a),(18219,0xfe27),(18239,0xfe28),(17233,0x2a9),(17243,0xcd),(17257,0x1001eca),(17277,0x100012c),(17291,0xce),(17325,0xcf),(17365,0xcc),(17379,0x1001ec8),(17391,0x3cf),(17444,0xff63),(17451,0x1000ff72),(17462,0x1000ff70),(17498,0x3c7),(18260,0x3a5),(18274,0x4a),(18278,0x2ac),(18306,0x4b),(18968,0xffb0),(18973,0xffb1),(18978,0xffb2),(18983,0xffb3),(18988,0xffb4),(18993,0xffb5),(18998,0xffb6),(19003,0xffb7),(19008,0xffb8),(19013,0xffb9),(19018,0xffab),(19025,0x1000ff75),(19036,0xff9d),(19045,0xffae),(19056,0xff9f),(19066,0xffaf),(19076,0xff99),(19084,0xff9c),(19091,0xff8d),(19100,0xffbd),(19109,0xff91),(19115,0xff92),(19121,0xff93),(19127,0xff94),(19133,0xff95),(19141,0xff9e),(19151,0xff96),(19159,0xffaa),(19171,0xff9b),(19179,0xff9b),(19192,0xff9a),(19203,0xff9a),(19212,0xff98),(19221,0xffac),(19234,0xff80),(19243,0xffad),(19255,0xff89),(19262,0xff97),(18511,0xff2d),(18722,0xff2e),(18905,0xff21),(18911,0xff37),(18930,0xff26),(18939,0x3d3),(18957,0xeff),(19272,0x4c),(19276,0xffc8),(19279,0xffd1),(19283,0xffc9),(19286,0xffca),(19289,0xffcb),(19292,0xffcc),(19295,0xffcd),(19298,0xffce),(19301,0xffcf),(19304,0xffd0),(19307,0x1c5),(19321,0xfed4),(19352,0x1001e36),(19372,0x1a5),(19386,0x3a6),(19404,0xff51),(19619,0xff0a),(19633,0x10020a4),(19702,0x1a3),(19718,0x4d),(19722,0x1001e40),(19742,0x6b5),(19770,0x6b2),(19798,0x6bc),(19756,0x6a5),(19784,0x6a2),(19812,0x6ac),(19833,0xff3e),(19883,0xff2c),(19890,0xff67),(19895,0xffe7),(19902,0xffe8),(19909,0x10020a5),(19932,0xff7e),(19944,0xfe77),(19967,0xfe76),(19987,0xff22),(19996,0xff20),(20006,0xff3d),(20128,0x4e),(20138,0x1d1),(20152,0x10020a6),(20162,0x1d2),(20176,0x3d1),(20194,0x10020aa),(20208,0xff56),(20213,0xfed2),(20276,0x0),(20340,0xd1),(20354,0xff7f),(20385,0x4f),(20699,0x13bc),(20389,0xd3),(20403,0x100019f),(20419,0x1001ecc),(20439,0x10001d1),(20453,0xd4),(20477,0x1001ed0),(20511,0x1001ed8),(20551,0x1001ed2),(20585,0x1001ed4),(20617,0x1001ed6),(20651,0xd6),(20673,0x1d5),(20712,0xd2),(20726,0x1001ece),(20738,0x10001a0),(20750,0x1001eda),(20772,0x1001ee2),(20800,0x1001edc),(20822,0x1001ede),(20842,0x1001ee0),(20864,0x3d2),(20961,0xd8),(21494,0xd8),(21508,0xd5),(21530,0xfe78),(21546,0xfe79),(21571,0x50),(21575,0x1001e56),(21595,0xff56),(21605,0xff55),(21679,0xff13),(21724,0x10020a7),(21770,0xfefa),(21789,0xfee9),(21805,0xfeea),(21821,0xfeeb),(21837,0xfeec),(21853,0xfeed),(21869,0xfee8),(21889,0xfeef),(21907,0xfef0),(21925,0xfef1),(21943,0xfef2),(21961,0xfef3),(21979,0xfeee),(22001,0xfefb),(22021,0xfefc),(22041,0xfee3),(22054,0xfee6),(22071,0xfee7),(22089,0xfef5),(22103,0xfef6),(22117,0xfef7),(22131,0xfef8),(22145,0xfefd),(22159,0xfef4),(22177,0xfef9),(22196,0xfee0),(22209,0xfee1),(22223,0xfee2),(22234,0xfee4),(22249,0xfee5),(22278,0xfed1),(22298,0xff3e),(22316,0xff61),(22322,0xff55),(22354,0x51),(22415,0x52),(22419,0xffd2),(22422,0xffdb),(22426,0xffdc),(22430,0xffdd),(22434,0xffde),(22438,0xffdf),(22442,0xffe0),(22446,0xffd3),(22449,0xffd4),(22452,0xffd5),(22455,0xffd6),(22458,0xffd7),(22461,0xffd8),(22464,0xffd9),(22467,0xffda),(22470,0x1c0),(22492,0x1d8),(22506,0x3a3),(22524,0xff66),(22540,0xfe72),(22558,0x1000ff6c),(22564,0xff0d),(22571,0xff53),(22786,0xff24),(22793,0x10020a8),(22803,0x53),(22873,0x100018f),(24044,0x1001e9e),(22807,0x1001e60),(22827,0x1a6),(22841,0x1a9),(22855,0x1aa),(22885,0x2de),(22923,0xff14),(22951,0xff60),(22984,0x6b1),(23008,0x6bf),(23032,0x6b8),(23054,0x6b9),(23078,0x6ba),(23102,0x6bb),(22996,0x6a1),(23020,0x6af),(23043,0x6a8),(23066,0x6a9),(23090,0x6aa),(23115,0x6ab),(23170,0xffe1),(23178,0xffe6),(23189,0xffe2),(23236,0xff3c),(23271,0x1000d85),(23278,0x1000d86),(23286,0x1000dcf),(23295,0x1000d87),(23303,0x1000dd0),(23312,0x1000d88),(23321,0x1000dd1),(23331,0x1000d93),(23339,0x1000ddb),(23348,0x1000dca),(23356,0x1000d96),(23364,0x1000dde),(23373,0x1000db6),(23381,0x1000db7),(23390,0x1000da0),(23398,0x1000da1),(23407,0x1000da9),(23416,0x1000daa),(23426,0x1000daf),(23435,0x1000db0),(23445,0x1000d91),(23452,0x1000dd9),(23460,0x1000d92),(23468,0x1000dda),(23477,0x1000dc6),(23485,0x1000d9c),(23493,0x1000d9d),(23502,0x1000d83),(23510,0x1000dc4),(23518,0x1000d89),(23525,0x1000dd2),(23533,0x1000d8a),(23541,0x1000dd3),(23550,0x1000da2),(23558,0x1000da3),(23567,0x1000da5),(23577,0x1000d9a),(23585,0x1000d9b),(23594,0x1000df4),(23610,0x1000dbd),(23618,0x1000dc5),(23627,0x1000d8f),(23635,0x1000ddf),(23644,0x1000d90),(23653,0x1000df3),(23663,0x1000db8),(23671,0x1000db9),(23680,0x1000db1),(23688,0x1000dac),(23698,0x1000db3),(23708,0x1000d82),(23716,0x1000d9e),(23725,0x1000d9f),(23734,0x1000da6),(23743,0x1000dab),(23752,0x1000da4),(23761,0x1000d94),(23768,0x1000ddc),(23776,0x1000d95),(23784,0x1000ddd),(23793,0x1000db4),(23801,0x1000db5),(23810,0x1000dbb),(23818,0x1000d8d),(23826,0x1000d8e),(23835,0x1000dd8),(23844,0x1000df2),(23854,0x1000dc3),(23862,0x1000dc1),(23871,0x1000dc2),(23881,0x1000dad),(23890,0x1000dae),(23900,0x1000da7),(23909,0x1000da8),(23919,0x1000d8b),(23926,0x1000dd4),(23934,0x1000d8c),(23942,0x1000dd6),(23951,0x1000dc0),(23959,0x1000dba),(23998,0xfe73),(24067,0xfe75),(24094,0xff66),(24103,0xff7e),(24115,0x1005ff77),(24135,0x1005ff78),(24148,0x1005ff79),(24168,0xff20),(24179,0x1005ff72),(24187,0x1005ff75),(24194,0x1005ff10),(24201,0x1005ff11),(24208,0x1005ff03),(24220,0x1005ff05),(24234,0x1005ff01),(24247,0x1005ff04),(24263,0x1005ff00),(24275,0x1005ff02),(24287,0xff68),(24295,0x1005ff71),(24304,0x1005ff73),(24312,0xff56),(24324,0xff55),(24334,0x1005ff74),(24343,0x1005ff76),(24358,0x1005ff7d),(24378,0xff61),(24394,0x1005ff70),(24403,0xff69),(24411,0x1005ff60),(24422,0xff65),(24430,0x1005ff7a),(24446,0x1005ff7b),(24470,0x1005ff7c),(24494,0xffeb),(24502,0xffec),(24510,0xff15),(24518,0x1000ff6d),(24525,0x54),(25730,0xde),(24529,0xff09),(24533,0x1001e6a),(24553,0x1ab),(24567,0x1de),(24613,0xfed5),(24630,0xddf),(24640,0xdba),(24654,0xda8),(24667,0xdaa),(24681,0xda9),(24695,0xdac),(24708,0xdae),(24721,0xdb4),(24732,0xdbd),(24742,0xdbf),(24753,0xdcb),(24764,0xdce),(24778,0xda2),(24791,0xda5),(24804,0xda3),(24818,0xda4),(24832,0xda6),(24848,0xda1),(24859,0xde5),(24876,0xdf7),(24889,0xdf5),(24900,0xdf6),(24912,0xdf9),(24924,0xdf1),(24937,0xdf8),(24950,0xdf3),(24962,0xdf4),(24973,0xdf2),(24986,0xdf0),(24998,0xdcc),(25011,0xdc5),(25023,0xdc6),(25031,0xdeb),(25048,0xde8),(25059,0xdd1),(25075,0xdde),(25098,0xde7),(25113,0xde9),(25125,0xdea),(25137,0xde6),(25151,0xdc1),(25161,0xda7),(25173,0xded),(25187,0xdb3),(25198,0xdb9),(25208,0xdcd),(25218,0xdcf),(25233,0xdda),(25246,0xdbe),(25259,0xdbc),(25273,0xdc0),(25289,0xdbb),(25300,0xdc3),(25311,0xdc4),(25319,0xdd0),(25330,0xdd2),(25342,0xde1),(25354,0xde4),(25374,0xde3),(25393,0xdd3),(25405,0xde0),(25416,0xdd4),(25427,0xdd5),(25439,0xde2),(25450,0xdd8),(25461,0xdd6),(25473,0xdd7),(25486,0xdd9),(25498,0xdc9),(25510,0xdc8),(25522,0xdab),(25532,0xdca),(25543,0xdec),(25560,0xdb1),(25579,0xdb2),(25595,0xdb7),(25610,0xdb0),(25623,0xdb8),(25637,0xdb6),(25651,0xdaf),(25664,0xdb5),(25675,0xdc7),(25687,0xdc2),(25698,0xdad),(25736,0xde),(25983,0xff2b),(26019,0x3ac),(26078,0x55),(26082,0xda),(26096,0x1001ee4),(26116,0x2dd),(26130,0xdb),(26154,0xdc),(26176,0x1db),(26202,0xd9),(26216,0x1001ee6),(26228,0x10001af),(26240,0x1001ee8),(26262,0x1001ef0),(26290,0x1001eea),(26312,0x1001eec),(26332,0x1001eee),(26354,0x6bd),(26406,0x6b6),(26430,0x6b4),(26456,0x6b7),(26380,0x6ad),(26418,0x6a6),(26443,0x6a4),(26469,0x6a7),(26482,0x6b6),(26504,0x6b4),(26528,0x6b7),(26493,0x6a6),(26516,0x6a4),(26540,0x6a7),(26552,0x3de),(26588,0xff65),(26599,0x3d9),(26615,0xff52),(26683,0x1d9),(26695,0x1000ff6e),(26700,0x3dd),(26714,0x56),(26762,0xffffff),(26776,0x57),(26780,0x1001e82),(26794,0x1000174),(26818,0x1001e84),(26840,0x1001e80),(26854,0x10020a9),(26862,0x58),(26886,0x100811b9),(26905,0x100811b8),(26922,0x1008126f),(26983,0x10081230),(26933,0x1008124e),(26951,0x1008ff39),(26967,0x100811ad),(27038,0x10081244),(26997,0x1008ff50),(27017,0x1008ff51),(27052,0x10081177),(27068,0x10081247),(27082,0x1008121c),(27099,0x1008121b),(27115,0x1008121d),(27135,0x10081188),(27145,0x100810d1),(27164,0x10081175),(27185,0x1008ff9b),(27205,0x1008126e),(27219,0x1008ff97),(27236,0x1008ff11),(27257,0x1008ff32),(27272,0x1008ffb2),(27289,0x1008ff12),(27303,0x1008ff17),(27317,0x1008ff31),(27332,0x1008ff14),(27346,0x1008ffb6),(27362,0x1008ff16),(27376,0x1008ff13),(27397,0x1008ff99),(27417,0x1008ff1c),(27433,0x1008ff98),(27449,0x1008ff3e),(27465,0x1008ff15),(27479,0x1008127d),(27505,0x1008ff8d),(27514,0x1008ff26),(27523,0x1008ff3f),(27539,0x1008ff93),(27551,0x1008ffa6),(27560,0x1008ff94),(27574,0x1008ff52),(27583,0x1008119b),(27593,0x1008ff3b),(27614,0x100810f4),(27633,0x10081251),(27651,0x10081250),(27669,0x10081240),(27909,0x1008ff53),(27686,0x1008ff54),(27701,0x1008ff1d),(27716,0x1008ff20),(27729,0x1008124c),(27753,0x1008124b),(27776,0x1008124d),(27799,0x10081218),(27814,0x10081210),(27830,0x10081219),(27845,0x1008121a),(27861,0x10081217),(27874,0x10081215),(27891,0x10081216),(27916,0x10081193),(27932,0x10081192),(27946,0x1008ff55),(27956,0x1008fe21),(27970,0x10081286),(27987,0x1008ff56),(27997,0x1008ff3d),(28011,0x100811b6),(28027,0x1008ff22),(28046,0x10081243),(28063,0x1008ff57),(28072,0x1008ff58),(28080,0x1008ff9c),(28205,0x1008ff5a),(28232,0x10081185),(28095,0x10081277),(28104,0x100811aa),(28117,0x1008124a),(28129,0x1008ff59),(28141,0x100810f5),(28156,0x100811af),(28188,0x1008124f),(28174,0x1008ff5b),(28213,0x10081283),(28240,0x100811a6),(28251,0x1008ff2c),(28261,0x10081253),(28292,0x10081252),(28322,0x10081249),(28338,0x1008ff5c),(28348,0x100810ae),(28357,0x1008ff5d),(28370,0x10081275),(28386,0x1008ff30),(28400,0x1008ff3c),(28412,0x10081281),(28429,0x100811d0),(28633,0x100811e5),(28436,0x100811de),(28445,0x100811df),(28454,0x100811e4),(28463,0x100811e0),(28472,0x100811e1),(28481,0x100811d1),(28492,0x100811e2),(28501,0x100811d2),(28511,0x100811db),(28522,0x100811dc),(28533,0x100811dd),(28544,0x100811d3),(28554,0x100811d4),(28564,0x100811d5),(28574,0x100811d6),(28584,0x100811d7),(28594,0x100811d8),(28604,0x100811d9),(28614,0x100811da),(28624,0x100811e3),(28650,0x1008ff27),(28662,0x1008ff9d),(28676,0x1008ff9e),(28693,0x1008ffb8),(28708,0x1008ff5e),(28717,0x1008ff5f),(28724,0x10081162),(28733,0x100811a8),(28752,0x1008ffa4),(28762,0x100811be),(28778,0x1008ffa8),(28792,0x1008ff37),(28804,0x1008ff18),(28817,0x1008ff3a),(28830,0x100811ba),(28841,0x10081166),(28861,0x10081242),(28873,0x1008ff06),(28895,0x1008ff05),(28915,0x10081264),(28940,0x10081265),(28965,0x10081261),(28988,0x10081263),(29016,0x10081260),(29039,0x10081262),(29067,0x100812b8),(29083,0x100812b9),(29099,0x100812ba),(29115,0x100812bb),(29131,0x100812bc),(29147,0x1008ff04),(29165,0x1008ffb3),(29178,0x1008ff40),(29190,0x1008ff41),(29202,0x1008ff42),(29214,0x1008ff43),(29226,0x1008ff44),(29238,0x1008ff45),(29250,0x1008ff46),(29262,0x1008ff47),(29274,0x1008ff48),(29286,0x1008ff49),(29298,0x1008ff4a),(29310,0x1008ff4b),(29322,0x1008ff4c),(29334,0x1008ff4d),(29346,0x1008ff4e),(29358,0x1008ff4f),(29370,0x10081269),(29383,0x10081268),(29394,0x1008ff35),(29408,0x1008121e),(29425,0x100811bf),(29439,0x1008fe25),(29455,0x1008ff61),(29466,0x1008fe24),(29484,0x10081290),(29495,0x10081299),(29507,0x1008129a),(29519,0x1008129b),(29531,0x1008129c),(29543,0x1008129d),(29555,0x1008129e),(29567,0x1008129f),(29579,0x100812a0),(29591,0x100812a1),(29603,0x100812a2),(29615,0x10081291),(29626,0x100812a3),(29638,0x100812a4),(29650,0x100812a5),(29662,0x100812a6),(29674,0x100812a7),(29686,0x100812a8),(29698,0x100812a9),(29710,0x100812aa),(29722,0x100812ab),(29734,0x100812ac),(29746,0x10081292),(29757,0x100812ad),(29769,0x10081293),(29780,0x10081294),(29791,0x10081295),(29802,0x10081296),(29813,0x10081297),(29824,0x10081298),(29835,0x100812b3),(29852,0x100812b4),(29869,0x100812b5),(29886,0x100812b2),(29907,0x100812b0),(29928,0x100812b1),(29948,0x1008ff19),(29957,0x1008ff90),(29984,0x1008127e),(29973,0x1008ff62),(30001,0x10081170),(30039,0x100810a4),(30058,0x10081199),(30023,0x10081183),(30076,0x100811b7),(30092,0x10081186),(30138,0x1008ff53),(30117,0x1008117a),(30156,0x10081185),(30175,0x1008116e),(30195,0x10081178),(30213,0x1008116a),(30241,0x10081181),(30262,0x1008117d),(30352,0x10081179),(30287,0x10081180),(30307,0x10081184),(30331,0x10081182),(30370,0x1008117b),(30389,0x1008117c),(30412,0x10081171),(30431,0x1008126b),(30448,0x1008ff63),(30460,0x1008ff1e),(30469,0x1008ff65),(30480,0x1008ff66),(30491,0x1008ff8e),(30505,0x1008ff01),(30518,0x100810f4),(30540,0x1008ff07),(30563,0x1008ff03),(30585,0x1008ff02),(30605,0x1008ff92),(30615,0x1008ff33),(30630,0x1008ff67),(30642,0x10081280),(30655,0x10081288),(30667,0x1008ff68),(30675,0x1008ff69),(30699,0x1008127b),(30715,0x10081270),(30684,0x1008fe22),(30732,0x100811bc),(30755,0x1008119d),(30775,0x10081200),(30788,0x10081201),(30801,0x1008126c),(30815,0x1008126d),(30829,0x10081202),(30842,0x10081203),(30855,0x10081204),(30868,0x10081205),(30881,0x10081206),(30894,0x10081207),(30907,0x10081208),(30920,0x10081209),(30933,0x1008120c),(30946,0x1008120d),(30959,0x1008120e),(30972,0x1008120f),(30985,0x1008120b),(31002,0x1008120a),(31033,0x10081160),(31018,0x1008ff6a),(31040,0x10081278),(31061,0x1008ff6b),(31070,0x1008ff38),(31082,0x1008ff6c),(31093,0x1008ff6d),(31103,0x10081272),(31119,0x100812bd),(31139,0x1008ff6e),(31149,0x100811bd),(31165,0x1008ff91),(31178,0x1008ff21),(31192,0x1008ff2a),(31205,0x100811a9),(31222,0x1008fe23),(31237,0x1008127c),(31257,0x10081279),(31281,0x1008ff70),(31367,0x1008ffb5),(31287,0x10081284),(31304,0x1008ffa3),(31312,0x1008ff29),(31324,0x10081232),(31346,0x1008ff73),(31357,0x1008ff72),(31378,0x10081267),(31392,0x10081266),(31404,0x1008ff24),(31419,0x1008ff25),(31435,0x1008ff23),(31448,0x1008126a),(31461,0x1008ff74),(31479,0x1008ff76),(31494,0x1008ffb7),(31517,0x1008ff75),(31532,0x1008ff77),(31541,0x1008ff2d),(31557,0x10081245),(31573,0x1008ff7a),(31589,0x1008ff79),(31604,0x1008ff78),(31617,0x1008ff1b),(31628,0x1008ffa0),(31639,0x1008127a),(31663,0x1008ff7b),(31672,0x1008ff36),(31681,0x10081287),(31697,0x10081282),(31718,0x1008ff2f),(31728,0x10081276),(31744,0x1008127f),(31752,0x1008ff7c),(31762,0x100811b0),(31777,0x1008ff7d),(31793,0x100810dc),(31803,0x1008ff10),(31815,0x1008ff1a),(31825,0x1008ff28),(31834,0x10081271),(31849,0x1008ff9a),(31862,0x1008ff7e),(31874,0x1008ffa7),(31886,0x1008fe01),(31902,0x1008fe0a),(31919,0x1008fe0b),(31936,0x1008fe0c),(31953,0x1008fe02),(31969,0x1008fe03),(31985,0x1008fe04),(32001,0x1008fe05),(32017,0x1008fe06),(32033,0x1008fe07),(32049,0x1008fe08),(32065,0x1008fe09),(32097,0x1008ff7f),(32081,0x10081241),(32110,0x1008ff80),(32123,0x1008ff9f),(32132,0x1008ff1f),(32145,0x1008ff81),(32155,0x1008ffa2),(32167,0x1008ffb1),(32183,0x1008ffb0),(32198,0x1008ffa9),(32217,0x10081285),(32238,0x1008ff82),(32306,0x1008ff96),(32249,0x1008fe20),(32260,0x10081274),(32271,0x1008ff85),(32283,0x1008ff86),(32295,0x1008ff84),(32378,0x10081273),(32314,0x1008ff34),(32329,0x10081168),(32344,0x1008ff87),(32354,0x100811a0),(32369,0x1008ffa1),(32386,0x10081246),(32403,0x100811ac),(32455,0x1008ff95),(32473,0x10081211),(32487,0x1008ffb4),(32496,0x1008ff2e),(32417,0x1008ff2b),(32428,0x1008ff8f),(32439,0x1008ff88),(32464,0x1008ff89),(32504,0x1008ff8a),(32513,0x1008ffa5),(32524,0x1008ff8b),(32535,0x1008ff8c),(32547,0x100811a4),(28850,0x1008ff60),(26866,0x1001e8a),(32561,0x59),(32565,0xdd),(32579,0x1001ef4),(32599,0x1000176),(32623,0x13be),(32649,0x1001ef2),(32663,0x1001ef6),(32675,0x1001ef8),(32689,0x5a),(32693,0x1af),(32713,0x1ac),(32727,0x1ae),(32741,0xff3d),(32750,0xff28),(32758,0xff2a),(32801,0x10001b5),(400,0x61),(409,0xe1),(426,0x1001ea1),(436,0x1ff),(452,0x1e3),(471,0x1001eaf),(498,0x1001eb7),(525,0x1001eb1),(548,0x1001eb3),(571,0x1001eb5),(634,0xe2),(663,0x1001ea5),(700,0x1001ead),(737,0x1001ea7),(770,0x1001ea9),(803,0x1001eab),(820,0xb4),(837,0xe4),(851,0xe6),(861,0xe0),(874,0x1001ea3),(900,0x3e0),(908,0x26),(926,0x1b1),(934,0x27),(945,0x1002248),(954,0x8c8),(2042,0xe5),(3260,0x5e),(3272,0x7e),(3283,0x2a),(3292,0x40),(3302,0xe3),(3330,0x62),(3342,0x1001e03),(3352,0x5c),(3380,0xaf4),(3392,0x7c),(3396,0x1002235),(3410,0x9df),(3416,0x100000fc),(3422,0x8a5),(3434,0x8ac),(3448,0x8a8),(3465,0x8b2),(3482,0x8ae),(3497,0x8aa),(3515,0x8b6),(3533,0x9f6),(3538,0x8b4),(3582,0x7b),(3592,0x7d),(3603,0x5b),(3615,0x5d),(3628,0x1002800),(3642,0xfff1),(3656,0xfffa),(3671,0xfff2),(3685,0xfff3),(3699,0xfff4),(3713,0xfff5),(3727,0xfff6),(3741,0xfff7),(3755,0xfff8),(3769,0xfff9),(3783,0x1002801),(3798,0x1002803),(3814,0x1002807),(3831,0x100280f),(3849,0x100281f),(3868,0x100283f),(3888,0x100287f),(3909,0x10028ff),(3931,0x10028bf),(3952,0x100285f),(3972,0x10028df),(3993,0x100289f),(4013,0x100282f),(4032,0x100286f),(4052,0x10028ef),(4073,0x10028af),(4093,0x100284f),(4112,0x10028cf),(4132,0x100288f),(4151,0x1002817),(4169,0x1002837),(4188,0x1002877),(4208,0x10028f7),(4229,0x10028b7),(4249,0x1002857),(4268,0x10028d7),(4288,0x1002897),(4307,0x1002827),(4325,0x1002867),(4344,0x10028e7),(4364,0x10028a7),(4383,0x1002847),(4401,0x10028c7),(4420,0x1002887),(4438,0x100280b),(4455,0x100281b),(4473,0x100283b),(4492,0x100287b),(4512,0x10028fb),(4533,0x10028bb),(4553,0x100285b),(4572,0x10028db),(4592,0x100289b),(4611,0x100282b),(4629,0x100286b),(4648,0x10028eb),(4668,0x10028ab),(4687,0x100284b),(4705,0x10028cb),(4724,0x100288b),(4742,0x1002813),(4759,0x1002833),(4777,0x1002873),(4796,0x10028f3),(4816,0x10028b3),(4835,0x1002853),(4853,0x10028d3),(4872,0x1002893),(4890,0x1002823),(4907,0x1002863),(4925,0x10028e3),(4944,0x10028a3),(4962,0x1002843),(4979,0x10028c3),(4997,0x1002883),(5014,0x1002805),(5030,0x100280d),(5047,0x100281d),(5065,0x100283d),(5084,0x100287d),(5104,0x10028fd),(5125,0x10028bd),(5145,0x100285d),(5164,0x10028dd),(5184,0x100289d),(5203,0x100282d),(5221,0x100286d),(5240,0x10028ed),(5260,0x10028ad),(5279,0x100284d),(5297,0x10028cd),(5316,0x100288d),(5334,0x1002815),(5351,0x1002835),(5369,0x1002875),(5388,0x10028f5),(5408,0x10028b5),(5427,0x1002855),(5445,0x10028d5),(5464,0x1002895),(5482,0x1002825),(5499,0x1002865),(5517,0x10028e5),(5536,0x10028a5),(5554,0x1002845),(5571,0x10028c5),(5589,0x1002885),(5606,0x1002809),(5622,0x1002819),(5639,0x1002839),(5657,0x1002879),(5676,0x10028f9),(5696,0x10028b9),(5715,0x1002859),(5733,0x10028d9),(5752,0x1002899),(5770,0x1002829),(5787,0x1002869),(5805,0x10028e9),(5824,0x10028a9),(5842,0x1002849),(5859,0x10028c9),(5877,0x1002889),(5894,0x1002811),(5910,0x1002831),(5927,0x1002871),(5945,0x10028f1),(5964,0x10028b1),(5982,0x1002851),(5999,0x10028d1),(6017,0x1002891),(6034,0x1002821),(6050,0x1002861),(6067,0x10028e1),(6085,0x10028a1),(6102,0x1002841),(6118,0x10028c1),(6135,0x1002881),(6151,0x1002802),(6166,0x1002806),(6182,0x100280e),(6199,0x100281e),(6217,0x100283e),(6236,0x100287e),(6256,0x10028fe),(6277,0x10028be),(6297,0x100285e),(6316,0x10028de),(6336,0x100289e),(6355,0x100282e),(6373,0x100286e),(6392,0x10028ee),(6412,0x10028ae),(6431,0x100284e),(6449,0x10028ce),(6468,0x100288e),(6486,0x1002816),(6503,0x1002836),(6521,0x1002876),(6540,0x10028f6),(6560,0x10028b6),(6579,0x1002856),(6597,0x10028d6),(6616,0x1002896),(6634,0x1002826),(6651,0x1002866),(6669,0x10028e6),(6688,0x10028a6),(6706,0x1002846),(6723,0x10028c6),(6741,0x1002886),(6758,0x100280a),(6774,0x100281a),(6791,0x100283a),(6809,0x100287a),(6828,0x10028fa),(6848,0x10028ba),(6867,0x100285a),(6885,0x10028da),(6904,0x100289a),(6922,0x100282a),(6939,0x100286a),(6957,0x10028ea),(6976,0x10028aa),(6994,0x100284a),(7011,0x10028ca),(7029,0x100288a),(7046,0x1002812),(7062,0x1002832),(7079,0x1002872),(7097,0x10028f2),(7116,0x10028b2),(7134,0x1002852),(7151,0x10028d2),(7169,0x1002892),(7186,0x1002822),(7202,0x1002862),(7219,0x10028e2),(7237,0x10028a2),(7254,0x1002842),(7270,0x10028c2),(7287,0x1002882),(7303,0x1002804),(7318,0x100280c),(7334,0x100281c),(7351,0x100283c),(7369,0x100287c),(7388,0x10028fc),(7408,0x10028bc),(7427,0x100285c),(7445,0x10028dc),(7464,0x100289c),(7482,0x100282c),(7499,0x100286c),(7517,0x10028ec),(7536,0x10028ac),(7554,0x100284c),(7571,0x10028cc),(7589,0x100288c),(7606,0x1002814),(7622,0x1002834),(7639,0x1002874),(7657,0x10028f4),(7676,0x10028b4),(7694,0x1002854),(7711,0x10028d4),(7729,0x1002894),(7746,0x1002824),(7762,0x1002864),(7779,0x10028e4),(7797,0x10028a4),(7814,0x1002844),(7830,0x10028c4),(7847,0x1002884),(7863,0x1002808),(7878,0x1002818),(7894,0x1002838),(7911,0x1002878),(7929,0x10028f8),(7948,0x10028b8),(7966,0x1002858),(7983,0x10028d8),(8001,0x1002898),(8018,0x1002828),(8034,0x1002868),(8051,0x10028e8),(8069,0x10028a8),(8086,0x1002848),(8102,0x10028c8),(8119,0x1002888),(8135,0x1002810),(8150,0x1002830),(8166,0x1002870),(8183,0x10028f0),(8201,0x10028b0),(8218,0x1002850),(8234,0x10028d0),(8251,0x1002890),(8267,0x1002820),(8282,0x1002860),(8298,0x10028e0),(8315,0x10028a0),(8331,0x1002840),(8346,0x10028c0),(8362,0x1002880),(8383,0x1a2),(8389,0xa6),(8441,0x63),(8451,0xfea3),(8465,0x2e5),(8482,0x1e6),(8506,0xab8),(8513,0xafc),(8519,0x1b7),(8532,0x1e8),(8548,0xe7),(8569,0x2e6),(8581,0xb8),(8589,0xa2),(8600,0xfea0),(8603,0x9e1),(8616,0xaf3),(8626,0xbcf),(8649,0xaec),(8664,0x3a),(8680,0x1000301),(8696,0x1000323),(8715,0x1000300),(8731,0x1000309),(8746,0x1000303),(8762,0x2c),(8768,0x100220b),(8799,0xa9),(8809,0x9e4),(8812,0x9ee),(8839,0x100221b),(8848,0xa4),(8857,0xaff),(10404,0x64),(10416,0x1001e0b),(10440,0xaf1),(10454,0x1ef),(10507,0xfe81),(10942,0xfe83),(11009,0xfe85),(11101,0xfe87),(11155,0xfe8b),(11239,0xfe89),(10514,0xfe80),(10521,0xfe64),(10537,0xfe56),(10551,0xfe65),(10575,0xfe58),(10590,0xfe91),(10613,0xfe51),(10624,0xfe8e),(10640,0xfe6b),(10656,0xfe69),(10677,0xfe6e),(10693,0xfe6c),(10713,0xfe60),(10727,0xfe68),(10744,0xfe67),(10759,0xfe6a),(10775,0xfe92),(10798,0xfe55),(10809,0xfe8b),(10828,0xfe5a),(10839,0xfe5b),(10852,0xfe52),(10868,0xfe6f),(10882,0xfe65),(10893,0xfe57),(10908,0xfe59),(10925,0xfe66),(10949,0xfe82),(10956,0xfe50),(10967,0xfe8c),(10978,0xfe8d),(10989,0xfe61),(10999,0xfe62),(11016,0xfe84),(11023,0xfe6d),(11042,0xfe5d),(11052,0xfe93),(11076,0xfe90),(11089,0xfe54),(11108,0xfe86),(11115,0xfe5c),(11127,0xfe53),(11144,0xfe64),(11166,0xfe8a),(11177,0xfe5f),(11199,0xfe8a),(11216,0xfe63),(11228,0xfe53),(11246,0xfe88),(11253,0xfe5e),(11271,0xabd),(11284,0xb0),(11334,0xa8),(11344,0xaed),(11352,0xaa5),(11363,0x100222c),(11373,0xf7),(11382,0x24),(11398,0xaaf),(11414,0x1bd),(11426,0xaf2),(11439,0xafe),(11463,0x8fe),(11473,0xba8),(11483,0xbd6),(11492,0xbc4),(11502,0xbc2),(11540,0x1f0),(11557,0x65),(11569,0x3ec),(11586,0xe9),(11603,0x1001eb9),(11620,0x1ec),(11639,0xea),(11668,0x1001ebf),(11705,0x1001ec7),(11742,0x1001ec1),(11775,0x1001ec3),(11808,0x1001ec5),(11844,0xeb),(11862,0xe8),(11875,0x1001ebb),(11881,0x1002088),(11896,0x1002078),(11933,0x1002208),(11943,0xaae),(11952,0xaa3),(11961,0xaa4),(11978,0x3ba),(11986,0xaa9),(11993,0xade),(12008,0xadf),(12021,0xace),(12034,0xacf),(12050,0x1002205),(12059,0xaa1),(12071,0xaaa),(12078,0xae6),(12097,0xae7),(12118,0x3bf),(12122,0xae0),(12139,0xae1),(12158,0xaa2),(12174,0x1ea),(12182,0x3d),(12203,0xf0),(12214,0x1001ebd),(12230,0x21),(12237,0xa1),(12282,0x1000292),(12288,0x66),(12431,0x1001e1f),(12531,0xaf8),(12544,0x9e3),(12558,0xabb),(12566,0xadc),(12586,0xadb),(12603,0xadd),(12624,0xae9),(12644,0xae8),(12688,0xac5),(12700,0xab7),(12711,0x1002085),(12725,0x1002075),(12738,0xab5),(12749,0x1002084),(12763,0x1002074),(12776,0x100221c),(12787,0x8f6),(12798,0x67),(12810,0x2f5),(12827,0x2bb),(12841,0x10001e7),(12857,0x3bb),(12878,0x2f8),(13397,0x60),(13403,0x3e),(13411,0x8be),(14472,0x100000be),(14480,0xab),(14494,0xbb),(14509,0xab),(14523,0xbb),(14540,0x68),(14542,0xaa8),(16228,0x2b6),(16240,0xaee),(16246,0xce0),(16259,0xcf2),(16271,0xce1),(16282,0xce1),(16294,0xce7),(16306,0xce3),(16319,0xce3),(16333,0xcdf),(16354,0xcea),(16371,0xced),(16387,0xcef),(16403,0xcf3),(16418,0xcf5),(16435,0xcf5),(16452,0xce2),(16465,0xce2),(16479,0xce4),(16489,0xce7),(16500,0xceb),(16512,0xcf7),(16523,0xcec),(16536,0xcee),(16547,0xcf0),(16558,0xcf4),(16568,0xcf7),(16580,0xcf8),(16592,0xcf1),(16606,0xcf1),(16620,0xcf9),(16646,0xcfa),(16657,0xcfa),(16668,0xce8),(16679,0xce8),(16691,0xce5),(16702,0xce9),(16713,0xcf6),(16725,0xcf6),(16737,0xce6),(16749,0xce6),(16786,0xada),(16827,0x8a3),(16842,0x9ef),(16857,0x9f0),(16872,0x9f1),(16887,0x9f2),(16902,0x9f3),(16917,0x1000ff74),(16935,0x1000ff6f),(16947,0x1000ff73),(16960,0x1000ff71),(17009,0x100000ee),(16983,0x1000ff72),(16996,0x1000ff70),(17014,0x1000ff75),(17046,0x1000ff48),(17058,0x1000ff49),(17150,0x1000ff6c),(17158,0x1000ff6d),(17167,0x1000ff6e),(17174,0x100000ee),(16927,0x100000fc),(16973,0x100000be),(17027,0x100000af),(17034,0x100000f6),(17070,0x100000a8),(17083,0x100000aa),(17102,0x100000ac),(17120,0x100000ab),(17137,0x100000a9),(17195,0x2b1),(17203,0x9e2),(17222,0xad),(17231,0x69),(17250,0xed),(17267,0x1001ecb),(17284,0x100012d),(17303,0xee),(17315,0x8cf),(17336,0xef),(17347,0x2b9),(17356,0x8cd),(17372,0xec),(17385,0x1001ec9),(17399,0x3ef),(17407,0x8ce),(17415,0x8da),(17426,0x8db),(17435,0x8c2),(17473,0x8bf),(17482,0x8dc),(17506,0x3e7),(18267,0x3b5),(18276,0x6a),(18290,0x2bc),(18302,0xbca),(18308,0x6b),(18310,0x4b1),(18324,0x4c1),(18381,0x4b4),(18395,0x4cc),(18417,0x4ca),(18425,0x4cd),(18433,0x4cb),(18441,0x4ce),(18449,0x4cc),(18457,0x4b2),(18471,0x4b6),(18479,0x4b9),(18487,0x4b7),(18495,0x4ba),(18503,0x4b8),(18521,0x4cf),(18529,0x4d2),(18537,0x4d0),(18560,0x4d3),(18568,0x4d1),(18576,0x4dd),(18583,0x4c5),(18591,0x4c8),(18599,0x4c6),(18607,0x4c9),(18615,0x4c7),(18623,0x4b5),(18657,0x4d7),(18665,0x4da),(18673,0x4d8),(18681,0x4db),(18689,0x4d9),(18697,0x4bb),(18705,0x4be),(18713,0x4bc),(18733,0x4bf),(18741,0x4bd),(18761,0x4c0),(18769,0x4c3),(18777,0x4c1),(18785,0x4c4),(18793,0x4c2),(18811,0x4c2),(18827,0x4b3),(18841,0x4dc),(18849,0x4a6),(18857,0x4d4),(18873,0x4d6),(18889,0x4d5),(18317,0x4a7),(18333,0x4a3),(18353,0x4a4),(18364,0x4a5),(18388,0x4aa),(18403,0x4a1),(18464,0x4a8),(18545,0x4a5),(18630,0x4ab),(18637,0x4a2),(18749,0xff7e),(18802,0x4af),(18819,0x4af),(18834,0x4a9),(18865,0x4ac),(18881,0x4ae),(18897,0x4ad),(18924,0x3a2),(18948,0x3f3),(19268,0x3a2),(19274,0x6c),(19314,0x1e5),(19341,0xad9),(19362,0x1001e37),(19379,0x1b5),(19395,0x3b6),(19409,0xabc),(19426,0x8fb),(19436,0xba3),(19446,0xad2),(19466,0x8af),(19487,0xacc),(19504,0xaea),(19516,0x8a1),(19528,0xbda),(19537,0x1002039),(19562,0xad0),(19582,0x9f4),(19588,0xbdc),(19597,0x3c),(19602,0x8bc),(19616,0x9e5),(19628,0x100000af),(19642,0x8de),(19653,0x8df),(19663,0x100000f6),(19673,0x9ed),(19687,0x9ea),(19710,0x1b3),(19720,0x6d),(19732,0x1001e41),(19826,0xaf),(19842,0xaf7),(19853,0xaf0),(19866,0xabf),(19873,0xba),(19918,0x2d),(19924,0xad6),(19984,0xb5),(20024,0xd7),(20033,0xaf6),(20045,0xaf5),(20058,0x100000a8),(20069,0x100000aa),(20086,0x100000ac),(20102,0x100000ab),(20117,0x100000a9),(20130,0x6e),(20132,0x8c5),(20145,0x1f1),(20169,0x1f2),(20185,0x3f1),(20233,0x1002089),(20247,0x1002079),(20260,0x9e8),(20263,0xa0),(20285,0x1002247),(20297,0x1002209),(20310,0x8bd),(20319,0x1002262),(20332,0xac),(20347,0xf1),(20363,0x23),(20374,0x6b0),(20387,0x6f),(20396,0xf3),(20411,0x1000275),(20429,0x1001ecd),(20446,0x10001d2),(20465,0xf4),(20494,0x1001ed1),(20531,0x1001ed9),(20568,0x1001ed3),(20601,0x1001ed5),(20634,0x1001ed7),(20662,0xf6),(20686,0x1f5),(20702,0x13bd),(20705,0x1b2),(20719,0xf2),(20732,0x1001ecf),(20744,0x10001a1),(20761,0x1001edb),(20786,0x1001ee3),(20811,0x1001edd),(20832,0x1001edf),(20853,0x1001ee1),(20872,0x3f2),(20880,0xac3),(20890,0xab2),(20899,0xbd),(20907,0xbc),(20918,0xab6),(20927,0x1002081),(20940,0xb9),(20952,0xab0),(20970,0xf8),(20979,0xae2),(20994,0xae5),(21003,0xae4),(21021,0xae3),(21037,0xaa),(21049,0xba),(21062,0x1004ff44),(21074,0x1004ff31),(21085,0x1004ff08),(21098,0x1004ff07),(21109,0x1004ff5a),(21122,0x1004ff58),(21135,0x1004ff69),(21145,0x1004ff0b),(21154,0x1004ff02),(21162,0x1004ff03),(21169,0x1004ffff),(21179,0x1004ff72),(21194,0x1004ff54),(21202,0x1004ff59),(21213,0x1004ff57),(21224,0x1004ff1b),(21234,0x1004ff74),(21244,0x1004ff6a),(21252,0x1004ff63),(21262,0x1004ff51),(21270,0x1004ff67),(21278,0x1004ff45),(21289,0x1004ff5e),(21302,0x1004ff5c),(21314,0x1004ff42),(21326,0x1004ff40),(21338,0x1004ff43),(21351,0x1004ff41),(21361,0x1004ff04),(21370,0x1004ff5d),(21383,0x1004ff5b),(21395,0x1004ff32),(21411,0x1004ff33),(21425,0x1004ff73),(21437,0x1004ff78),(21448,0x1004ff53),(21457,0x1004ff60),(21467,0x1004ff71),(21480,0x1004ff65),(21488,0x1004ff52),(21501,0xf8),(21515,0xf5),(21522,0xbc0),(21562,0x47e),(21573,0x70),(21585,0x1001e57),(21613,0xb6),(21623,0x28),(21633,0x29),(21644,0x1002202),(21661,0x8ef),(21685,0x25),(21693,0x2e),(21700,0xb7),(21715,0xad5),(21735,0xafb),(21755,0x2b),(21760,0xb1),(22265,0xad4),(22328,0x4b0),(22343,0xaa6),(22356,0x71),(22358,0xbcc),(22363,0x3f),(22372,0xbf),(22385,0x22),(22394,0x60),(22404,0x27),(22417,0x72),(22477,0x1e0),(22484,0x8d6),(22499,0x1f8),(22515,0x3b3),(22529,0xae),(22577,0xabe),(22595,0x8fd),(22606,0xba6),(22617,0xad3),(22638,0x8b0),(22660,0x8b7),(22681,0xacd),(22699,0xaeb),(22712,0xbd8),(22722,0x100203a),(22748,0xad1),(22769,0x9f5),(22776,0xbfc),(22805,0x73),(22817,0x1001e61),(22834,0x1b6),(22848,0x1b9),(22864,0x1ba),(22879,0x1000259),(22897,0x2fe),(22909,0xff7e),(22935,0xad7),(22943,0xa7),(22958,0x3b),(22968,0x4df),(23128,0xac6),(23141,0x1002087),(23156,0x1002077),(23197,0xaca),(23211,0xaac),(23223,0x8c9),(23252,0xafd),(23967,0x1002086),(23980,0x1002076),(23992,0x2f),(24014,0x9e0),(24027,0x20),(24033,0x100221a),(24051,0xdf),(24058,0xa3),(24085,0x1002263),(24527,0x74),(24543,0x1001e6b),(24560,0x1bb),(24576,0x1fe),(24585,0xaf9),(24595,0xafa),(25710,0x8c0),(25720,0xaa7),(25742,0xfe),(25748,0xac4),(25761,0xab4),(25773,0xbe),(25787,0x1002083),(25802,0xb3),(25816,0x100222d),(25826,0x8a4),(25838,0x8ab),(25852,0x8a2),(25867,0x8a7),(25884,0x8b1),(25901,0x8ad),(25916,0x8a9),(25934,0x8b5),(25952,0x9f7),(25957,0x8b3),(25991,0xac9),(26001,0xacb),(26026,0x3bc),(26033,0xab3),(26043,0x1002082),(26056,0xb2),(26068,0xab1),(26080,0x75),(26089,0xfa),(26106,0x1001ee5),(26123,0x2fd),(26142,0xfb),(26165,0xfc),(26189,0x1fb),(26209,0xf9),(26222,0x1001ee7),(26234,0x10001b0),(26251,0x1001ee9),(26276,0x1001ef1),(26301,0x1001eeb),(26322,0x1001eed),(26343,0x1001eef),(26560,0x3fe),(26568,0xbc6),(26577,0x5f),(26593,0x8dd),(26607,0x3f9),(26618,0x8fc),(26626,0xba9),(26634,0x9ec),(26647,0x9eb),(26661,0xbc3),(26668,0xbd3),(26676,0xbce),(26689,0x1f9),(26707,0x3fd),(26716,0x76),(26718,0x8c1),(26728,0x9f8),(26736,0x8a6),(26750,0x4de),(26773,0x9e9),(26778,0x77),(26787,0x1001e83),(26806,0x1000175),(26829,0x1001e85),(26847,0x1001e81),(26864,0x78),(26876,0x1001e8b),(32563,0x79),(32572,0xfd),(32589,0x1001ef5),(32611,0x1000177),(32634,0xff),(32645,0xa5),(32656,0x1001ef3),(32669,0x1001ef7),(32682,0x1001ef9),(32691,0x7a),(32703,0x1bf),(32720,0x1bc),(32734,0x1be),(32774,0x1002080),(32788,0x1002070),(32809,0x10001b6)];
@@ -13,7 +13,7 @@ fn get_name_bytes(offset: u32) -> &'static [u8] {
     &rest[..end]
 }
 pub(crate) fn keysym_get_name(ks: u32) -> Option<Cow<'static, str>> {
-    let name = Keysym::new(ks).name()?;
+    let name = XKeysym::new(ks).name()?;
     Some(match name.strip_prefix("XK_") {
         Some(name) => Cow::Borrowed(name),
         None => Cow::Owned(name.replacen("XK_", "", 1)),
@@ -71,7 +71,7 @@ pub(crate) fn xkb_keysym_from_name(name: &[u8], flags: u32) -> Option<u32> {
     None
 }
 pub(crate) fn xkb_keysym_is_keypad(keysym: u32) -> bool {
-    Keysym::new(keysym).is_keypad_key()
+    XKeysym::new(keysym).is_keypad_key()
 }
 pub(crate) const XKB_KEY_XF86_NUMERIC_0: u32 = 0x10081200;
 pub(crate) const XKB_KEY_XF86_NUMERIC_9: u32 = 0x10081209;
@@ -95,7 +95,7 @@ pub fn keysym_to_codepoint(keysym: u32) -> Option<u32> {
     if let Ok(idx) = DEAD_KEYSYM_TO_COMBINING.binary_search_by_key(&keysym, |e| e.0) {
         return Some(DEAD_KEYSYM_TO_COMBINING[idx].1 as u32);
     }
-    Keysym::new(keysym).key_char().map(u32::from)
+    XKeysym::new(keysym).key_char().map(u32::from)
 }
 pub fn codepoint_to_keysym(ucs: u32) -> Option<u32> {
     if ucs == 0 {
@@ -105,7 +105,7 @@ pub fn codepoint_to_keysym(ucs: u32) -> Option<u32> {
     if ucs & 0xfffe == 0xfffe || (0xfdd0..=0xfdef).contains(&ucs) {
         return Some(ucs | XKB_KEYSYM_UNICODE_OFFSET);
     }
-    let ks = Keysym::from_char(ch).raw();
+    let ks = XKeysym::from_char(ch).raw();
     (ks != 0).then_some(ks)
 }
 fn case_char(ks: u32) -> Option<char> {
@@ -160,3 +160,101 @@ pub(crate) fn xkb_keysym_is_upper_or_title(ks: u32) -> bool {
         _ => ch.is_uppercase(),
     })
 }
+
+/// An XKB keysym: the symbolic value a key produces at a given level.
+///
+/// Keysyms are what key bindings, X11 bridges and keymap sources are written
+/// in. Use [`Keysym::from_name`] / [`Keysym::name`] to convert to and from
+/// the standard names (`"a"`, `"Return"`, `"XF86AudioMute"`, `"U20AC"`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Keysym(u32);
+
+impl Keysym {
+    /// The empty keysym (`NoSymbol`).
+    pub const NO_SYMBOL: Keysym = Keysym(0);
+
+    /// Wrap a raw keysym value.
+    #[inline]
+    pub const fn new(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    /// Return the raw keysym value.
+    #[inline]
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
+    /// Parse a keysym name, matching xkbcommon's `xkb_keysym_from_name`
+    /// without flags.
+    ///
+    /// Accepts the standard names, `Uxxxx` Unicode keysyms and `0x` hex
+    /// values. Names are case-sensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mut buf = Vec::with_capacity(name.len() + 1);
+        buf.extend_from_slice(name.as_bytes());
+        buf.push(0);
+        xkb_keysym_from_name(&buf, XKB_KEYSYM_NO_FLAGS).map(Self)
+    }
+
+    /// Return the standard name of this keysym, if it has one.
+    ///
+    /// Unnamed keysyms format through [`std::fmt::Display`] instead.
+    pub fn name(self) -> Option<Cow<'static, str>> {
+        keysym_get_name(self.0)
+    }
+
+    /// Return the keysym producing `ch`: the legacy keysym when one exists,
+    /// otherwise the Unicode keysym.
+    pub fn from_char(ch: char) -> Self {
+        Self(codepoint_to_keysym(ch as u32).unwrap_or(0))
+    }
+
+    /// Return the character this keysym produces, if any.
+    ///
+    /// Dead keys map to their combining characters.
+    pub fn to_char(self) -> Option<char> {
+        keysym_to_char(self.0)
+    }
+
+    /// Return the [`NamedKey`](crate::NamedKey) for this keysym.
+    pub fn named_key(self) -> crate::NamedKey {
+        super::keysym_to_named_key(self.0)
+    }
+
+    /// Return whether this is a keypad keysym (`KP_*`).
+    pub fn is_keypad(self) -> bool {
+        xkb_keysym_is_keypad(self.0)
+    }
+
+    /// Return the upper-case variant of this keysym, or itself.
+    pub fn to_upper(self) -> Self {
+        Self(xkb_keysym_to_upper(self.0))
+    }
+}
+
+impl From<u32> for Keysym {
+    fn from(raw: u32) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Keysym> for u32 {
+    fn from(keysym: Keysym) -> Self {
+        keysym.0
+    }
+}
+
+impl std::fmt::Display for Keysym {
+    /// Formats like `xkb_keysym_get_name`: the standard name, `Uxxxx` for
+    /// Unicode keysyms, or a hexadecimal value.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = self.name() {
+            f.write_str(&name)
+        } else if (0x0100_0100..=0x0110_ffff).contains(&self.0) {
+            write!(f, "U{:04X}", self.0 - XKB_KEYSYM_UNICODE_OFFSET)
+        } else {
+            write!(f, "{:#010x}", self.0)
+        }
+    }
+}
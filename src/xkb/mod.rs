@@ -1,33 +1,61 @@
 //! XKB module — keymap construction from RMLVO names and XKB strings,
 //! plus XKB v1 text serialization.
+pub(crate) mod compat;
 pub(crate) mod compose;
+mod context;
+mod diagnostics;
 pub(crate) mod keymap;
 pub(crate) mod keynames;
 pub(crate) mod keysym;
 mod parse_xkb;
 pub(crate) mod parser;
+mod rules;
 pub(crate) mod serialize;
 pub(crate) mod symbols;
-use crate::flat_keymap::{FlatKeymap, FlatNamedKeyMap, MAX_LEVELS};
+use crate::flat_keymap::{FlatKeymap, FlatKeysymMap, FlatNamedKeyMap, MAX_LEVELS};
+use crate::key_types::{KeyType, KeyTypeEntry, KeyTypes};
 use crate::xkb::keymap::{xkb_context_new, xkb_keymap_new_from_names, xkb_keymap_new_from_string};
-use crate::xkb::parser::{ActionFlags, XkbAction, XkbGroupAction};
-#[cfg(not(feature = "compose"))]
-use crate::Composer;
-use crate::WKB;
+use crate::xkb::parser::{ActionFlags, XkbAction, XkbGroupAction, XkbModAction};
 use crate::{modifiers::*, KBLayout};
+use crate::{Composer, Keymap};
 use crate::{Group, GroupChange, GroupKind, Groups, KeyBitSet};
 use compose::layout_composer;
 pub use compose::{load_compose_from_path, load_compose_from_path_uncached};
+pub use context::Context;
+pub use diagnostics::{Diagnostic, DiagnosticCode, Section};
 pub use keynames::keysym_to_named_key;
+pub use keysym::Keysym;
 use std::collections::BTreeMap;
 #[derive(Debug, thiserror::Error)]
 pub enum XkbError {
     #[error("Failed to create XKB context")]
     ContextCreation,
-    #[error("Failed to compile keymap")]
-    KeymapCompilation,
-    #[error("Failed to parse keymap string")]
-    KeymapParsing,
+    /// The keymap is well-formed but cannot be compiled.
+    #[error("Failed to compile keymap: {0}")]
+    KeymapCompilation(Box<Diagnostic>),
+    /// The keymap is not valid XKB text.
+    #[error("Failed to parse keymap string: {0}")]
+    KeymapParsing(Box<Diagnostic>),
+}
+impl XkbError {
+    /// The diagnostic describing where and why compilation failed.
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            XkbError::ContextCreation => None,
+            XkbError::KeymapCompilation(diagnostic) | XkbError::KeymapParsing(diagnostic) => {
+                Some(diagnostic)
+            }
+        }
+    }
+    fn from_diagnostics(ctx: &mut parser::XkbContext) -> Self {
+        let diagnostic = Box::new(ctx.diagnostics.take_error());
+        match diagnostic.code {
+            DiagnosticCode::InvalidSyntax | DiagnosticCode::InvalidFileEncoding => {
+                XkbError::KeymapParsing(diagnostic)
+            }
+            _ => XkbError::KeymapCompilation(diagnostic),
+        }
+    }
 }
 pub(crate) fn level_code(modifiers: &Modifiers, mod_type: ModType) -> Option<(u32, Option<u8>)> {
     let mut other_mod = None;
@@ -74,7 +102,7 @@ struct CompiledType {
     num_lock_affected: bool,
 }
 impl CompiledType {
-    fn new(xkb_key_type: &parser::XkbKeyType) -> Self {
+    fn new(xkb_key_type: &parser::XkbKeyType, num_mask: u32) -> Self {
         let default_state = CompiledTypeState {
             level: 0,
             consumed_mods: xkb_key_type.mods.mask,
@@ -98,7 +126,7 @@ impl CompiledType {
             num_lock_affected: xkb_key_type
                 .entries
                 .iter()
-                .any(|entry| entry.mods.mask & (1 << 4) != 0),
+                .any(|entry| entry.mods.mask & num_mask != 0),
         }
     }
     #[inline]
@@ -241,20 +269,45 @@ fn build_groups_from_keymap(keymap: &keymap::XkbKeymap) -> Groups {
     }
     Groups::new(entries)
 }
-fn resolve_char(
+fn resolve_sym(
     group: &parser::XkbGroup,
     type_: &CompiledType,
     mods: u32,
     caps_mask: u32,
-) -> Option<char> {
+) -> Option<u32> {
     let state = type_.state(mods);
     let raw_sym = group.levels.get(state.level as usize)?.sym;
-    let sym = if mods & caps_mask != 0 && state.consumed_mods & caps_mask == 0 {
-        keysym::xkb_keysym_to_upper(raw_sym)
-    } else {
-        raw_sym
-    };
-    keysym::keysym_to_char(sym)
+    Some(
+        if mods & caps_mask != 0 && state.consumed_mods & caps_mask == 0 {
+            keysym::xkb_keysym_to_upper(raw_sym)
+        } else {
+            raw_sym
+        },
+    )
+}
+fn resolve_char(
+    group: &parser::XkbGroup,
+    type_: &CompiledType,
+    mods: u32,
+    caps_mask: u32,
+) -> Option<char> {
+    resolve_sym(group, type_, mods, caps_mask).and_then(keysym::keysym_to_char)
+}
+fn runtime_key_type(xkb_key_type: &parser::XkbKeyType) -> KeyType {
+    let real = |mask: u32| (mask & parser::MOD_REAL_MASK_ALL) as u8;
+    KeyType {
+        mask: real(xkb_key_type.mods.mask),
+        entries: xkb_key_type
+            .entries
+            .iter()
+            .filter(|entry| entry.mods.mods == 0 || entry.mods.mask != 0)
+            .map(|entry| KeyTypeEntry {
+                mods: real(entry.mods.mask),
+                level: entry.level as u8,
+                preserve: real(entry.preserve.mask),
+            })
+            .collect(),
+    }
 }
 fn key_affected_by_caps(group: &parser::XkbGroup, num_levels: usize) -> bool {
     let Some(l0_sym) = group
@@ -318,30 +371,24 @@ fn layout_has_level5_activation(
                         .any(|level| matches!(level.sym, 0xfe11 | 0xfe12))
             })
 }
-fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str>) -> WKB {
+fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str>) -> Keymap {
     const EVDEV_OFFSET: u32 = 8;
     let min_keycode = keymap.min_key_code.max(EVDEV_OFFSET);
     let max_keycode = keymap.keys.len().saturating_sub(1) as u32;
     let num_keys = keymap.keys.len().saturating_sub(EVDEV_OFFSET as usize);
     let num_layouts = (keymap.num_groups as usize).max(1);
-    let modifiers = build_modifiers_from_keymap(keymap);
-    let compiled_types: Vec<_> = keymap.types.iter().map(CompiledType::new).collect();
+    let mod_map = build_mod_map(keymap);
+    let modifiers = build_modifiers_from_keymap(keymap, &mod_map);
+    let num_mask = modifiers.masks.num;
+    let compiled_types: Vec<_> = keymap
+        .types
+        .iter()
+        .map(|type_| CompiledType::new(type_, num_mask))
+        .collect();
+    let key_types: Vec<_> = keymap.types.iter().map(runtime_key_type).collect();
     let caps_mask = keymap.mod_get_mask("Lock");
-    let num_mask = keymap.mod_get_mask("Mod2");
     let level2_mask = keymap.mod_get_mask("Shift");
-    let level3_mask = {
-        let m = keymap.mod_get_mask("ISO_Level3_Shift");
-        if m != 0 {
-            m
-        } else {
-            let m = keymap.mod_get_mask("Mode_switch");
-            if m != 0 {
-                m
-            } else {
-                keymap.mod_get_mask("Mod5")
-            }
-        }
-    };
+    let level3_mask = modifiers.masks.level3;
     let level5_mask = {
         let m = keymap.mod_get_mask("ISO_Level5_Shift");
         if m != 0 {
@@ -369,10 +416,7 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
         .map(|locales| locales.split(',').collect())
         .unwrap_or_default();
     #[cfg(feature = "compose")]
-    let env_locale = std::env::var("LC_ALL")
-        .or_else(|_| std::env::var("LC_CTYPE"))
-        .or_else(|_| std::env::var("LANG"))
-        .ok();
+    let env_locale = keymap::env_locale();
     let mut layouts = Vec::with_capacity(num_layouts);
     for layout_idx in 0..num_layouts {
         let layout_level5 = layout_has_level5_activation(keymap, layout_idx, level5_mask);
@@ -383,8 +427,8 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
                 mods
             }
         };
-        let states: [[u32; MAX_LEVELS]; 4] = std::array::from_fn(|kind| {
-            std::array::from_fn(|level| {
+        let states: [[u32; 4]; MAX_LEVELS] = std::array::from_fn(|level| {
+            std::array::from_fn(|kind| {
                 transform(
                     level_masks[level]
                         | (u32::from(kind & 1 != 0 && caps_active[level]) * caps_mask)
@@ -394,6 +438,13 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
         });
         let mut level_exceptions_keymap = FlatKeymap::new(num_keys);
         let mut named_key_map = FlatNamedKeyMap::new(num_keys);
+        let mut keysym_map = FlatKeysymMap::new(num_keys);
+        let mut level_keysym_map = FlatKeysymMap::new(num_keys);
+        let mut level_keysym_lists = Vec::new();
+        let mut layout_types = KeyTypes {
+            types: key_types.clone(),
+            by_key: vec![u16::MAX; num_keys],
+        };
         let mut maps: [FlatKeymap; 4] = std::array::from_fn(|_| FlatKeymap::new(num_keys));
         let mut repeat_keys = KeyBitSet::default();
         for (kc, key) in keymap.keys.iter().enumerate() {
@@ -408,6 +459,11 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
             let raw_group =
                 (!key.groups.is_empty()).then(|| &key.groups[layout_idx % key.groups.len()]);
             let state_group = key.groups.get(layout_idx);
+            if let Some(group) =
+                state_group.filter(|group| (group.type_idx as usize) < key_types.len())
+            {
+                layout_types.by_key[evdev] = group.type_idx as u16;
+            }
             let state_type =
                 state_group.and_then(|group| compiled_types.get(group.type_idx as usize));
             let (caps_affected, num_affected) = state_group
@@ -420,8 +476,15 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
                     ))
                 })
                 .unwrap_or_default();
-            for level in 0..MAX_LEVELS {
+            for (level, level_states) in states.iter().enumerate() {
                 let idx = level * num_keys + evdev;
+                if let Some(data) = raw_group
+                    .and_then(|group| group.levels.get(level))
+                    .filter(|data| data.syms.len() > 1)
+                {
+                    let syms = data.syms.iter().map(|&sym| Keysym::new(sym)).collect();
+                    level_keysym_lists.push(((evdev as u32, level), syms));
+                }
                 if let Some(sym) = raw_group
                     .and_then(|group| group.levels.get(level))
                     .map(|data| data.sym)
@@ -429,6 +492,7 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
                 {
                     if sym != 0 {
                         named_key_map.data[idx] = keysym_to_named_key(sym);
+                        level_keysym_map.data[idx] = Keysym::new(sym);
                     }
                     if let Some(ch) = keysym::keysym_to_char(sym) {
                         level_exceptions_keymap.data[idx] = Some(ch);
@@ -437,7 +501,9 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
                 let (Some(group), Some(type_)) = (state_group, state_type) else {
                     continue;
                 };
-                let base = resolve_char(group, type_, states[0][level], 0);
+                keysym_map.data[idx] =
+                    Keysym::new(resolve_sym(group, type_, level_states[0], 0).unwrap_or(0));
+                let base = resolve_char(group, type_, level_states[0], 0);
                 maps[0].data[idx] = base;
                 for (kind, affected) in [
                     true,
@@ -455,7 +521,7 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
                     let value = resolve_char(
                         group,
                         type_,
-                        states[kind][level],
+                        level_states[kind],
                         u32::from(kind & 1 != 0 && caps_affected) * caps_mask,
                     );
                     if value != base {
@@ -465,34 +531,7 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
             }
         }
         let [state_keymap, caps_lock_keymap, num_lock_keys, caps_num_lock_keys] = maps;
-        #[cfg(feature = "compose")]
-        let composer = {
-            let mut reachable: Vec<char> = state_keymap
-                .data
-                .iter()
-                .chain(&caps_lock_keymap.data)
-                .chain(&num_lock_keys.data)
-                .chain(&caps_num_lock_keys.data)
-                .filter_map(|ch| *ch)
-                .collect();
-            reachable.sort_unstable();
-            reachable.dedup();
-            let compose_locale = locale_hints
-                .get(layout_idx)
-                .copied()
-                .filter(|locale| !locale.is_empty())
-                .or(env_locale.as_deref());
-            compose_locale
-                .and_then(keymap::resolve_compose_file)
-                .map(|subpath| {
-                    let path = std::path::Path::new("/usr/share/X11/locale").join(&subpath);
-                    layout_composer(&path, &reachable)
-                })
-                .unwrap_or_default()
-        };
-        #[cfg(not(feature = "compose"))]
-        let composer = Composer::new();
-        layouts.push(KBLayout {
+        let layout = KBLayout {
             name: keymap
                 .group_names
                 .get(layout_idx)
@@ -501,7 +540,7 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
                 .map(str::to_owned)
                 .unwrap_or_else(|| format!("Layout {layout_idx}")),
             repeat_keys,
-            composer,
+            composer: Composer::new(),
             modifiers: modifiers.clone(),
             state_keymap,
             num_lock_keys,
@@ -509,38 +548,90 @@ fn build_wkb_from_keymap(keymap: &keymap::XkbKeymap, layout_locales: Option<&str
             caps_num_lock_keys,
             level_exceptions_keymap,
             named_key_map,
-        });
+            keysym_map,
+            level_keysym_map,
+            level_keysym_lists,
+            key_types: layout_types,
+        };
+        #[cfg(feature = "compose")]
+        let layout = {
+            let mut layout = layout;
+            // Layout hints only apply when they name a locale with a Compose
+            // file. They are not resolved through `locale.alias`, which maps
+            // layout names such as `de` to legacy 8-bit locales.
+            let compose_locale = locale_hints
+                .get(layout_idx)
+                .copied()
+                .filter(|locale| keymap::compose_dir_file(locale).is_some())
+                .unwrap_or(&env_locale);
+            if let Some(path) = keymap::resolve_compose_file(compose_locale) {
+                layout.composer = layout_composer(&path, compose_locale, &layout.reachable());
+            }
+            layout
+        };
+        layouts.push(layout);
     }
-    WKB {
-        current_layout_idx: 0,
+    Keymap {
         layouts,
         groups,
+        mods: mod_map,
     }
 }
+/// Collect the keymap's modifier names and their real mappings.
+///
+/// Virtual modifiers without an explicit mapping take the real modifiers of
+/// the keys bound to them with `virtualMods`, as xkbcommon derives them, and
+/// otherwise their usual evdev mapping.
+fn build_mod_map(keymap: &keymap::XkbKeymap) -> ModMap {
+    let mods = keymap.mods.mods[..keymap.mods.num_mods as usize]
+        .iter()
+        .enumerate()
+        .map(|(idx, modifier)| {
+            let mapping = if modifier.type_0 & parser::MOD_REAL != 0 {
+                1 << idx
+            } else if modifier.mapping != 0 {
+                modifier.mapping & parser::MOD_REAL_MASK_ALL
+            } else {
+                keymap
+                    .keys
+                    .iter()
+                    .filter(|key| key.vmodmap.unwrap_or(0) & (1 << idx) != 0)
+                    .fold(0, |acc, key| acc | key.modmap)
+            };
+            (keymap.ctx.atom_text(modifier.name).to_owned(), mapping)
+        })
+        .collect();
+    ModMap { mods }.with_default_mappings()
+}
 pub(crate) fn new_from_names(
-    _rules: &str,
-    _model: &str,
+    context: &Context,
+    rules: &str,
+    model: &str,
     layout: &str,
     variant: &str,
     options: Option<&str>,
-) -> Result<WKB, XkbError> {
+) -> Result<(Keymap, Vec<Diagnostic>), XkbError> {
     use parser::XkbRuleNames;
-    let ctx = xkb_context_new();
+    let ctx = xkb_context_new(context);
     let rmlvo = XkbRuleNames {
+        rules: rules.into(),
+        model: model.into(),
         layout: layout.into(),
         variant: variant.into(),
-        options: options.unwrap_or("").into(),
+        options: options.map(Into::into),
     };
-    let keymap = xkb_keymap_new_from_names(ctx, &rmlvo)?;
-    Ok(build_wkb_from_keymap(&keymap, Some(layout)))
+    let mut keymap = xkb_keymap_new_from_names(ctx, &rmlvo)?;
+    let warnings = std::mem::take(&mut keymap.ctx.diagnostics.warnings);
+    Ok((build_wkb_from_keymap(&keymap, Some(layout)), warnings))
 }
-pub(crate) fn new_from_string(string: &str) -> Result<WKB, XkbError> {
-    let ctx = xkb_context_new();
-    if string.as_bytes().contains(&0) {
-        return Err(XkbError::KeymapParsing);
-    }
-    let keymap = xkb_keymap_new_from_string(ctx, string.as_bytes())?;
-    Ok(build_wkb_from_keymap(&keymap, None))
+pub(crate) fn new_from_string(
+    context: &Context,
+    string: &str,
+) -> Result<(Keymap, Vec<Diagnostic>), XkbError> {
+    let ctx = xkb_context_new(context);
+    let mut keymap = xkb_keymap_new_from_string(ctx, string.as_bytes())?;
+    let warnings = std::mem::take(&mut keymap.ctx.diagnostics.warnings);
+    Ok((build_wkb_from_keymap(&keymap, None), warnings))
 }
 fn modtype_from_name(name: &str) -> Option<ModType> {
     match name {
@@ -548,15 +639,53 @@ fn modtype_from_name(name: &str) -> Option<ModType> {
         "ISO_Level3_Shift" | "Mode_switch" | "LevelThree" => Some(ModType::Level3),
         "ISO_Level5_Shift" | "LevelFive" => Some(ModType::Level5),
         "Lock" => Some(ModType::Caps),
-        "Mod2" => Some(ModType::Num),
-        "Mod5" => Some(ModType::Level3),
         "Scroll_Lock" | "ScrollLock" => Some(ModType::Scroll),
         "Control" => Some(ModType::None),
         _ => None,
     }
 }
-fn build_modifiers_from_keymap(keymap: &keymap::XkbKeymap) -> Modifiers {
+fn mod_action_kind(action: XkbAction) -> Option<(ModKind, XkbModAction)> {
+    match action {
+        XkbAction::ModSet(action) => Some((ModKind::Press { pressed: false }, action)),
+        XkbAction::ModLatch(action) => Some((
+            ModKind::Latch {
+                pressed: false,
+                latched: false,
+                locked: 0,
+            },
+            action,
+        )),
+        XkbAction::ModLock(action) => Some((
+            ModKind::Lock {
+                pressed: false,
+                locked: 0,
+            },
+            action,
+        )),
+        _ => None,
+    }
+}
+/// The role of a key that sets `mask` and has no keysym-derived one.
+fn mod_type_from_mask(mask: u32, masks: &ModMasks) -> ModType {
+    if mask & masks.level3 != 0 {
+        ModType::Level3
+    } else if mask & masks.level5 != 0 {
+        ModType::Level5
+    } else if mask & MOD_SHIFT != 0 {
+        ModType::Level2
+    } else if mask == MOD_CAPS_LOCK {
+        ModType::Caps
+    } else if mask == masks.num {
+        ModType::Num
+    } else if mask == masks.scroll {
+        ModType::Scroll
+    } else {
+        ModType::None
+    }
+}
+fn build_modifiers_from_keymap(keymap: &keymap::XkbKeymap, mod_map: &ModMap) -> Modifiers {
     let mut modifiers = Modifiers::new();
+    modifiers.masks = mod_map.masks();
     let keysym_to_modtype = |ks: u32| -> Option<ModType> {
         match ks {
             0xfe03 | 0xfe04 | 0xfe05 | 0xfe0d => Some(ModType::Level3),
@@ -573,17 +702,21 @@ fn build_modifiers_from_keymap(keymap: &keymap::XkbKeymap) -> Modifiers {
                     locked: 0,
                 },
                 mod_type: mt,
+                flags: ModFlags::default(),
             },
             0xfe04 | 0xfe12 => StateModifier {
                 kind: ModKind::Latch {
                     pressed: false,
                     latched: false,
+                    locked: 0,
                 },
                 mod_type: mt,
+                flags: ModFlags::default(),
             },
             _ => StateModifier {
                 kind: ModKind::Press { pressed: false },
                 mod_type: mt,
+                flags: ModFlags::default(),
             },
         }
     };
@@ -619,7 +752,12 @@ fn build_modifiers_from_keymap(keymap: &keymap::XkbKeymap) -> Modifiers {
         }
         for modifier in keymap.mods.mods.iter().take(keymap.mods.num_mods as usize) {
             let mod_mask = modifier.mapping;
-            let named_type = modtype_from_name(keymap.ctx.atom_text(modifier.name));
+            let named_type = modtype_from_name(keymap.ctx.atom_text(modifier.name)).or(
+                match mod_type_from_mask(mod_mask, &modifiers.masks) {
+                    ModType::None => None,
+                    mod_type => Some(mod_type),
+                },
+            );
             if (key.modmap & mod_mask) == 0 && (vmodmap & mod_mask) == 0 {
                 continue;
             }
@@ -645,6 +783,7 @@ fn build_modifiers_from_keymap(keymap: &keymap::XkbKeymap) -> Modifiers {
                                         locked: 0,
                                     },
                                     mod_type: ModType::Caps,
+                                    flags: ModFlags::default(),
                                 },
                             )
                         })
@@ -663,32 +802,119 @@ fn build_modifiers_from_keymap(keymap: &keymap::XkbKeymap) -> Modifiers {
                         locked: 0,
                     },
                     mod_type,
+                    flags: ModFlags::default(),
                 },
                 (_, _) => StateModifier {
                     kind: ModKind::Press { pressed: false },
                     mod_type,
+                    flags: ModFlags::default(),
                 },
             };
             modifiers.set_modifier(evdev_code, Modifier::Single(state_modifier));
         }
     }
+    // Alt and Logo keys carry no level or lock role; track them as plain
+    // modifiers setting whatever real modifiers the keymap maps them to.
+    let shortcut_mask = modifiers.masks.alt | modifiers.masks.logo;
+    for (keycode, key) in keymap.keys.iter().enumerate().skip(EVDEV_OFFSET as usize) {
+        let evdev_code = keycode as u32 - EVDEV_OFFSET;
+        if key.modmap & shortcut_mask != 0 && modifiers.get(evdev_code).is_none() {
+            modifiers.set_modifier(
+                evdev_code,
+                Modifier::Single(StateModifier {
+                    kind: ModKind::Press { pressed: false },
+                    mod_type: ModType::None,
+                    flags: ModFlags::default(),
+                }),
+            );
+        }
+    }
+    modifiers.key_masks = keymap
+        .keys
+        .iter()
+        .enumerate()
+        .skip(EVDEV_OFFSET as usize)
+        .filter(|(_, key)| key.modmap & (MOD_CTRL | shortcut_mask) != 0)
+        .map(|(keycode, key)| (keycode as u32 - EVDEV_OFFSET, key.modmap))
+        .collect();
+    // Modifier actions bound by the compat map decide how a key behaves and
+    // which modifiers it sets, over what its keysym suggests. Modifier keys
+    // act the same on every level here, so keys whose levels bind different
+    // actions keep their keysym-derived behaviour.
+    for (keycode, key) in keymap.keys.iter().enumerate().skip(EVDEV_OFFSET as usize) {
+        let evdev_code = keycode as u32 - EVDEV_OFFSET;
+        let mut actions = key
+            .groups
+            .first()
+            .into_iter()
+            .flat_map(|group| &group.levels)
+            .filter(|level| level.sym != 0)
+            .map(|level| level.action);
+        let Some(Some(base)) = actions.next() else {
+            continue;
+        };
+        if !actions.all(|action| action == Some(base)) {
+            continue;
+        }
+        let Some((kind, action)) = mod_action_kind(base) else {
+            continue;
+        };
+        let mask = keymap::mod_mask_get_effective(&keymap.mods, action.mods);
+        let mod_type = match modifiers.get(evdev_code) {
+            Some(Modifier::Leveled(_)) => continue,
+            Some(Modifier::Single(modifier)) => modifier.mod_type,
+            None => mod_type_from_mask(mask, &modifiers.masks),
+        };
+        if mask == 0 {
+            continue;
+        }
+        modifiers.set_modifier(
+            evdev_code,
+            Modifier::Single(StateModifier {
+                kind,
+                mod_type,
+                flags: ModFlags {
+                    clear_locks: action.flags.contains(ActionFlags::LOCK_CLEAR),
+                    latch_to_lock: action.flags.contains(ActionFlags::LATCH_TO_LOCK),
+                    no_lock: action.flags.contains(ActionFlags::LOCK_NO_LOCK),
+                    no_unlock: action.flags.contains(ActionFlags::LOCK_NO_UNLOCK),
+                },
+            }),
+        );
+        modifiers.key_masks.retain(|(code, _)| *code != evdev_code);
+        modifiers.key_masks.push((evdev_code, mask));
+    }
     for &code in &[LEFT_CTRL, RIGHT_CTRL] {
-        let already_control = modifiers.iter().any(|(c, m)| {
-            *c == code
-                && matches!(
-                    m,
-                    Modifier::Single(StateModifier {
-                        kind: ModKind::Press { .. },
-                        mod_type: ModType::None
-                    }) | Modifier::Leveled(_)
-                )
-        });
+        // Keys the keymap binds to other modifiers keep them, and keys it
+        // gives a plain keysym set no modifier at all.
+        let bound =
+            modifiers.get(code).is_some() && modifiers.key_masks.iter().any(|(c, _)| *c == code);
+        let rebound = keymap
+            .keys
+            .get((code + EVDEV_OFFSET) as usize)
+            .and_then(|key| key.groups.first())
+            .and_then(|group| group.levels.first())
+            .is_some_and(|level| level.sym != 0 && !parser::is_modifier_keysym(level.sym));
+        let already_control = bound
+            || rebound
+            || modifiers.iter().any(|(c, m)| {
+                *c == code
+                    && matches!(
+                        m,
+                        Modifier::Single(StateModifier {
+                            kind: ModKind::Press { .. },
+                            mod_type: ModType::None,
+                            ..
+                        }) | Modifier::Leveled(_)
+                    )
+            });
         if !already_control {
             modifiers.set_modifier(
                 code,
                 Modifier::Single(StateModifier {
                     kind: ModKind::Press { pressed: false },
                     mod_type: ModType::None,
+                    flags: ModFlags::default(),
                 }),
             );
         }
@@ -1,3 +1,4 @@
+use super::diagnostics::DiagnosticCode;
 use super::keysym::xkb_keysym_from_name;
 use super::parser::*;
 use crate::xkb::keysym::codepoint_to_keysym;
@@ -47,10 +48,16 @@ enum Token<'a> {
 struct Lexer<'a> {
     input: &'a [u8],
     pos: usize,
+    /// Start of the last token returned by [`Lexer::next`].
+    start: usize,
 }
 impl<'a> Lexer<'a> {
     fn new(input: &'a [u8]) -> Self {
-        Self { input, pos: 0 }
+        Self {
+            input,
+            pos: 0,
+            start: 0,
+        }
     }
     fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a [u8] {
         let start = self.pos;
@@ -78,6 +85,7 @@ impl<'a> Lexer<'a> {
             }
             break;
         }
+        self.start = self.pos;
         let Some(&byte) = self.input.get(self.pos) else {
             return Token::End;
         };
@@ -156,6 +164,8 @@ impl<'a> Lexer<'a> {
 struct Parser<'a> {
     lexer: Lexer<'a>,
     token: Token<'a>,
+    /// Set when a statement wkb does not model was skipped on purpose.
+    skipped: bool,
 }
 struct MapSpan {
     name: String,
@@ -178,16 +188,25 @@ impl OwnedMap {
     pub(crate) fn stream(&self) -> Stream<'_> {
         Stream::new(&self.data[self.body.clone()])
     }
+    pub(crate) fn data(&self) -> &Arc<Vec<u8>> {
+        &self.data
+    }
 }
 pub(crate) struct Stream<'a> {
     parser: Parser<'a>,
+    start: usize,
 }
 impl<'a> Stream<'a> {
     pub(crate) fn new(input: &'a [u8]) -> Self {
         Self {
             parser: Parser::new(input),
+            start: 0,
         }
     }
+    /// Start of the statement or variable returned last.
+    pub(crate) fn statement_start(&self) -> *const u8 {
+        self.parser.lexer.input[self.start..].as_ptr()
+    }
     pub(crate) fn next_statement(
         &mut self,
         ctx: &mut XkbContext,
@@ -197,34 +216,57 @@ impl<'a> Stream<'a> {
                 return Ok(None);
             }
             if matches!(self.parser.token, Token::Error) {
-                return Err(());
+                return self.parser.syntax_error(ctx);
             }
             let pos = self.parser.lexer.pos;
+            self.start = self.parser.lexer.start;
+            self.parser.skipped = false;
             if let Some(statement) = self.parser.parse_statement(ctx) {
+                if matches!(statement, Statement::Unknown) {
+                    self.ignored(ctx);
+                }
                 return Ok(Some(statement));
             }
             if self.parser.lexer.pos == pos {
-                return Err(());
+                return self.parser.syntax_error(ctx);
+            }
+            if !self.parser.skipped {
+                self.ignored(ctx);
             }
         }
     }
+    fn ignored(&self, ctx: &mut XkbContext) {
+        let mark = ctx.diagnostics.mark(Some(self.statement_start()));
+        ctx.diagnostics.warn(
+            DiagnosticCode::IgnoredStatement,
+            mark,
+            "ignoring malformed statement",
+        );
+    }
     pub(crate) fn next_var(&mut self, ctx: &mut XkbContext) -> Result<Option<VarDef>, ()> {
         if matches!(self.parser.token, Token::End) {
             return Ok(None);
         }
         if matches!(self.parser.token, Token::Error) {
-            return Err(());
+            return self.parser.syntax_error(ctx);
+        }
+        self.start = self.parser.lexer.start;
+        match self.parser.parse_body_var(ctx) {
+            Some(var) => Ok(Some(var)),
+            None => self.parser.syntax_error(ctx),
         }
-        self.parser.parse_body_var(ctx).map(Some).ok_or(())
     }
-    pub(crate) fn next_map(&mut self) -> Result<Option<SelectedMap<'a>>, ()> {
+    pub(crate) fn next_map(&mut self, ctx: &mut XkbContext) -> Result<Option<SelectedMap<'a>>, ()> {
         if matches!(self.parser.token, Token::End) {
             return Ok(None);
         }
         if matches!(self.parser.token, Token::Error) {
-            return Err(());
+            return self.parser.syntax_error(ctx);
         }
-        let span = self.parser.scan_file().ok_or(())?;
+        self.start = self.parser.lexer.start;
+        let Some(span) = self.parser.scan_file() else {
+            return self.parser.syntax_error(ctx);
+        };
         Ok(Some(SelectedMap {
             file_type: span.file_type,
             body: &self.parser.lexer.input[span.body],
@@ -235,7 +277,26 @@ impl<'a> Parser<'a> {
     fn new(input: &'a [u8]) -> Self {
         let mut lexer = Lexer::new(input);
         let token = lexer.next();
-        Self { lexer, token }
+        Self {
+            lexer,
+            token,
+            skipped: false,
+        }
+    }
+    /// Where the current token starts.
+    fn token_start(&self) -> *const u8 {
+        self.lexer.input[self.lexer.start..].as_ptr()
+    }
+    /// Report a syntax error at the current token.
+    fn syntax_error<T>(&self, ctx: &mut XkbContext) -> Result<T, ()> {
+        let mark = ctx.diagnostics.mark(Some(self.token_start()));
+        let message = match self.token {
+            Token::End => "syntax error: incomplete statement",
+            _ => "syntax error",
+        };
+        ctx.diagnostics
+            .error(DiagnosticCode::InvalidSyntax, mark, message);
+        Err(())
     }
     fn bump(&mut self) -> Token<'a> {
         let next = self.lexer.next();
@@ -275,11 +336,11 @@ impl<'a> Parser<'a> {
             word if word.eq_ignore_ascii_case(b"xkb_compat")
                 || word.eq_ignore_ascii_case(b"xkb_compat_map")
                 || word.eq_ignore_ascii_case(b"xkb_compatibility")
-                || word.eq_ignore_ascii_case(b"xkb_compatibility_map")
-                || word.eq_ignore_ascii_case(b"xkb_geometry") =>
+                || word.eq_ignore_ascii_case(b"xkb_compatibility_map") =>
             {
-                FileType::Ignored
+                FileType::Compat
             }
+            word if word.eq_ignore_ascii_case(b"xkb_geometry") => FileType::Ignored,
             _ => return None,
         })
     }
@@ -346,8 +407,12 @@ impl<'a> Parser<'a> {
     }
     fn parse_statement(&mut self, ctx: &mut XkbContext) -> Option<Statement<'a>> {
         let merge = self.merge();
-        if self.word(b"include") {
-            self.bump();
+        // `augment "file"` is shorthand for an include with that merge mode.
+        let bare_include = merge != MergeMode::Default && matches!(self.token, Token::String(_));
+        if bare_include || self.word(b"include") {
+            if !bare_include {
+                self.bump();
+            }
             let Token::String(value) = self.bump() else {
                 return None;
             };
@@ -398,6 +463,26 @@ impl<'a> Parser<'a> {
             let name = self.parse_lhs_with(ctx, b"key")?;
             return self.parse_variable(ctx, merge, name);
         }
+        if self.word(b"interpret") {
+            self.bump();
+            if matches!(self.token, Token::Punct(b'.')) {
+                let name = self.parse_lhs_with(ctx, b"interpret")?;
+                return self.parse_variable(ctx, merge, name);
+            }
+            let sym = self.parse_keysym(ctx)?;
+            let predicate = if self.punct(b'+') {
+                Some(self.parse_expr(ctx, 0)?)
+            } else {
+                None
+            };
+            let body = self.take_body()?;
+            return Some(Statement::Interpret(InterpretDef {
+                merge,
+                sym,
+                predicate,
+                body,
+            }));
+        }
         if self.word(b"modifier_map") || self.word(b"modmap") || self.word(b"mod_map") {
             self.bump();
             let modifier_word = self.take_word()?;
@@ -443,15 +528,16 @@ impl<'a> Parser<'a> {
             self.punct(b';').then_some(())?;
             return Some(Statement::Keycode(KeycodeDef { merge, name, value }));
         }
-        if self.word(b"indicator") || self.word(b"virtual") || self.word(b"interpret") {
+        if self.word(b"indicator") || self.word(b"virtual") || self.word(b"group") {
             self.skip_statement();
+            self.skipped = true;
             return None;
         }
         let name = if self.punct(b'!') || self.punct(b'~') {
             let word = self.take_word()?;
             let atom = Self::atom(ctx, word);
             self.punct(b';').then_some(())?;
-            return Some(Statement::Var(VarDef {
+            return Some(Statement::Var(Box::new(VarDef {
                 merge,
                 name: Some(Lhs {
                     element: Element::None,
@@ -459,7 +545,7 @@ impl<'a> Parser<'a> {
                     index: None,
                 }),
                 value: Some(scalar(Scalar::Integer(0))),
-            }));
+            })));
         } else {
             self.parse_lhs(ctx)?
         };
@@ -479,11 +565,11 @@ impl<'a> Parser<'a> {
             self.punct(b';').then_some(())?;
             value
         };
-        Some(Statement::Var(VarDef {
+        Some(Statement::Var(Box::new(VarDef {
             merge,
             name: Some(name),
             value,
-        }))
+        })))
     }
     fn skip_statement(&mut self) -> Option<Statement<'a>> {
         let mut depth = 0;
@@ -571,6 +657,10 @@ impl<'a> Parser<'a> {
             Element::Key
         } else if word.eq_ignore_ascii_case(b"type") {
             Element::Type
+        } else if word.eq_ignore_ascii_case(b"interpret") {
+            Element::Interpret
+        } else if let Some(action) = ActionType::from_name(word) {
+            Element::Action(action)
         } else {
             Element::Other
         }
@@ -588,6 +678,11 @@ impl<'a> Parser<'a> {
             (LevelName, &[b"levelname", b"level_name"]),
             (Map, &[b"map"]),
             (Preserve, &[b"preserve"]),
+            (Action, &[b"action"]),
+            (VirtualModifier, &[b"virtualmodifier", b"virtualmod"]),
+            (UseModMapMods, &[b"usemodmapmods", b"usemodmap"]),
+            (ClearLocks, &[b"clearlocks"]),
+            (LatchToLock, &[b"latchtolock"]),
             (
                 Ignored,
                 &[
@@ -670,8 +765,7 @@ impl<'a> Parser<'a> {
             Token::Word(word) => {
                 let first = Self::atom(ctx, word);
                 if self.punct(b'(') {
-                    self.skip_call()?;
-                    Some(ExprKind::Actions)
+                    self.parse_call(ctx, first)
                 } else {
                     Some(scalar(Scalar::Ident(first)))
                 }
@@ -687,32 +781,82 @@ impl<'a> Parser<'a> {
             _ => None,
         }
     }
+    fn parse_call(&mut self, ctx: &mut XkbContext, name: u32) -> Option<ExprKind> {
+        let mut args = Vec::new();
+        while !self.punct(b')') {
+            let arg = if self.punct(b'!') || self.punct(b'~') {
+                let field = self.take_word()?;
+                ActionArg {
+                    field: Some(Self::atom(ctx, field)),
+                    value: scalar(Scalar::Integer(0)),
+                    signed: false,
+                }
+            } else {
+                let signed = matches!(self.token, Token::Punct(b'+' | b'-'));
+                let value = self.parse_expr(ctx, 0)?;
+                // Array fields such as `data[0]=` only occur in actions wkb
+                // does not model, so the index is dropped.
+                if self.punct(b'[') {
+                    self.parse_expr(ctx, 0)?;
+                    self.punct(b']').then_some(())?;
+                }
+                if self.punct(b'=') {
+                    let ExprKind::Scalar(ScalarExpr { terms, .. }) = value else {
+                        return None;
+                    };
+                    let Some(&(true, Scalar::Ident(field))) = terms.first() else {
+                        return None;
+                    };
+                    let signed = matches!(self.token, Token::Punct(b'+' | b'-'));
+                    ActionArg {
+                        field: Some(field),
+                        value: self.parse_expr(ctx, 0)?,
+                        signed,
+                    }
+                } else {
+                    ActionArg {
+                        field: None,
+                        value,
+                        signed,
+                    }
+                }
+            };
+            args.push(arg);
+            if !self.punct(b',') {
+                self.punct(b')').then_some(())?;
+                break;
+            }
+        }
+        Some(ExprKind::Action(ActionCall { name, args }))
+    }
     fn parse_list(&mut self, ctx: &mut XkbContext) -> Option<ExprKind> {
         self.punct(b'[').then_some(())?;
         self.parse_list_after_open(ctx)
     }
-    fn parse_list_after_open(&mut self, _ctx: &mut XkbContext) -> Option<ExprKind> {
+    fn parse_list_after_open(&mut self, ctx: &mut XkbContext) -> Option<ExprKind> {
         if self.punct(b']') {
-            return Some(ExprKind::Symbols(Vec::new()));
+            return Some(ExprKind::Symbols(Vec::new(), Vec::new()));
         }
         let mut items = Vec::new();
+        let mut multi = Vec::new();
         let mut actions = false;
         loop {
             let item = if self.punct(b'{') {
-                let mut first_sym = None;
+                let mut syms = Vec::new();
                 while !self.punct(b'}') {
-                    let next = self.parse_keysym()?;
-                    if first_sym.is_none() {
-                        first_sym = Some(next);
-                    }
+                    syms.push(self.parse_keysym(ctx)?);
                     if !self.punct(b',') {
                         self.punct(b'}').then_some(())?;
                         break;
                     }
                 }
-                first_sym.unwrap_or(0)
+                let first = syms.first().copied().unwrap_or(0);
+                if syms.len() > 1 {
+                    multi.push((items.len(), syms));
+                }
+                first
             } else if matches!(self.token, Token::Word(_)) {
-                let saved = self.lexer.pos;
+                let (saved, saved_start) = (self.lexer.pos, self.lexer.start);
                 let word = match self.bump() {
                     Token::Word(word) => word,
                     _ => unreachable!(),
@@ -723,11 +867,12 @@ impl<'a> Parser<'a> {
                     0
                 } else {
                     self.lexer.pos = saved;
+                    self.lexer.start = saved_start;
                     self.token = Token::Word(word);
-                    self.parse_keysym()?
+                    self.parse_keysym(ctx)?
                 }
             } else {
-                self.parse_keysym()?
+                self.parse_keysym(ctx)?
             };
             items.push(item);
             if !self.punct(b',') {
@@ -738,18 +883,26 @@ impl<'a> Parser<'a> {
         Some(if actions {
             ExprKind::Actions
         } else {
-            ExprKind::Symbols(items)
+            ExprKind::Symbols(items, multi)
         })
     }
     fn parse_keysym_expr(&mut self, ctx: &mut XkbContext) -> Option<ExprKind> {
         match self.token {
             Token::Key(_) => self.parse_primary(ctx),
-            _ => Some(ExprKind::KeySym(self.parse_keysym()?)),
+            _ => Some(ExprKind::KeySym(self.parse_keysym(ctx)?)),
         }
     }
-    fn parse_keysym(&mut self) -> Option<u32> {
+    fn parse_keysym(&mut self, ctx: &mut XkbContext) -> Option<u32> {
+        let start = self.token_start();
         Some(match self.bump() {
-            Token::Word(word) => resolve_keysym(word).unwrap_or(XKB_KEY_NO_SYMBOL),
+            Token::Word(word) => resolve_keysym(word).unwrap_or_else(|| {
+                let mark = ctx.diagnostics.mark(Some(start));
+                let name = String::from_utf8_lossy(word);
+                let message = format!("unrecognized keysym `{name}`");
+                ctx.diagnostics
+                    .warn(DiagnosticCode::UnknownKeysym, mark, message);
+                XKB_KEY_NO_SYMBOL
+            }),
             Token::String(value) => std::str::from_utf8(value)
                 .ok()?
                 .chars()
@@ -841,36 +994,61 @@ fn valid_input(input: &[u8]) -> Option<&[u8]> {
     let input = input.strip_prefix(&[0xef, 0xbb, 0xbf]).unwrap_or(input);
     (input.len() < 2 || input[0].is_ascii() && input[0] != 0 && input[1] != 0).then_some(input)
 }
-fn select_span(input: &[u8], wanted: &str) -> Option<MapSpan> {
-    let input = valid_input(input)?;
+/// Find the map named `wanted`, or the default one. On failure, returns where
+/// the syntax error is, if there is one.
+fn select_span(input: &[u8], wanted: &str) -> Result<MapSpan, Option<*const u8>> {
+    let input = valid_input(input).ok_or(None)?;
     let mut parser = Parser::new(input);
     let mut first = None;
     while !matches!(parser.token, Token::End | Token::Error) {
-        let span = parser.scan_file()?;
+        let Some(span) = parser.scan_file() else {
+            return Err(Some(parser.token_start()));
+        };
         if span.file_type == FileType::Ignored {
             continue;
         }
         if !wanted.is_empty() && span.name == wanted
             || wanted.is_empty() && span.flags & MAP_IS_DEFAULT != 0
         {
-            return Some(span);
+            return Ok(span);
         }
         if first.is_none() {
             first = Some(span);
         }
     }
-    first
+    first.ok_or(None)
 }
-pub(crate) fn xkb_select_map<'a>(input: &'a [u8], wanted: &str) -> Option<SelectedMap<'a>> {
-    let input = valid_input(input)?;
-    let span = select_span(input, wanted)?;
-    Some(SelectedMap {
-        file_type: span.file_type,
-        body: &input[span.body],
-    })
+pub(crate) fn xkb_select_map<'a>(
+    ctx: &mut XkbContext,
+    input: &'a [u8],
+    wanted: &str,
+) -> Option<SelectedMap<'a>> {
+    let Some(input) = valid_input(input) else {
+        let mark = ctx.diagnostics.mark(Some(input.as_ptr()));
+        let message = "keymap is not ASCII-compatible text";
+        ctx.diagnostics
+            .error(DiagnosticCode::InvalidFileEncoding, mark, message);
+        return None;
+    };
+    match select_span(input, wanted) {
+        Ok(span) => Some(SelectedMap {
+            file_type: span.file_type,
+            body: &input[span.body],
+        }),
+        Err(at) => {
+            let mark = ctx.diagnostics.mark(at);
+            let message = match at {
+                Some(_) => "syntax error",
+                None => "no keymap found in input",
+            };
+            ctx.diagnostics
+                .error(DiagnosticCode::InvalidSyntax, mark, message);
+            None
+        }
+    }
 }
 pub(crate) fn xkb_select_owned(data: Arc<Vec<u8>>, wanted: &str) -> Option<OwnedMap> {
-    let span = select_span(data.as_slice(), wanted)?;
+    let span = select_span(data.as_slice(), wanted).ok()?;
     Some(OwnedMap {
         data,
         body: span.body,
@@ -878,3 +1056,10 @@ pub(crate) fn xkb_select_owned(data: Arc<Vec<u8>>, wanted: &str) -> Option<Owned
         flags: span.flags,
     })
 }
+/// Text of the token starting at `input`, for diagnostics.
+pub(crate) fn token_text(input: &[u8]) -> Option<String> {
+    let mut lexer = Lexer::new(input);
+    lexer.next();
+    let text = &input[lexer.start..lexer.pos];
+    (!text.is_empty()).then(|| String::from_utf8_lossy(text).into_owned())
+}
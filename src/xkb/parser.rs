@@ -1,3 +1,6 @@
+use super::compat::{apply_interprets, compile_compat};
+use super::context::{Context, MEMORY_ROOT};
+use super::diagnostics::{DiagnosticCode, Diagnostics, Section};
 use super::keymap::mod_mask_get_effective;
 pub(crate) use super::parse_xkb::{
     include_create, xkb_select_map, xkb_select_owned, OwnedMap, SelectedMap, Stream,
@@ -11,6 +14,7 @@ fn directory_for_include(type_0: FileType) -> &'static str {
         FileType::Keycodes => "keycodes",
         FileType::Types => "types",
         FileType::Symbols => "symbols",
+        FileType::Compat => "compat",
         _ => "",
     }
 }
@@ -19,22 +23,30 @@ pub(crate) fn find_file_in_xkb_path(
     name: &str,
     type_0: FileType,
     offset: &mut u32,
-) -> Option<Arc<Vec<u8>>> {
+) -> Option<(String, Arc<Vec<u8>>)> {
     let type_dir = directory_for_include(type_0);
-    let path_count = ctx.includes.len() as u32;
-    for i in *offset..path_count {
-        let path = format!("{}/{}/{}", ctx.includes[i as usize], type_dir, name);
+    let roots = ctx.context.roots();
+    for i in *offset..roots.len() as u32 {
+        let path = format!("{}/{}/{}", roots[i as usize], type_dir, name);
         if let Some(data) = ctx.read_file(&path) {
             *offset = i;
-            return Some(data);
+            return Some((path, data));
         }
     }
 
     None
 }
-pub(crate) fn exceeds_include_max_depth(include_depth: u32) -> bool {
-    include_depth >= INCLUDE_MAX_DEPTH as u32
+pub(crate) fn exceeds_include_max_depth(ctx: &mut XkbContext, include_depth: u32) -> bool {
+    let exceeds = include_depth >= INCLUDE_MAX_DEPTH as u32;
+    if exceeds {
+        let message = format!("includes nest deeper than {INCLUDE_MAX_DEPTH} levels");
+        ctx.diagnostics
+            .fail(DiagnosticCode::RecursiveInclude, None, message);
+    }
+    exceeds
 }
+/// Find and select the map an include statement names. On success the file
+/// becomes the current diagnostics source; leave it once it is compiled.
 pub(crate) fn process_include_stream(
     ctx: &mut XkbContext,
     stmt: &IncludeStmt,
@@ -42,24 +54,53 @@ pub(crate) fn process_include_stream(
 ) -> Option<OwnedMap> {
     let mut offset = 0;
     let mut candidate = None;
+    let mut found = false;
     loop {
         let data = if stmt.file.starts_with('/') {
-            (offset == 0).then(|| ctx.read_file(&stmt.file)).flatten()
+            (offset == 0)
+                .then(|| ctx.read_file(&stmt.file))
+                .flatten()
+                .map(|data| (stmt.file.clone(), data))
         } else {
             find_file_in_xkb_path(ctx, &stmt.file, file_type, &mut offset)
         };
-        let Some(data) = data else { break };
+        let Some((path, data)) = data else { break };
+        found = true;
         if let Some(parsed) = xkb_select_owned(data, &stmt.map) {
             if parsed.file_type == file_type {
                 if !stmt.map.is_empty() || parsed.flags != 0 {
+                    ctx.diagnostics.enter(Some(path), parsed.data().clone());
                     return Some(parsed);
                 }
-                candidate.get_or_insert(parsed);
+                candidate.get_or_insert((path, parsed));
             }
         }
         offset += 1;
     }
-    candidate
+    let Some((path, parsed)) = candidate else {
+        let dir = directory_for_include(file_type);
+        let message = if found {
+            format!(
+                "cannot find map \"{}\" in {dir} file \"{}\"",
+                stmt.map, stmt.file
+            )
+        } else {
+            format!(
+                "cannot find {dir} file \"{}\" in the include paths",
+                stmt.file
+            )
+        };
+        let token = if stmt.map.is_empty() {
+            stmt.file.clone()
+        } else {
+            format!("{}({})", stmt.file, stmt.map)
+        };
+        ctx.diagnostics
+            .fail(DiagnosticCode::IncludedFileNotFound, Some(&token), message);
+        return None;
+    };
+    ctx.diagnostics.enter(Some(path), parsed.data().clone());
+    Some(parsed)
 }
 #[inline]
 fn compute_effective_mask_with(mod_set: &XkbModSet, mods: &mut XkbMods) {
@@ -96,7 +137,7 @@ pub(crate) fn wkb_group_action(sym: u32) -> Option<XkbAction> {
         _ => None,
     }
 }
-fn is_modifier_keysym(sym: u32) -> bool {
+pub(crate) fn is_modifier_keysym(sym: u32) -> bool {
     matches!(sym, 0xff2d..=0xff30 | 0xff7e | 0xff7f | 0xffe1..=0xffee | 0xfe01..=0xfe13)
 }
 fn apply_wkb_compat(keymap: &mut XkbKeymap) {
@@ -154,6 +195,7 @@ fn update_derived_keymap_fields(info: &mut XkbKeymap) -> bool {
         .fold(keymap.num_groups, |max, key| {
             max.max(key.groups.len() as u32)
         });
+    apply_interprets(info);
     apply_wkb_compat(info);
     update_mod_mappings(info);
     compute_type_entry_masks(info);
@@ -200,6 +242,7 @@ fn compute_type_entry_masks(info: &mut XkbKeymap) {
 pub(crate) fn compile_components(
     keycodes: &[u8],
     types: &[u8],
+    compat: &[u8],
     symbols: &[u8],
     keymap: &mut XkbKeymap,
 ) -> bool {
@@ -208,24 +251,35 @@ pub(crate) fn compile_components(
             .ok()
             .and_then(|input| include_create(input, MergeMode::Default))
     };
-    let (Some(mut keycodes), Some(mut types), Some(mut symbols)) =
-        (parse(keycodes), parse(types), parse(symbols))
+    let (Some(mut keycodes), Some(mut types), Some(mut compat), Some(mut symbols)) =
+        (parse(keycodes), parse(types), parse(compat), parse(symbols))
     else {
-        return false;
+        keymap.ctx.diagnostics.section = Section::Rules;
+        let message = "rules produced an invalid component name";
+        return keymap
+            .ctx
+            .diagnostics
+            .fail(DiagnosticCode::UnresolvedRmlvo, None, message);
     };
     compile_keycodes(CompileInput::Includes(&mut keycodes), keymap)
         && compile_key_types(CompileInput::Includes(&mut types), keymap)
+        && compile_compat(CompileInput::Includes(&mut compat), keymap)
         && compile_symbols(CompileInput::Includes(&mut symbols), keymap)
         && update_derived_keymap_fields(keymap)
 }
 pub(crate) fn compile_keymap_stream(file: SelectedMap<'_>, keymap: &mut XkbKeymap) -> bool {
     if file.file_type != FileType::Keymap {
-        return false;
+        keymap.ctx.diagnostics.set_statement(file.body.as_ptr());
+        let message = "expected an xkb_keymap block";
+        return keymap
+            .ctx
+            .diagnostics
+            .fail(DiagnosticCode::WrongStatementType, None, message);
     }
-    let mut parts: [Option<SelectedMap<'_>>; 3] = [None, None, None];
+    let mut parts: [Option<SelectedMap<'_>>; 4] = [None, None, None, None];
     let mut maps = Stream::new(file.body);
     loop {
-        let map = match maps.next_map() {
+        let map = match maps.next_map(&mut keymap.ctx) {
             Ok(Some(map)) => map,
             Ok(None) => break,
             Err(()) => return false,
@@ -238,8 +292,10 @@ pub(crate) fn compile_keymap_stream(file: SelectedMap<'_>, keymap: &mut XkbKeyma
     let mut keycodes = parts[0].as_ref().map(|map| Stream::new(map.body));
     let mut types = parts[1].as_ref().map(|map| Stream::new(map.body));
     let mut symbols = parts[2].as_ref().map(|map| Stream::new(map.body));
+    let mut compat = parts[3].as_ref().map(|map| Stream::new(map.body));
     compile_keycodes(CompileInput::Stream(keycodes.as_mut()), keymap)
         && compile_key_types(CompileInput::Stream(types.as_mut()), keymap)
+        && compile_compat(CompileInput::Stream(compat.as_mut()), keymap)
         && compile_symbols(CompileInput::Stream(symbols.as_mut()), keymap)
         && update_derived_keymap_fields(keymap)
 }
@@ -247,15 +303,18 @@ use lasso::Key as _;
 use std::collections::HashMap;
 #[derive(Clone, Debug, Default)]
 pub(crate) struct XkbRuleNames {
+    pub(crate) rules: String,
+    pub(crate) model: String,
     pub(crate) layout: String,
     pub(crate) variant: String,
-    pub(crate) options: String,
+    pub(crate) options: Option<String>,
 }
 #[derive(Clone)]
 pub(crate) struct XkbContext {
-    pub(crate) includes: Vec<String>,
+    pub(crate) context: Context,
     pub(crate) atom_table: lasso::Rodeo,
     pub(crate) files: HashMap<String, Arc<Vec<u8>>>,
+    pub(crate) diagnostics: Diagnostics,
 }
 impl XkbContext {
     pub(crate) fn atom_text(&self, atom: u32) -> &str {
@@ -273,6 +332,9 @@ impl XkbContext {
         if let Some(data) = self.files.get(path) {
             return Some(data.clone());
         }
+        if path.starts_with(MEMORY_ROOT) {
+            return self.context.memory_file(path);
+        }
         let data = Arc::new(std::fs::read(path).ok()?);
         self.files.insert(path.to_owned(), data.clone());
         Some(data)
@@ -289,6 +351,7 @@ pub(crate) struct XkbKeymap {
     pub(crate) mods: XkbModSet,
     pub(crate) num_groups: u32,
     pub(crate) group_names: Vec<u32>,
+    pub(crate) interprets: Vec<XkbInterpret>,
 }
 impl XkbKeymap {
     pub(crate) fn mod_get_mask(&self, name: &str) -> u32 {
@@ -324,18 +387,74 @@ pub(crate) enum XkbAction {
     GroupSet(XkbGroupAction),
     GroupLatch(XkbGroupAction),
     GroupLock(XkbGroupAction),
+    ModSet(XkbModAction),
+    ModLatch(XkbModAction),
+    ModLock(XkbModAction),
 }
 bitflags::bitflags! {
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub struct ActionFlags: u32 {
     const LOCK_CLEAR            = 1;
     const LATCH_TO_LOCK         = 2;
+    const LOCK_NO_LOCK          = 4;
+    const LOCK_NO_UNLOCK        = 8;
+    const MODS_LOOKUP_MODMAP    = 16;
     const ABSOLUTE_SWITCH       = 32; } }
 #[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub struct XkbGroupAction {
     pub flags: ActionFlags,
     pub group: i32,
 }
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct XkbModAction {
+    pub flags: ActionFlags,
+    pub mods: u32,
+}
+/// The action statements wkb models; any other action compiles to
+/// [`XkbAction::None`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ActionType {
+    ModSet,
+    ModLatch,
+    ModLock,
+    GroupSet,
+    GroupLatch,
+    GroupLock,
+}
+impl ActionType {
+    pub(crate) fn from_name(name: &[u8]) -> Option<Self> {
+        const NAMES: [(ActionType, &[u8]); 6] = [
+            (ActionType::ModSet, b"setmods"),
+            (ActionType::ModLatch, b"latchmods"),
+            (ActionType::ModLock, b"lockmods"),
+            (ActionType::GroupSet, b"setgroup"),
+            (ActionType::GroupLatch, b"latchgroup"),
+            (ActionType::GroupLock, b"lockgroup"),
+        ];
+        NAMES
+            .iter()
+            .find(|(_, action)| name.eq_ignore_ascii_case(action))
+            .map(|(action, _)| *action)
+    }
+}
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MatchOperation {
+    NoneOf,
+    AnyOfOrNone,
+    AnyOf,
+    AllOf,
+    Exactly,
+}
+/// A compiled `interpret` statement of the compat map.
+#[derive(Copy, Clone)]
+pub(crate) struct XkbInterpret {
+    pub(crate) sym: u32,
+    pub(crate) match_op: MatchOperation,
+    pub(crate) mods: u32,
+    pub(crate) level_one_only: bool,
+    pub(crate) virtual_mod: Option<u32>,
+    pub(crate) action: XkbAction,
+}
 #[derive(Copy, Clone, Default)]
 pub(crate) struct XkbMods {
     pub(crate) mods: u32,
@@ -373,11 +492,16 @@ pub(crate) struct XkbGroup {
 #[derive(Clone, Default)]
 pub(crate) struct XkbLevel {
     pub(crate) sym: u32,
+    /// Every keysym when the level has more than one, starting with `sym`.
+    pub(crate) syms: Vec<u32>,
     pub(crate) action: Option<XkbAction>,
 }
 pub(crate) const XKB_MAX_GROUPS: u32 = 32;
 pub(crate) const MOD_REAL_MASK_ALL: u32 = 0xff_i32 as u32;
 pub(crate) const DFLT_XKB_LEGACY_ROOT: &str = "/usr/share/X11/xkb";
+pub(crate) const DFLT_XKB_CONFIG_EXTRA_PATH: &str = "/etc/xkb";
+pub(crate) const DEFAULT_XKB_RULES: &str = "evdev";
+pub(crate) const DEFAULT_XKB_MODEL: &str = "pc105";
 pub(crate) const XKB_KEYSYM_NO_FLAGS: u32 = 0;
 pub(crate) const XKB_KEYSYM_MAX: u32 = 0x1fffffff;
 pub(crate) const XKB_ATOM_NONE: u32 = 0;
@@ -418,6 +542,7 @@ pub(crate) enum FileType {
     Keycodes = 0,
     Types = 1,
     Symbols = 2,
+    Compat = 3,
     #[default]
     Keymap,
     Ignored,
@@ -451,14 +576,32 @@ pub(crate) enum ExprKind {
     KeyName(u32),
     KeySym(u32),
     Scalar(ScalarExpr),
-    Symbols(Vec<u32>),
+    /// The first keysym of each level, then every keysym of the levels
+    /// written as `{ a, b }`, by level index.
+    Symbols(Vec<u32>, Vec<(usize, Vec<u32>)>),
     Actions,
+    Action(ActionCall),
+}
+/// A call such as `SetMods(modifiers=Shift,clearLocks)` or `AnyOf(all)`.
+pub(crate) struct ActionCall {
+    pub(crate) name: u32,
+    pub(crate) args: Vec<ActionArg>,
+}
+/// One argument of an [`ActionCall`]: `field=value`, `!field` (stored as
+/// `field=0`) or a bare expression. `signed` records an explicit leading `+`
+/// or `-`, which makes group values relative.
+pub(crate) struct ActionArg {
+    pub(crate) field: Option<u32>,
+    pub(crate) value: ExprKind,
+    pub(crate) signed: bool,
 }
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Element {
     None,
     Key,
     Type,
+    Interpret,
+    Action(ActionType),
     Other,
 }
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -473,6 +616,11 @@ pub(crate) enum Field {
     LevelName,
     Map,
     Preserve,
+    Action,
+    VirtualModifier,
+    UseModMapMods,
+    ClearLocks,
+    LatchToLock,
     Ignored,
     Other,
 }
@@ -507,6 +655,12 @@ pub(crate) struct NamedVarDef<'a> {
     pub(crate) name: u32,
     pub(crate) body: &'a [u8],
 }
+pub(crate) struct InterpretDef<'a> {
+    pub(crate) merge: MergeMode,
+    pub(crate) sym: u32,
+    pub(crate) predicate: Option<ExprKind>,
+    pub(crate) body: &'a [u8],
+}
 pub(crate) struct ModMapDef {
     pub(crate) merge: MergeMode,
     pub(crate) modifier: u32,
@@ -517,11 +671,12 @@ pub(crate) enum Statement<'a> {
     Include(Vec<IncludeStmt>),
     Keycode(KeycodeDef),
     KeyAlias(KeyAliasDef),
-    Var(VarDef),
+    Var(Box<VarDef>),
     VMods(Vec<VModDef>),
     KeyType(NamedVarDef<'a>),
     Symbols(NamedVarDef<'a>),
     ModMap(ModMapDef),
+    Interpret(InterpretDef<'a>),
     Unknown,
 }
 pub(crate) fn parse_hex_u32(s: &[u8]) -> (u32, i32) {
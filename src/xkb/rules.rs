@@ -0,0 +1,625 @@
+//! RMLVO resolution: matching rules, models, layouts, variants and options
+//! against an XKB rules file to get the keycodes, types, compat and symbols
+//! components, following xkbcommon's `rules.c`.
+use super::diagnostics::{DiagnosticCode, Section};
+use super::keymap::getenv_or;
+use super::parser::{XkbContext, XkbRuleNames, DFLT_XKB_CONFIG_EXTRA_PATH, DFLT_XKB_LEGACY_ROOT};
+
+/// Rules files can include each other up to this depth.
+const MAX_INCLUDE_DEPTH: u32 = 5;
+
+/// The KcCGST component strings a rules file resolves RMLVO names to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct XkbComponentNames {
+    pub(crate) keycodes: String,
+    pub(crate) types: String,
+    pub(crate) compat: String,
+    pub(crate) symbols: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mlvo {
+    Model,
+    Option,
+    Layout,
+    Variant,
+}
+
+const MLVO_NAMES: [(&str, Mlvo); 4] = [
+    ("model", Mlvo::Model),
+    ("option", Mlvo::Option),
+    ("layout", Mlvo::Layout),
+    ("variant", Mlvo::Variant),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kccgst {
+    Keycodes,
+    Types,
+    Compat,
+    Symbols,
+    Geometry,
+}
+
+const KCCGST_NAMES: [(&str, Kccgst); 5] = [
+    ("keycodes", Kccgst::Keycodes),
+    ("types", Kccgst::Types),
+    ("compat", Kccgst::Compat),
+    ("symbols", Kccgst::Symbols),
+    ("geometry", Kccgst::Geometry),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Bang,
+    Equals,
+    Star,
+    GroupName(&'a str),
+    Include,
+    Identifier(&'a str),
+    EndOfLine,
+    EndOfFile,
+    Error,
+}
+
+/// Characters allowed in identifiers: printable ASCII except `\`.
+fn is_ident(ch: u8) -> bool {
+    ch.is_ascii_graphic() && ch != b'\\'
+}
+
+struct Scanner<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+    fn chr(&mut self, ch: u8) -> bool {
+        let found = self.peek() == Some(ch);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+    fn lit(&mut self, lit: &str) -> bool {
+        let found = self.s[self.pos..].starts_with(lit.as_bytes());
+        if found {
+            self.pos += lit.len();
+        }
+        found
+    }
+    fn eol(&self) -> bool {
+        self.peek() == Some(b'\n')
+    }
+    fn ident(&mut self) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(is_ident) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.s[start..self.pos]).unwrap_or_default()
+    }
+    fn next_token(&mut self) -> Token<'a> {
+        loop {
+            while matches!(self.peek(), Some(b' ' | b'\t' | b'\r')) {
+                self.pos += 1;
+            }
+            if self.lit("//") {
+                while self.peek().is_some_and(|ch| ch != b'\n') {
+                    self.pos += 1;
+                }
+            }
+            if self.eol() {
+                while self.eol() {
+                    self.pos += 1;
+                }
+                return Token::EndOfLine;
+            }
+            // A backslash at the end of a line continues it.
+            if self.chr(b'\\') {
+                self.chr(b'\r');
+                if !self.chr(b'\n') {
+                    return Token::Error;
+                }
+                continue;
+            }
+            break;
+        }
+        let Some(ch) = self.peek() else {
+            return Token::EndOfFile;
+        };
+        match ch {
+            b'!' | b'=' | b'*' => {
+                self.pos += 1;
+                match ch {
+                    b'!' => Token::Bang,
+                    b'=' => Token::Equals,
+                    _ => Token::Star,
+                }
+            }
+            b'$' => {
+                self.pos += 1;
+                match self.ident() {
+                    "" => Token::Error,
+                    name => Token::GroupName(name),
+                }
+            }
+            _ if self.lit("include") => Token::Include,
+            _ if is_ident(ch) => Token::Identifier(self.ident()),
+            _ => Token::Error,
+        }
+    }
+}
+
+/// Parse a `[N]` group index, returning the zero-based index and the length
+/// of the index text.
+fn extract_layout_index(s: &[u8]) -> Option<(usize, usize)> {
+    match s {
+        [b'[', digit @ b'1'..=b'9', b']', ..] => Some(((digit - b'1') as usize, 3)),
+        _ => None,
+    }
+}
+
+/// Split a comma separated RMLVO value, always yielding at least one value.
+fn split_comma_separated_mlv(s: &str) -> Vec<String> {
+    s.split(',').map(Into::into).collect()
+}
+
+/// The value `%l[N]`/`%v[N]` expands to: indexed forms only expand with
+/// several layouts, plain ones only with one.
+fn indexed_value(values: &[String], idx: Option<usize>) -> Option<&str> {
+    match idx {
+        Some(idx) if values.len() > 1 => values.get(idx).map(String::as_str),
+        None if values.len() == 1 => Some(values[0].as_str()),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MatchType<'a> {
+    Normal(&'a str),
+    Wildcard,
+    Group(&'a str),
+}
+
+/// The `! mlvo... = kccgst...` header introducing a set of rules.
+#[derive(Default)]
+struct Mapping {
+    mlvo: Vec<Mlvo>,
+    kccgst: Vec<Kccgst>,
+    layout_idx: Option<usize>,
+    variant_idx: Option<usize>,
+    skip: bool,
+}
+
+#[derive(Default)]
+struct Rule<'a> {
+    mlvo_values: Vec<MatchType<'a>>,
+    kccgst_values: Vec<&'a str>,
+    skip: bool,
+}
+
+struct Matcher {
+    model: String,
+    layouts: Vec<String>,
+    variants: Vec<String>,
+    options: Vec<String>,
+    groups: Vec<(String, Vec<String>)>,
+    mapping: Mapping,
+    kccgst: [String; 5],
+}
+
+impl Matcher {
+    fn new(rmlvo: &XkbRuleNames) -> Self {
+        Self {
+            model: rmlvo.model.clone(),
+            layouts: split_comma_separated_mlv(&rmlvo.layout),
+            variants: split_comma_separated_mlv(&rmlvo.variant),
+            options: split_comma_separated_mlv(rmlvo.options.as_deref().unwrap_or("")),
+            groups: Vec::new(),
+            mapping: Mapping::default(),
+            kccgst: Default::default(),
+        }
+    }
+
+    fn mapping_set_mlvo(&mut self, ident: &str) {
+        let Some(&(name, mlvo)) = MLVO_NAMES.iter().find(|(name, _)| ident.starts_with(name))
+        else {
+            self.mapping.skip = true;
+            return;
+        };
+        if self.mapping.mlvo.contains(&mlvo) {
+            self.mapping.skip = true;
+            return;
+        }
+        // Leftovers can only be a group index.
+        let rest = &ident.as_bytes()[name.len()..];
+        if !rest.is_empty() {
+            let idx = match extract_layout_index(rest) {
+                Some((idx, consumed)) if consumed == rest.len() => idx,
+                _ => {
+                    self.mapping.skip = true;
+                    return;
+                }
+            };
+            match mlvo {
+                Mlvo::Layout => self.mapping.layout_idx = Some(idx),
+                Mlvo::Variant => self.mapping.variant_idx = Some(idx),
+                _ => {
+                    self.mapping.skip = true;
+                    return;
+                }
+            }
+        }
+        self.mapping.mlvo.push(mlvo);
+    }
+
+    fn mapping_set_kccgst(&mut self, ident: &str) {
+        match KCCGST_NAMES.iter().find(|(name, _)| *name == ident) {
+            Some(&(_, kccgst)) if !self.mapping.kccgst.contains(&kccgst) => {
+                self.mapping.kccgst.push(kccgst)
+            }
+            _ => self.mapping.skip = true,
+        }
+    }
+
+    /// Like xkbcomp, a mapping on `layout` only applies to a single layout,
+    /// and one on `layout[N]` only when there are several.
+    fn mapping_verify(&mut self) {
+        let applies = |defined: bool, idx: Option<usize>, count: usize| {
+            !defined
+                || match idx {
+                    None => count <= 1,
+                    Some(idx) => count > 1 && idx < count,
+                }
+        };
+        let mapping = &self.mapping;
+        self.mapping.skip = mapping.mlvo.is_empty()
+            || mapping.kccgst.is_empty()
+            || !applies(
+                mapping.mlvo.contains(&Mlvo::Layout),
+                mapping.layout_idx,
+                self.layouts.len(),
+            )
+            || !applies(
+                mapping.mlvo.contains(&Mlvo::Variant),
+                mapping.variant_idx,
+                self.variants.len(),
+            );
+    }
+
+    fn match_group(&self, group_name: &str, to: &str) -> bool {
+        // rules/evdev refers to some groups it leaves commented out, so an
+        // unknown group just does not match.
+        self.groups
+            .iter()
+            .find(|(name, _)| name == group_name)
+            .is_some_and(|(_, elements)| elements.iter().any(|element| element == to))
+    }
+
+    fn match_value(&self, match_type: MatchType, to: &str) -> bool {
+        match match_type {
+            MatchType::Normal(value) => value == to,
+            MatchType::Wildcard => true,
+            MatchType::Group(group) => self.match_group(group, to),
+        }
+    }
+
+    fn rule_apply_if_matches(&mut self, rule: &Rule) {
+        for (&mlvo, &match_type) in self.mapping.mlvo.iter().zip(&rule.mlvo_values) {
+            let to = match mlvo {
+                Mlvo::Model => Some(&self.model),
+                Mlvo::Layout => self.layouts.get(self.mapping.layout_idx.unwrap_or(0)),
+                Mlvo::Variant => self.variants.get(self.mapping.variant_idx.unwrap_or(0)),
+                Mlvo::Option => None,
+            };
+            let matched = match to {
+                Some(to) => self.match_value(match_type, to),
+                None => self
+                    .options
+                    .iter()
+                    .any(|option| self.match_value(match_type, option)),
+            };
+            if !matched {
+                return;
+            }
+        }
+        for (&kccgst, value) in self.mapping.kccgst.iter().zip(&rule.kccgst_values) {
+            if let Some(expanded) = self.expand_kccgst_value(value) {
+                append_kccgst_value(&mut self.kccgst[kccgst as usize], &expanded);
+            }
+        }
+        // Only the first matching rule of a set applies, except in sets
+        // matching options, which may hold several legitimate rules.
+        if !self.mapping.mlvo.contains(&Mlvo::Option) {
+            self.mapping.skip = true;
+        }
+    }
+
+    /// Expand `%m`, `%l`, `%v`, their `%l[N]` indexed forms and the `%+l`,
+    /// `%|l`, `%_l`, `%-l` and `%(l)` decorated forms, which only add their
+    /// decoration around a non-empty value.
+    fn expand_kccgst_value(&self, value: &str) -> Option<String> {
+        let bytes = value.as_bytes();
+        let mut expanded = String::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'%' {
+                expanded.push(bytes[i] as char);
+                i += 1;
+                continue;
+            }
+            i += 1;
+            let (mut pfx, mut sfx) = (None, None);
+            if let Some(&ch @ (b'(' | b'+' | b'|' | b'_' | b'-')) = bytes.get(i) {
+                pfx = Some(ch as char);
+                if ch == b'(' {
+                    sfx = Some(')');
+                }
+                i += 1;
+            }
+            let mlv = match bytes.get(i)? {
+                b'm' => Mlvo::Model,
+                b'l' => Mlvo::Layout,
+                b'v' => Mlvo::Variant,
+                _ => return None,
+            };
+            i += 1;
+            let mut idx = None;
+            if bytes.get(i) == Some(&b'[') {
+                if mlv == Mlvo::Model {
+                    return None;
+                }
+                let (index, consumed) = extract_layout_index(&bytes[i..])?;
+                idx = Some(index);
+                i += consumed;
+            }
+            if let Some(sfx) = sfx {
+                if bytes.get(i).map(|&ch| ch as char) != Some(sfx) {
+                    return None;
+                }
+                i += 1;
+            }
+            let value = match mlv {
+                Mlvo::Model => Some(self.model.as_str()),
+                Mlvo::Layout => indexed_value(&self.layouts, idx),
+                Mlvo::Variant => indexed_value(&self.variants, idx),
+                Mlvo::Option => None,
+            };
+            let Some(value) = value.filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            expanded.extend(pfx);
+            expanded.push_str(value);
+            expanded.extend(sfx);
+        }
+        Some(expanded)
+    }
+
+    fn include(&mut self, ctx: &mut XkbContext, include_depth: u32, inc: &str) {
+        if include_depth >= MAX_INCLUDE_DEPTH {
+            return;
+        }
+        let mut path = String::new();
+        let mut chars = inc.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                path.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('%') => path.push('%'),
+                Some('H') => match std::env::var("HOME") {
+                    Ok(home) => path.push_str(&home),
+                    Err(_) => return,
+                },
+                Some('S') => {
+                    path.push_str(&getenv_or("XKB_CONFIG_ROOT", DFLT_XKB_LEGACY_ROOT));
+                    path.push_str("/rules");
+                }
+                Some('E') => {
+                    path.push_str(&getenv_or(
+                        "XKB_CONFIG_EXTRA_PATH",
+                        DFLT_XKB_CONFIG_EXTRA_PATH,
+                    ));
+                    path.push_str("/rules");
+                }
+                _ => return,
+            }
+        }
+        if let Some(data) = ctx.read_file(&path) {
+            self.read_rules_file(ctx, include_depth + 1, &data);
+        }
+    }
+
+    fn read_rules_file(&mut self, ctx: &mut XkbContext, include_depth: u32, data: &[u8]) -> bool {
+        #[derive(Clone, Copy)]
+        enum State {
+            Initial,
+            Bang,
+            GroupName,
+            GroupElement,
+            IncludeStatement,
+            MappingMlvo,
+            MappingKccgst,
+            RuleMlvoFirst,
+            RuleMlvo,
+            RuleKccgst,
+        }
+        let mut s = Scanner { s: data, pos: 0 };
+        let mut state = State::Initial;
+        let mut rule = Rule::default();
+        loop {
+            let tok = s.next_token();
+            state = match (state, tok) {
+                (_, Token::Error) => return false,
+                (State::Initial | State::RuleMlvoFirst, Token::Bang) => State::Bang,
+                (State::Initial | State::RuleMlvoFirst, Token::EndOfLine) => state,
+                (State::Initial | State::RuleMlvoFirst, Token::EndOfFile) => return true,
+                (State::Bang, Token::GroupName(name)) => {
+                    self.groups.push((name.into(), Vec::new()));
+                    State::GroupName
+                }
+                (State::Bang, Token::Include) => State::IncludeStatement,
+                (State::Bang, Token::Identifier(ident)) => {
+                    self.mapping = Mapping::default();
+                    self.mapping_set_mlvo(ident);
+                    State::MappingMlvo
+                }
+                (State::GroupName, Token::Equals) => State::GroupElement,
+                (State::GroupElement, Token::Identifier(element)) => {
+                    if let Some((_, elements)) = self.groups.last_mut() {
+                        elements.push(element.into());
+                    }
+                    State::GroupElement
+                }
+                (State::GroupElement, Token::EndOfLine) => State::Initial,
+                (State::IncludeStatement, Token::Identifier(inc)) => {
+                    self.include(ctx, include_depth, inc);
+                    State::Initial
+                }
+                (State::MappingMlvo, Token::Identifier(ident)) => {
+                    if !self.mapping.skip {
+                        self.mapping_set_mlvo(ident);
+                    }
+                    State::MappingMlvo
+                }
+                (State::MappingMlvo, Token::Equals) => State::MappingKccgst,
+                (State::MappingKccgst, Token::Identifier(ident)) => {
+                    if !self.mapping.skip {
+                        self.mapping_set_kccgst(ident);
+                    }
+                    State::MappingKccgst
+                }
+                (State::MappingKccgst, Token::EndOfLine) => {
+                    if !self.mapping.skip {
+                        self.mapping_verify();
+                    }
+                    State::RuleMlvoFirst
+                }
+                (
+                    State::RuleMlvoFirst | State::RuleMlvo,
+                    Token::Identifier(_) | Token::Star | Token::GroupName(_),
+                ) => {
+                    if matches!(state, State::RuleMlvoFirst) {
+                        rule = Rule {
+                            skip: self.mapping.skip,
+                            ..Rule::default()
+                        };
+                    }
+                    let match_type = match tok {
+                        Token::Identifier(value) => MatchType::Normal(value),
+                        Token::GroupName(group) => MatchType::Group(group),
+                        _ => MatchType::Wildcard,
+                    };
+                    if !rule.skip {
+                        if rule.mlvo_values.len() < self.mapping.mlvo.len() {
+                            rule.mlvo_values.push(match_type);
+                        } else {
+                            rule.skip = true;
+                        }
+                    }
+                    State::RuleMlvo
+                }
+                (State::RuleMlvoFirst, Token::Equals) => {
+                    rule = Rule::default();
+                    State::RuleKccgst
+                }
+                (State::RuleMlvo, Token::Equals) => State::RuleKccgst,
+                (State::RuleKccgst, Token::Identifier(value)) => {
+                    if !rule.skip {
+                        if rule.kccgst_values.len() < self.mapping.kccgst.len() {
+                            rule.kccgst_values.push(value);
+                        } else {
+                            rule.skip = true;
+                        }
+                    }
+                    State::RuleKccgst
+                }
+                (State::RuleKccgst, Token::EndOfLine) => {
+                    let complete = rule.mlvo_values.len() == self.mapping.mlvo.len()
+                        && rule.kccgst_values.len() == self.mapping.kccgst.len();
+                    if !rule.skip && complete {
+                        self.rule_apply_if_matches(&rule);
+                    }
+                    State::RuleMlvoFirst
+                }
+                _ => return false,
+            };
+        }
+    }
+}
+
+/// Append an expanded rule value to a component, the way xkbcomp does:
+///
+/// - `bar` to `foo` gives `foo`
+/// - `+bar` to `foo` gives `foo+bar`
+/// - `bar` to `+foo` gives `bar+foo`
+/// - `+bar` to `+foo` gives `+foo+bar`
+fn append_kccgst_value(to: &mut String, expanded: &str) {
+    let is_plus = |s: &str| s.starts_with(['+', '|']);
+    if is_plus(expanded) || to.is_empty() {
+        to.push_str(expanded);
+    } else if is_plus(to) {
+        to.insert_str(0, expanded);
+    }
+}
+
+/// Resolve `rmlvo` to keymap components with the rules file it names,
+/// looked up under `rules/` in the context's include paths.
+pub(crate) fn xkb_components_from_rules(
+    ctx: &mut XkbContext,
+    rmlvo: &XkbRuleNames,
+) -> Option<XkbComponentNames> {
+    ctx.diagnostics.section = Section::Rules;
+    let paths: Vec<String> = ctx
+        .context
+        .roots()
+        .iter()
+        .map(|include| format!("{include}/rules/{}", rmlvo.rules))
+        .collect();
+    let Some((path, data)) = paths
+        .iter()
+        .find_map(|path| Some((path, ctx.read_file(path)?)))
+    else {
+        let message = format!("cannot find rules file \"{}\"", rmlvo.rules);
+        let rules = rmlvo.rules.as_str();
+        ctx.diagnostics
+            .fail(DiagnosticCode::RulesFileNotFound, Some(rules), message);
+        return None;
+    };
+    ctx.diagnostics.enter(Some(path.clone()), data.clone());
+    let mut matcher = Matcher::new(rmlvo);
+    if !matcher.read_rules_file(ctx, 0, &data) {
+        let message = "invalid rules file";
+        ctx.diagnostics
+            .fail(DiagnosticCode::InvalidSyntax, None, message);
+        return None;
+    }
+    let [keycodes, types, compat, symbols, _geometry] = matcher.kccgst;
+    let missing = [
+        ("keycodes", &keycodes),
+        ("types", &types),
+        ("compat", &compat),
+        ("symbols", &symbols),
+    ]
+    .into_iter()
+    .find(|(_, component)| component.is_empty());
+    if let Some((name, _)) = missing {
+        let message = format!(
+            "rules resolve no {name} for model \"{}\", layout \"{}\", variant \"{}\"",
+            rmlvo.model, rmlvo.layout, rmlvo.variant
+        );
+        ctx.diagnostics
+            .fail(DiagnosticCode::UnresolvedRmlvo, None, message);
+        return None;
+    }
+    ctx.diagnostics.leave();
+    Some(XkbComponentNames {
+        keycodes,
+        types,
+        compat,
+        symbols,
+    })
+}
@@ -4,7 +4,7 @@ use super::keysym::keysym_get_name;
 use crate::flat_keymap::MAX_LEVELS;
 use crate::modifiers::{ModKind, ModType, Modifier, StateModifier};
 use crate::named_keys::NamedKey;
-use crate::{KBLayout, WKB};
+use crate::{KBLayout, Keymap, Keysym};
 use std::fmt::Write;
 fn max_level(layout: &KBLayout, key: u32) -> usize {
     (0..MAX_LEVELS)
@@ -62,7 +62,7 @@ fn sym_name(sym: u32) -> String {
             .unwrap_or_else(|| format!("{sym:#010x}"))
     }
 }
-impl WKB {
+impl Keymap {
     fn resolve_keysym(&self, layout: usize, level: usize, key: u32) -> u32 {
         let layout = &self.layouts[layout];
         let named = named_key_to_keysym(layout.named_key_map.get(level, key));
@@ -88,17 +88,35 @@ impl WKB {
         }
         0
     }
+    /// Number of evdev keycodes the serialized keymap covers, up to XKB
+    /// keycode 255.
+    fn serialized_keys(&self) -> u32 {
+        self.layouts
+            .first()
+            .map_or(0, |layout| layout.named_key_map.num_keys.min(248)) as u32
+    }
+    fn key_used(&self, key: u32) -> bool {
+        self.layouts
+            .iter()
+            .any(|layout| max_level(layout, key) != 0)
+    }
+    /// Evdev keycodes with no symbols in any layout that a serialized
+    /// keymap can still define, highest first.
+    pub(crate) fn unused_keys(&self) -> Vec<u32> {
+        (1..self.serialized_keys())
+            .rev()
+            .filter(|&key| !self.key_used(key))
+            .collect()
+    }
     pub(crate) fn generate_xkb_string(&self) -> String {
+        self.generate_xkb_string_with_keys(&[])
+    }
+    /// Serialize the keymap with `extra` one-level keys added, each an
+    /// unused evdev keycode and its keysym.
+    pub(crate) fn generate_xkb_string_with_keys(&self, extra: &[(u32, Keysym)]) -> String {
         let layouts = self.layouts.len();
-        let keys = self
-            .layouts
-            .first()
-            .map_or(0, |layout| layout.named_key_map.num_keys.min(248)) as u32;
-        let used = |key| {
-            self.layouts
-                .iter()
-                .any(|layout| max_level(layout, key) != 0)
-        };
+        let keys = self.serialized_keys();
+        let used = |key| self.key_used(key) || extra.iter().any(|&(code, _)| code == key);
         let mut out = String::with_capacity(40 * 1024);
         out.push_str("xkb_keymap {\nxkb_keycodes \"wkb\" {\n\tminimum = 8;\n");
         writeln!(out, "\tmaximum = {};", keys + 7).unwrap();
@@ -112,7 +130,7 @@ impl WKB {
         for (index, layout) in self.layouts.iter().enumerate() {
             writeln!(out, "\tname[{}]= {:?};", index + 1, layout.name).unwrap();
         }
-        for key in (0..keys).filter(|&key| used(key)) {
+        for key in (0..keys).filter(|&key| self.key_used(key)) {
             let levels = self
                 .layouts
                 .iter()
@@ -146,9 +164,39 @@ impl WKB {
             }
             out.push_str("\n\t};\n");
         }
-        out.push_str("\tmodifier_map Shift { <I050>, <I062> };\n\tmodifier_map Lock { <I066> };\n\tmodifier_map Control { <I037>, <I105> };\n\tmodifier_map Mod1 { <I064> };\n\tmodifier_map Mod2 { <I077> };\n\tmodifier_map Mod4 { <I133> };\n\tmodifier_map Mod5 { <I092> };\n};\n\n};\n");
+        for &(key, sym) in extra {
+            writeln!(
+                out,
+                "\tkey <{}> {{\n\t\ttype[group1]= \"ONE_LEVEL\",\n\t\tsymbols[group1]= [ {} ]\n\t}};",
+                evdev_to_keyname(key),
+                sym_name(sym.raw())
+            )
+            .unwrap();
+        }
+        for (name, syms) in MODIFIER_MAP {
+            let keys: Vec<String> = (0..keys)
+                .filter(|&key| self.key_used(key))
+                .filter(|&key| syms.contains(&self.resolve_keysym(0, 0, key)))
+                .map(|key| format!("<{}>", evdev_to_keyname(key)))
+                .collect();
+            if !keys.is_empty() {
+                writeln!(out, "\tmodifier_map {name} {{ {} }};", keys.join(", ")).unwrap();
+            }
+        }
+        out.push_str("};\n\n};\n");
         out
     }
 }
+/// Real modifiers of the first layout's modifier keysyms, so remapped
+/// modifier keys keep their modifier.
+const MODIFIER_MAP: [(&str, &[u32]); 7] = [
+    ("Shift", &[0xffe1, 0xffe2]),
+    ("Lock", &[0xffe5]),
+    ("Control", &[0xffe3, 0xffe4]),
+    ("Mod1", &[0xffe9]),
+    ("Mod2", &[0xff7f]),
+    ("Mod4", &[0xffeb]),
+    ("Mod5", &[0xfe03]),
+];
 const TYPES_XKB: &str = "xkb_types \"wkb\" {\n\tvirtual_modifiers NumLock,Alt,LevelThree,LevelFive;\n\n\ttype \"ONE_LEVEL\" {\n\t\tmodifiers= none;\n\t\tlevel_name[Level1]= \"Any\";\n\t};\n\ttype \"TWO_LEVEL\" {\n\t\tmodifiers= Shift;\n\t\tmap[Shift]= Level2;\n\t\tlevel_name[Level1]= \"Base\";\n\t\tlevel_name[Level2]= \"Shift\";\n\t};\n\ttype \"ALPHABETIC\" {\n\t\tmodifiers= Shift+Lock;\n\t\tmap[Shift]= Level2;\n\t\tmap[Lock]= Level2;\n\t\tlevel_name[Level1]= \"Base\";\n\t\tlevel_name[Level2]= \"Shift\";\n\t};\n\ttype \"FOUR_LEVEL\" {\n\t\tmodifiers= Shift+LevelThree;\n\t\tmap[Shift]= Level2;\n\t\tmap[LevelThree]= Level3;\n\t\tmap[Shift+LevelThree]= Level4;\n\t\tlevel_name[Level1]= \"Base\";\n\t\tlevel_name[Level2]= \"Shift\";\n\t\tlevel_name[Level3]= \"Alt Base\";\n\t\tlevel_name[Level4]= \"Shift Alt\";\n\t};\n\ttype \"FOUR_LEVEL_SEMIALPHABETIC\" {\n\t\tmodifiers= Shift+Lock+LevelThree;\n\t\tmap[Shift]= Level2;\n\t\tmap[Lock]= Level2;\n\t\tmap[LevelThree]= Level3;\n\t\tmap[Shift+LevelThree]= Level4;\n\t\tmap[Lock+LevelThree]= Level3;\n\t\tmap[Shift+Lock+LevelThree]= Level4;\n\t\tpreserve[Lock+LevelThree]= Lock;\n\t\tpreserve[Shift+Lock+LevelThree]= Lock;\n\t\tlevel_name[Level1]= \"Base\";\n\t\tlevel_name[Level2]= \"Shift\";\n\t\tlevel_name[Level3]= \"Alt Base\";\n\t\tlevel_name[Level4]= \"Shift Alt\";\n\t};\n\ttype \"EIGHT_LEVEL\" {\n\t\tmodifiers= Shift+LevelThree+LevelFive;\n\t\tmap[Shift]= Level2;\n\t\tmap[LevelThree]= Level3;\n\t\tmap[Shift+LevelThree]= Level4;\n\t\tmap[LevelFive]= Level5;\n\t\tmap[Shift+LevelFive]= Level6;\n\t\tmap[LevelThree+LevelFive]= Level7;\n\t\tmap[Shift+LevelThree+LevelFive]= Level8;\n\t\tlevel_name[Level1]= \"Base\";\n\t\tlevel_name[Level2]= \"Shift\";\n\t\tlevel_name[Level3]= \"Alt Base\";\n\t\tlevel_name[Level4]= \"Shift Alt\";\n\t\tlevel_name[Level5]= \"X1\";\n\t\tlevel_name[Level6]= \"X2\";\n\t\tlevel_name[Level7]= \"X3\";\n\t\tlevel_name[Level8]= \"X4\";\n\t};\n};\n";
 const COMPAT_XKB: &str = "xkb_compat \"wkb\" {\n\tvirtual_modifiers NumLock,Alt,LevelThree,LevelFive;\n\n\tinterpret Any+AnyOf(all) {\n\t\taction= SetMods(modifiers=modMapMods,clearLocks);\n\t};\n\tinterpret Shift_L+AnyOf(all) {\n\t\taction= SetMods(modifiers=Shift,clearLocks);\n\t};\n\tinterpret Shift_R+AnyOf(all) {\n\t\taction= SetMods(modifiers=Shift,clearLocks);\n\t};\n\tinterpret Caps_Lock+AnyOf(all) {\n\t\taction= LockMods(modifiers=Lock);\n\t};\n\tinterpret Num_Lock+AnyOf(all) {\n\t\taction= LockMods(modifiers=NumLock);\n\t};\n\tinterpret Control_L+AnyOf(all) {\n\t\taction= SetMods(modifiers=Control,clearLocks);\n\t};\n\tinterpret Control_R+AnyOf(all) {\n\t\taction= SetMods(modifiers=Control,clearLocks);\n\t};\n\tinterpret Alt_L+AnyOf(all) {\n\t\taction= SetMods(modifiers=Alt,clearLocks);\n\t};\n\tinterpret Super_L+AnyOf(all) {\n\t\taction= SetMods(modifiers=Mod4,clearLocks);\n\t};\n\tinterpret Super_R+AnyOf(all) {\n\t\taction= SetMods(modifiers=Mod4,clearLocks);\n\t};\n\tinterpret ISO_Level3_Shift+AnyOf(all) {\n\t\taction= SetMods(modifiers=LevelThree,clearLocks);\n\t};\n\tinterpret Scroll_Lock+AnyOf(all) {\n\t\taction= LockMods(modifiers=Mod3);\n\t};\n\n\tindicator \"Caps Lock\" {\n\t\tmodifiers= Lock;\n\t};\n\tindicator \"Num Lock\" {\n\t\tmodifiers= NumLock;\n\t};\n\tindicator \"Scroll Lock\" {\n\t\tmodifiers= Mod3;\n\t};\n};\n";
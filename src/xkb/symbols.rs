@@ -1,3 +1,4 @@
+use super::diagnostics::{DiagnosticCode, Section};
 pub(crate) use super::keymap::xkb_mod_name_to_index;
 use super::keysym::xkb_keysym_is_keypad;
 use super::keysym::{xkb_keysym_is_lower, xkb_keysym_is_upper_or_title};
@@ -45,14 +46,24 @@ pub(crate) enum CompileInput<'a, 'src> {
     Stream(Option<&'a mut Stream<'src>>),
     Includes(&'a mut [IncludeStmt]),
 }
-fn compile_stream<T>(
+pub(crate) fn compile_stream<T>(
     keymap: &mut XkbKeymap,
     state: &mut T,
     stream: &mut Stream<'_>,
     mut compile: impl FnMut(&mut XkbKeymap, &mut T, &mut Statement<'_>) -> bool,
 ) -> bool {
     while let Ok(Some(mut statement)) = stream.next_statement(&mut keymap.ctx) {
+        keymap
+            .ctx
+            .diagnostics
+            .set_statement(stream.statement_start());
         if !compile(keymap, state, &mut statement) {
+            let mark = keymap.ctx.diagnostics.mark(Some(stream.statement_start()));
+            let message = format!("invalid {} statement", keymap.ctx.diagnostics.section);
+            keymap
+                .ctx
+                .diagnostics
+                .error(DiagnosticCode::InvalidStatement, mark, message);
             return false;
         }
     }
@@ -152,7 +163,7 @@ impl SymbolsBuilder {
         }
     }
     fn include(&mut self, ki: &mut XkbKeymap, includes: &mut [IncludeStmt]) -> bool {
-        if exceeds_include_max_depth(self.include_depth) {
+        if exceeds_include_max_depth(&mut ki.ctx, self.include_depth) {
             return false;
         }
         let mut included = Self::new(ki, self.include_depth + 1, &self.mods);
@@ -179,6 +190,7 @@ impl SymbolsBuilder {
             if !next.compile_stream(ki, &mut file.stream()) {
                 return false;
             }
+            ki.ctx.diagnostics.leave();
             included.merge_from(ki, &mut next, statement.merge);
         }
         if let Some(first) = includes.first() {
@@ -201,7 +213,7 @@ impl SymbolsBuilder {
                 .all(|vmod| handle_vmod_def(&mut ki.ctx, &mut self.mods, vmod)),
             Statement::ModMap(definition) => self.compile_modmap(ki, definition),
             Statement::Unknown => true,
-            _ => false,
+            _ => wrong_statement(ki),
         }
     }
     fn compile_key(&mut self, ki: &mut XkbKeymap, stmt: &mut NamedVarDef<'_>) -> bool {
@@ -210,6 +222,14 @@ impl SymbolsBuilder {
         keyi.name = stmt.name;
         if self.compile_key_body(ki, &mut Stream::new(stmt.body), &mut keyi) {
             set_explicit_group(self, &mut keyi);
+            if ki.key_index_by_name(keyi.name, true).is_none() {
+                let name = format!("<{}>", ki.ctx.atom_text(keyi.name));
+                let message = format!("key {name} is not defined in the keycodes; ignoring it");
+                let mark = ki.ctx.diagnostics.mark(None).with_token(name);
+                ki.ctx
+                    .diagnostics
+                    .warn(DiagnosticCode::UndefinedKeycode, mark, message);
+            }
             self.add_key(ki, &mut keyi, stmt.merge);
             return true;
         }
@@ -222,7 +242,15 @@ impl SymbolsBuilder {
         } else {
             match xkb_mod_name_to_index(&self.mods, def.modifier, MOD_REAL) {
                 Some(n) => n,
-                None => return false,
+                None => {
+                    let message = format!("modifier_map names unknown modifier {modifier_name}");
+                    let token = modifier_name.to_owned();
+                    return ki.ctx.diagnostics.fail(
+                        DiagnosticCode::UndeclaredModifier,
+                        Some(&token),
+                        message,
+                    );
+                }
             }
         };
         for key in def.keys.iter() {
@@ -302,6 +330,7 @@ impl SymbolsBuilder {
             };
             let (field, index) = if let Some(lhs) = &def.name {
                 if lhs.element != Element::None {
+                    invalid_value(ki, body, "cannot set global defaults inside a key");
                     all_valid_entries = false;
                     continue;
                 }
@@ -319,11 +348,28 @@ impl SymbolsBuilder {
                 _ => false,
             };
             if !valid {
+                invalid_value(ki, body, "invalid value for key field");
                 all_valid_entries = false;
             }
         }
     }
 }
+/// Report an invalid assignment at the variable `body` returned last.
+pub(crate) fn invalid_value(ki: &mut XkbKeymap, body: &Stream<'_>, message: &str) {
+    let mark = ki.ctx.diagnostics.mark(Some(body.statement_start()));
+    ki.ctx
+        .diagnostics
+        .error(DiagnosticCode::InvalidValue, mark, message);
+}
+pub(crate) fn wrong_statement(ki: &mut XkbKeymap) -> bool {
+    let message = format!(
+        "statement not allowed in the {} section",
+        ki.ctx.diagnostics.section
+    );
+    ki.ctx
+        .diagnostics
+        .fail(DiagnosticCode::WrongStatementType, None, message)
+}
 fn init_key_info_with_atom(keyi: &mut KeyInfo, star_atom: u32) {
     *keyi = KeyInfo {
         name: star_atom,
@@ -341,6 +387,7 @@ fn merge_groups(into: &mut GroupInfo, from: &mut GroupInfo, clobber: bool) {
     for (dst, mut src) in into.levels.iter_mut().zip(source) {
         if src.sym != 0 && (dst.sym == 0 || clobber) {
             dst.sym = src.sym;
+            dst.syms = std::mem::take(&mut src.syms);
             changed += 1;
         }
         if src.action.is_some() && (dst.action.is_none() || clobber) {
@@ -407,7 +454,7 @@ fn add_symbols_to_key(
         return false;
     };
     let group = &mut key.groups[group_index];
-    let ExprKind::Symbols(syms) = value else {
+    let ExprKind::Symbols(syms, multi) = value else {
         return false;
     };
     if group.explicit_syms {
@@ -422,6 +469,11 @@ fn add_symbols_to_key(
     for (level, &sym) in syms.iter().take(level_count).enumerate() {
         group.levels[level].sym = sym;
     }
+    for (level, syms) in multi {
+        if let Some(data) = group.levels.get_mut(*level) {
+            data.syms.clone_from(syms);
+        }
+    }
     true
 }
 fn set_symbols_field(
@@ -599,6 +651,7 @@ fn find_key_by_symbol(keymap: &XkbKeymap, start: usize, sym: u32) -> Option<usiz
     })
 }
 pub(crate) fn compile_symbols(input: CompileInput<'_, '_>, keymap: &mut XkbKeymap) -> bool {
+    keymap.ctx.diagnostics.section = Section::Symbols;
     let mods = keymap.mods;
     let mut builder = SymbolsBuilder::new(keymap, 0, &mods);
     let valid = match input {
@@ -613,6 +666,7 @@ pub(crate) fn compile_symbols(input: CompileInput<'_, '_>, keymap: &mut XkbKeyma
     builder.finish(keymap);
     true
 }
+use super::diagnostics::Mark;
 use super::parser::*;
 #[derive(Default)]
 pub(crate) struct KeyTypesInfo {
@@ -656,7 +710,7 @@ fn handle_include_key_types(
     info: &mut KeyTypesInfo,
     includes: &mut [IncludeStmt],
 ) -> bool {
-    if exceeds_include_max_depth(info.include_depth) {
+    if exceeds_include_max_depth(&mut ki.ctx, info.include_depth) {
         return false;
     }
     let mut included = key_types_info(info.include_depth.wrapping_add(1), &info.mods);
@@ -668,6 +722,7 @@ fn handle_include_key_types(
         if !compile_stream(ki, &mut next, &mut file.stream(), handle_key_type_statement) {
             return false;
         }
+        ki.ctx.diagnostics.leave();
         merge_included_key_types(&mut included, &mut next, stmt.merge);
     }
     if let Some(first) = includes.first() {
@@ -761,6 +816,7 @@ fn handle_key_type_body(
             Err(()) => return false,
         };
         let Some(lhs) = def.name.as_ref() else {
+            invalid_value(ki, &vars, "expected a field assignment");
             return false;
         };
         if !(lhs.element == Element::Type
@@ -769,6 +825,7 @@ fn handle_key_type_body(
                     set_key_type_field(ki, info, type_0, lhs.field, lhs.index.as_ref(), value)
                 }))
         {
+            invalid_value(ki, &vars, "invalid value for key type field");
             return false;
         }
     }
@@ -802,10 +859,11 @@ fn handle_key_type_statement(
             .iter()
             .all(|vmod| handle_vmod_def(&mut ki.ctx, &mut info.mods, vmod)),
         Statement::Unknown => !ki.strict,
-        _ => false,
+        _ => wrong_statement(ki),
     }
 }
 pub(crate) fn compile_key_types(input: CompileInput<'_, '_>, keymap: &mut XkbKeymap) -> bool {
+    keymap.ctx.diagnostics.section = Section::Types;
     let mut info = key_types_info(0, &keymap.mods);
     let valid = match input {
         CompileInput::Stream(stream) => stream.is_none_or(|stream| {
@@ -896,7 +954,7 @@ struct KeyNamesInfo {
     depth: u32,
     codes: Vec<u32>,
     names: Vec<u32>,
-    aliases: Vec<(u32, u32)>,
+    aliases: Vec<(u32, u32, Mark)>,
 }
 const KEY_ALIAS: u32 = 1 << 31;
 fn add_key_name(info: &mut KeyNamesInfo, code: u32, name: u32, clobber: bool) -> bool {
@@ -934,7 +992,7 @@ fn compile_keycode_statement(
 ) -> bool {
     match statement {
         Statement::Include(includes) => {
-            if exceeds_include_max_depth(info.depth) {
+            if exceeds_include_max_depth(&mut ki.ctx, info.depth) {
                 return false;
             }
             info.depth += 1;
@@ -946,6 +1004,7 @@ fn compile_keycode_statement(
                 if !compile_stream(ki, info, &mut file.stream(), compile_keycode_statement) {
                     return false;
                 }
+                ki.ctx.diagnostics.leave();
             }
             info.depth -= 1;
             true
@@ -954,14 +1013,16 @@ fn compile_keycode_statement(
             add_key_name(info, code, def.name, def.merge != MergeMode::Augment)
         }),
         Statement::KeyAlias(def) => {
-            info.aliases.push((def.alias, def.real));
+            let mark = ki.ctx.diagnostics.mark(None);
+            info.aliases.push((def.alias, def.real, mark));
             true
         }
         Statement::Var(_) | Statement::Unknown => true,
-        _ => false,
+        _ => wrong_statement(ki),
     }
 }
 pub(crate) fn compile_keycodes(input: CompileInput<'_, '_>, keymap: &mut XkbKeymap) -> bool {
+    keymap.ctx.diagnostics.section = Section::Keycodes;
     let mut info = KeyNamesInfo::default();
     let valid = match input {
         CompileInput::Stream(stream) => stream.is_none_or(|stream| {
@@ -979,7 +1040,7 @@ pub(crate) fn compile_keycodes(input: CompileInput<'_, '_>, keymap: &mut XkbKeym
     info.codes.resize(info.codes.len().max(256), 0);
     keymap.min_key_code = info.codes.iter().position(|name| *name != 0).unwrap_or(8) as u32;
     keymap.keys.resize_with(info.codes.len(), XkbKey::default);
-    for (alias, real) in info.aliases {
+    for (alias, real, mark) in info.aliases {
         info.names
             .resize(info.names.len().max(alias as usize + 1), 0);
         if info
@@ -988,12 +1049,20 @@ pub(crate) fn compile_keycodes(input: CompileInput<'_, '_>, keymap: &mut XkbKeym
             .is_some_and(|value| *value != 0)
         {
             info.names[alias as usize] = KEY_ALIAS | real;
+        } else {
+            let (alias, real) = (keymap.ctx.atom_text(alias), keymap.ctx.atom_text(real));
+            let message = format!("alias <{alias}> refers to undefined key <{real}>; ignoring it");
+            let mark = mark.with_token(format!("<{alias}>"));
+            keymap
+                .ctx
+                .diagnostics
+                .warn(DiagnosticCode::UnresolvedKeyAlias, mark, message);
         }
     }
     keymap.key_names = info.names;
     true
 }
-fn named_bool(value: &str) -> Option<bool> {
+pub(crate) fn named_bool(value: &str) -> Option<bool> {
     ["true", "yes", "on"]
         .iter()
         .any(|name| value.eq_ignore_ascii_case(name))
@@ -1039,7 +1108,7 @@ pub(crate) fn expr_resolve_level(ctx: &XkbContext, expr: &ExprKind) -> Option<u3
         .contains(&value)
         .then_some(value as u32 - 1)
 }
-fn expr_resolve_group(
+pub(crate) fn expr_resolve_group(
     keymap_info: &XkbKeymap,
     expr: &ExprKind,
     absolute: bool,
@@ -1107,7 +1176,7 @@ fn expr_resolve_repeat(ctx: &XkbContext, expr: &ExprKind) -> Option<Option<bool>
         named_bool(value).map(Some)
     }
 }
-fn expr_resolve_mod_mask(
+pub(crate) fn expr_resolve_mod_mask(
     ctx: &XkbContext,
     expr: &ExprKind,
     mod_type: u32,
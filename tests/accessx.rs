@@ -0,0 +1,221 @@
+//! Tests for AccessX StickyKeys, SlowKeys and BounceKeys.
+
+use wkb::{
+    AccessX, AccessXConfig, AccessXEvent, AccessXFilter, KeyDirection, StickyKeys, LEFT_SHIFT, WKB,
+};
+
+const LEFT_CTRL: u32 = 29;
+const KEY_A: u32 = 30;
+const KEY_S: u32 = 31;
+const MOD_SHIFT: u32 = 1;
+
+fn us() -> WKB {
+    WKB::new_from_names("", "", "us", "", None).unwrap()
+}
+
+fn tap(accessx: &mut AccessX, wkb: &mut WKB, evdev_code: u32, time: u32) -> Vec<AccessXEvent> {
+    let mut events = accessx.key_event(wkb, evdev_code, KeyDirection::Down, time);
+    events.extend(accessx.key_event(wkb, evdev_code, KeyDirection::Up, time + 10));
+    events
+}
+
+fn accepted(events: &[AccessXEvent]) -> Vec<(u32, KeyDirection)> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            AccessXEvent::Accepted {
+                evdev_code,
+                direction,
+                ..
+            } => Some((*evdev_code, *direction)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn sticky(two_keys_disable: bool) -> AccessXConfig {
+    AccessXConfig {
+        sticky_keys: Some(StickyKeys { two_keys_disable }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn sticky_keys_latch_lock_unlock() {
+    let mut wkb = us();
+    let mut accessx = AccessX::new(&mut wkb, sticky(false));
+
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 0);
+    assert_eq!(wkb.raw_modifiers().latched, MOD_SHIFT);
+    let events = tap(&mut accessx, &mut wkb, KEY_A, 100);
+    match &events[0] {
+        AccessXEvent::Accepted { result, .. } => assert_eq!(result.text, Some('A')),
+        event => panic!("unexpected {event:?}"),
+    }
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 200);
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 300);
+    assert_eq!(wkb.raw_modifiers().locked, MOD_SHIFT);
+    tap(&mut accessx, &mut wkb, KEY_A, 400);
+    assert_eq!(wkb.key_char(KEY_S), Some('S'));
+
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 500);
+    assert_eq!(wkb.raw_modifiers().locked, 0);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+    assert_eq!(wkb.key_char(KEY_S), Some('s'));
+
+    // Held modifiers still act while held.
+    accessx.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Down, 600);
+    tap(&mut accessx, &mut wkb, KEY_A, 610);
+    assert_eq!(wkb.key_char(KEY_S), Some('S'));
+    accessx.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Up, 700);
+    assert_eq!(wkb.raw_modifiers().depressed, 0);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+
+    // Turning StickyKeys off restores the keymap behaviour.
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 800);
+    let changes = accessx.set_config(&mut wkb, AccessXConfig::default());
+    assert!(changes.modifiers_updated);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 900);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+}
+
+#[test]
+fn two_keys_disable_sticky_keys() {
+    let mut wkb = us();
+    let mut accessx = AccessX::new(&mut wkb, sticky(true));
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 0);
+    assert_eq!(wkb.raw_modifiers().latched, MOD_SHIFT);
+
+    accessx.key_event(&mut wkb, LEFT_CTRL, KeyDirection::Down, 100);
+    let events = accessx.key_event(&mut wkb, KEY_A, KeyDirection::Down, 110);
+    assert!(matches!(events[0], AccessXEvent::StickyKeysDisabled { .. }));
+    assert_eq!(accessx.config().sticky_keys, None);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+    accessx.key_event(&mut wkb, KEY_A, KeyDirection::Up, 120);
+    accessx.key_event(&mut wkb, LEFT_CTRL, KeyDirection::Up, 130);
+    assert_eq!(wkb.raw_modifiers().depressed, 0);
+
+    tap(&mut accessx, &mut wkb, LEFT_SHIFT, 200);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+}
+
+#[test]
+fn slow_keys() {
+    let mut wkb = us();
+    let mut accessx = AccessX::new(
+        &mut wkb,
+        AccessXConfig {
+            slow_keys_delay: Some(300),
+            ..Default::default()
+        },
+    );
+
+    // Released too early.
+    let events = tap(&mut accessx, &mut wkb, KEY_A, 1000);
+    assert_eq!(
+        events,
+        [
+            AccessXEvent::Delayed {
+                evdev_code: KEY_A,
+                accept_time: 1300
+            },
+            AccessXEvent::Rejected {
+                evdev_code: KEY_A,
+                direction: KeyDirection::Up,
+                filter: AccessXFilter::SlowKeys
+            },
+        ]
+    );
+    assert_eq!(accessx.next_timeout(), None);
+
+    // Held long enough.
+    accessx.key_event(&mut wkb, KEY_A, KeyDirection::Down, 2000);
+    assert_eq!(accessx.next_timeout(), Some(2300));
+    assert!(accessx.timeout(&mut wkb, 2299).is_empty());
+    let events = accessx.timeout(&mut wkb, 2300);
+    assert_eq!(accepted(&events), [(KEY_A, KeyDirection::Down)]);
+    let events = accessx.key_event(&mut wkb, KEY_A, KeyDirection::Up, 2400);
+    assert_eq!(accepted(&events), [(KEY_A, KeyDirection::Up)]);
+
+    // Due presses are accepted before the next event.
+    accessx.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Down, 3000);
+    let events = accessx.key_event(&mut wkb, KEY_A, KeyDirection::Down, 3500);
+    assert_eq!(accepted(&events), [(LEFT_SHIFT, KeyDirection::Down)]);
+    let events = accessx.timeout(&mut wkb, 3800);
+    match &events[..] {
+        [AccessXEvent::Accepted { result, .. }] => {
+            assert_eq!(result.text, Some('A'))
+        }
+        events => panic!("unexpected {events:?}"),
+    }
+
+    // Timestamps wrap.
+    let mut wkb = us();
+    let mut accessx = AccessX::new(&mut wkb, accessx.config());
+    accessx.key_event(&mut wkb, KEY_A, KeyDirection::Down, u32::MAX - 100);
+    assert_eq!(accessx.next_timeout(), Some(199));
+    assert_eq!(accepted(&accessx.timeout(&mut wkb, 199)).len(), 1);
+}
+
+#[test]
+fn bounce_keys() {
+    let mut wkb = us();
+    let mut accessx = AccessX::new(
+        &mut wkb,
+        AccessXConfig {
+            bounce_keys_delay: Some(200),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(accepted(&tap(&mut accessx, &mut wkb, KEY_A, 0)).len(), 2);
+    let events = tap(&mut accessx, &mut wkb, KEY_A, 100);
+    assert_eq!(
+        events,
+        [
+            AccessXEvent::Rejected {
+                evdev_code: KEY_A,
+                direction: KeyDirection::Down,
+                filter: AccessXFilter::BounceKeys
+            },
+            AccessXEvent::Rejected {
+                evdev_code: KEY_A,
+                direction: KeyDirection::Up,
+                filter: AccessXFilter::BounceKeys
+            },
+        ]
+    );
+    // Other keys and later presses pass.
+    assert_eq!(accepted(&tap(&mut accessx, &mut wkb, KEY_S, 120)).len(), 2);
+    assert_eq!(accepted(&tap(&mut accessx, &mut wkb, KEY_A, 300)).len(), 2);
+}
+
+#[test]
+fn releases_keys_held_before() {
+    let mut wkb = us();
+    wkb.press_key(LEFT_SHIFT);
+    wkb.press_key(KEY_A);
+    let mut accessx = AccessX::new(
+        &mut wkb,
+        AccessXConfig {
+            slow_keys_delay: Some(300),
+            bounce_keys_delay: Some(200),
+            ..Default::default()
+        },
+    );
+
+    let events = accessx.key_event(&mut wkb, KEY_A, KeyDirection::Up, 0);
+    assert_eq!(accepted(&events), [(KEY_A, KeyDirection::Up)]);
+    let events = accessx.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Up, 10);
+    assert_eq!(accepted(&events), [(LEFT_SHIFT, KeyDirection::Up)]);
+    assert_eq!(wkb.pressed_keys().count(), 0);
+    assert_eq!(wkb.raw_modifiers().depressed, 0);
+
+    // Stray releases are still dropped.
+    assert!(accessx
+        .key_event(&mut wkb, KEY_S, KeyDirection::Up, 20)
+        .is_empty());
+}
@@ -0,0 +1,236 @@
+//! Modifier keys driven by the compat map's `interpret` actions, compared
+//! with xkbcommon.
+
+use wkb::{KeyDirection, WKB};
+use xkbcommon::xkb::{self as xkbcmn, Keycode};
+
+const LEFT_SHIFT: u32 = 42;
+const CAPS_LOCK: u32 = 58;
+const RIGHT_ALT: u32 = 100;
+const LOGO: u32 = 125;
+const KEY_Q: u32 = 16;
+
+/// A small keymap whose modifier keys behave as `compat` says.
+fn keymap(compat: &str) -> String {
+    format!(
+        r#"xkb_keymap {{
+    xkb_keycodes {{
+        minimum = 8;
+        maximum = 255;
+        <LFSH> = 50;
+        <CAPS> = 66;
+        <RALT> = 108;
+        <LWIN> = 133;
+        <AD01> = 24;
+    }};
+    xkb_types {{
+        virtual_modifiers LevelThree;
+        type "ONE_LEVEL" {{
+            modifiers = none;
+            map[none] = Level1;
+        }};
+        type "FOUR_LEVEL" {{
+            modifiers = Shift+LevelThree;
+            map[Shift] = Level2;
+            map[LevelThree] = Level3;
+            map[Shift+LevelThree] = Level4;
+        }};
+    }};
+    xkb_compat {{
+        virtual_modifiers LevelThree;
+        {compat}
+    }};
+    xkb_symbols {{
+        key <LFSH> {{ [ Shift_L ] }};
+        key <CAPS> {{ [ Caps_Lock ] }};
+        key <RALT> {{ [ ISO_Level3_Latch ] }};
+        key <LWIN> {{ [ Super_L ] }};
+        key <AD01> {{ type = "FOUR_LEVEL", [ q, Q, at, Greek_OMEGA ] }};
+        modifier_map Shift {{ <LFSH> }};
+        modifier_map Lock {{ <CAPS> }};
+        modifier_map Mod4 {{ <LWIN> }};
+        modifier_map Mod5 {{ <RALT> }};
+    }};
+}};"#
+    )
+}
+
+struct Both {
+    wkb: WKB,
+    xkb: xkbcmn::State,
+}
+
+impl Both {
+    fn new(compat: &str) -> Self {
+        let keymap = keymap(compat);
+        let wkb = WKB::new_from_string(&keymap).unwrap();
+        let context = xkbcmn::Context::new(xkbcmn::CONTEXT_NO_FLAGS);
+        let xkb_keymap = xkbcmn::Keymap::new_from_string(
+            &context,
+            keymap,
+            xkbcmn::KEYMAP_FORMAT_TEXT_V1,
+            xkbcmn::KEYMAP_COMPILE_NO_FLAGS,
+        )
+        .unwrap();
+        Self {
+            wkb,
+            xkb: xkbcmn::State::new(&xkb_keymap),
+        }
+    }
+
+    fn key(&mut self, code: u32, direction: KeyDirection) {
+        match direction {
+            KeyDirection::Down => self.wkb.press_key(code),
+            KeyDirection::Up => self.wkb.release_key(code),
+        };
+        let xkb_direction = match direction {
+            KeyDirection::Down => xkbcmn::KeyDirection::Down,
+            KeyDirection::Up => xkbcmn::KeyDirection::Up,
+        };
+        self.xkb.update_key(Keycode::new(code + 8), xkb_direction);
+        let raw = self.wkb.raw_modifiers();
+        let context = format!("key {code} {direction:?}");
+        assert_eq!(
+            raw.depressed,
+            self.xkb.serialize_mods(xkbcmn::STATE_MODS_DEPRESSED),
+            "depressed after {context}"
+        );
+        assert_eq!(
+            raw.latched,
+            self.xkb.serialize_mods(xkbcmn::STATE_MODS_LATCHED),
+            "latched after {context}"
+        );
+        assert_eq!(
+            raw.locked,
+            self.xkb.serialize_mods(xkbcmn::STATE_MODS_LOCKED),
+            "locked after {context}"
+        );
+    }
+
+    fn tap(&mut self, code: u32) {
+        self.key(code, KeyDirection::Down);
+        self.key(code, KeyDirection::Up);
+    }
+
+    fn assert_char(&self, code: u32) {
+        let expected = self.xkb.key_get_utf8(Keycode::new(code + 8)).chars().next();
+        assert_eq!(self.wkb.key_char(code), expected, "key {code}");
+    }
+}
+
+#[test]
+fn lock_mods_affect_lock_never_unlocks() {
+    let mut both =
+        Both::new("interpret Caps_Lock { action = LockMods(modifiers=Lock,affect=lock); };");
+    both.tap(CAPS_LOCK);
+    assert_eq!(both.wkb.raw_modifiers().locked, 1 << 1);
+    both.tap(CAPS_LOCK);
+    both.tap(CAPS_LOCK);
+    assert_eq!(both.wkb.raw_modifiers().locked, 1 << 1);
+}
+
+#[test]
+fn lock_mods_affect_unlock_never_locks() {
+    let mut both =
+        Both::new("interpret Caps_Lock { action = LockMods(modifiers=Lock,affect=unlock); };");
+    both.tap(CAPS_LOCK);
+    assert_eq!(both.wkb.raw_modifiers().locked, 0);
+}
+
+#[test]
+fn latch_to_lock_then_clear_locks() {
+    let mut both = Both::new(
+        "interpret ISO_Level3_Latch { useModMapMods = level1; virtualModifier = LevelThree; \
+         action = LatchMods(modifiers=LevelThree,clearLocks,latchToLock); };",
+    );
+    both.tap(RIGHT_ALT);
+    both.assert_char(KEY_Q);
+    both.tap(RIGHT_ALT);
+    assert_eq!(both.wkb.raw_modifiers().locked, 1 << 7);
+    both.tap(KEY_Q);
+    both.assert_char(KEY_Q);
+    both.tap(RIGHT_ALT);
+    assert_eq!(both.wkb.raw_modifiers().locked, 0);
+    both.assert_char(KEY_Q);
+}
+
+#[test]
+fn latch_without_latch_to_lock_toggles() {
+    let mut both = Both::new("interpret ISO_Level3_Latch { action = LatchMods(modifiers=Mod5); };");
+    both.tap(RIGHT_ALT);
+    assert_eq!(both.wkb.raw_modifiers().latched, 1 << 7);
+    both.tap(KEY_Q);
+    assert_eq!(both.wkb.raw_modifiers().latched, 0);
+}
+
+#[test]
+fn set_mods_sets_custom_target() {
+    let mut both = Both::new("interpret Super_L { action = SetMods(modifiers=Mod3); };");
+    both.key(LOGO, KeyDirection::Down);
+    assert_eq!(both.wkb.raw_modifiers().depressed, 1 << 5);
+    both.key(LOGO, KeyDirection::Up);
+}
+
+#[test]
+fn set_mods_clear_locks_on_tap_only() {
+    let compat = "setMods.clearLocks = True; \
+         interpret Caps_Lock { action = LockMods(modifiers=Shift); }; \
+         interpret Shift_L { action = SetMods(modifiers=modMapMods); };";
+    let mut both = Both::new(compat);
+    both.tap(CAPS_LOCK);
+    assert_eq!(both.wkb.raw_modifiers().locked, 1);
+
+    both.key(LEFT_SHIFT, KeyDirection::Down);
+    both.tap(KEY_Q);
+    both.key(LEFT_SHIFT, KeyDirection::Up);
+    assert_eq!(both.wkb.raw_modifiers().locked, 1);
+
+    both.tap(LEFT_SHIFT);
+    assert_eq!(both.wkb.raw_modifiers().locked, 0);
+}
+
+#[test]
+fn names_keymap_modifiers_match_xkbcommon() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let context = xkbcmn::Context::new(xkbcmn::CONTEXT_NO_FLAGS);
+    let keymap = xkbcmn::Keymap::new_from_names(
+        &context,
+        "evdev",
+        "pc105",
+        "us",
+        "",
+        None,
+        xkbcmn::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .unwrap();
+    let mut xkb = xkbcmn::State::new(&keymap);
+    for (code, direction) in [
+        (CAPS_LOCK, KeyDirection::Down),
+        (CAPS_LOCK, KeyDirection::Up),
+        (LEFT_SHIFT, KeyDirection::Down),
+        (LOGO, KeyDirection::Down),
+        (LOGO, KeyDirection::Up),
+        (LEFT_SHIFT, KeyDirection::Up),
+        (CAPS_LOCK, KeyDirection::Down),
+        (CAPS_LOCK, KeyDirection::Up),
+    ] {
+        match direction {
+            KeyDirection::Down => wkb.press_key(code),
+            KeyDirection::Up => wkb.release_key(code),
+        };
+        let xkb_direction = match direction {
+            KeyDirection::Down => xkbcmn::KeyDirection::Down,
+            KeyDirection::Up => xkbcmn::KeyDirection::Up,
+        };
+        xkb.update_key(Keycode::new(code + 8), xkb_direction);
+        let raw = wkb.raw_modifiers();
+        assert_eq!(
+            (raw.depressed, raw.locked),
+            (
+                xkb.serialize_mods(xkbcmn::STATE_MODS_DEPRESSED),
+                xkb.serialize_mods(xkbcmn::STATE_MODS_LOCKED)
+            ),
+            "key {code} {direction:?}"
+        );
+    }
+}
@@ -7,7 +7,7 @@
 
 use std::path::Path;
 use std::sync::Mutex;
-use wkb::WKB;
+use wkb::{ComposeKey, ComposeTable, Keystroke, Keysym, WKB};
 use xkbcommon::xkb::{self, Keycode};
 
 const EVDEV_OFFSET: u32 = 8;
@@ -33,6 +33,13 @@ const N: u32 = 49;
 const S: u32 = 31;
 const O: u32 = 24;
 const C: u32 = 46;
+const J: u32 = 36;
+const M: u32 = 50;
+const A: u32 = 30;
+const Q: u32 = 16;
+const G: u32 = 34;
+const KP_ADD: u32 = 78;
+const KP_SUBTRACT: u32 = 74;
 
 struct ComposeCase {
     name: &'static str,
@@ -145,8 +152,8 @@ fn wkb_compose_char(wkb: &mut WKB, keys: &[(u32, bool)]) -> Option<char> {
         if down {
             wkb.press_key(evdev);
             let result = wkb.compose(evdev);
-            if let Some(wkb::ComposeState::Finished(c)) = &result {
-                final_char = Some(*c);
+            if let Some(wkb::ComposeState::Finished(output)) = &result {
+                final_char = output.char();
             }
         } else {
             wkb.release_key(evdev);
@@ -393,9 +400,359 @@ fn compose_set_compose_key() {
     }
 }
 
+/// Sequences producing several characters, such as a letter with a
+/// combining accent, finish with the whole string.
+#[test]
+fn compose_string_output() {
+    if !skip_unless_compose_file() {
+        return;
+    }
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+
+    let saved_lc_all = std::env::var("LC_ALL").ok();
+    unsafe { std::env::set_var("LC_ALL", COMPOSE_LOCALE) };
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.set_compose_key(COMPOSE_KEY);
+    restore_env(&saved_lc_all);
+
+    let ctx = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+    let table = xkb::compose::Table::new_from_locale(
+        &ctx,
+        std::ffi::OsStr::new(COMPOSE_LOCALE),
+        xkb::compose::COMPILE_NO_FLAGS,
+    )
+    .expect("xkbcommon compose table");
+    let mut compose_state = xkb::compose::State::new(&table, xkb::compose::STATE_NO_FLAGS);
+    for sym in [XKB_KEY_MULTI_KEY, 0x27, 0x4a] {
+        compose_state.feed(xkb::Keysym::new(sym));
+    }
+    let expected = compose_state.utf8().expect("xkbcommon compose output");
+    assert_eq!(expected.chars().count(), 2);
+
+    let keys = [
+        (COMPOSE_KEY, true),
+        (COMPOSE_KEY, false),
+        (APOSTROPHE, true),
+        (APOSTROPHE, false),
+        (SHIFT, true),
+        (J, true),
+    ];
+    let mut result = None;
+    for &(code, down) in &keys {
+        if down {
+            wkb.press_key(code);
+            result = wkb.compose(code);
+        } else {
+            wkb.release_key(code);
+        }
+    }
+    let Some(wkb::ComposeState::Finished(output)) = result else {
+        panic!("compose did not finish: {result:?}");
+    };
+    assert_eq!(&*output.text, expected);
+    assert_eq!(output.char(), None);
+}
+
+/// Build the US layout with the Menu key as Compose under the given
+/// environment, restoring it afterwards.
+fn wkb_with_env(vars: &[(&str, Option<&str>)]) -> WKB {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+    let saved: Vec<_> = vars
+        .iter()
+        .map(|(name, _)| (*name, std::env::var(name).ok()))
+        .collect();
+    for (name, value) in vars {
+        match value {
+            Some(value) => unsafe { std::env::set_var(name, value) },
+            None => unsafe { std::env::remove_var(name) },
+        }
+    }
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.set_compose_key(COMPOSE_KEY);
+    for (name, value) in saved {
+        match value {
+            Some(value) => unsafe { std::env::set_var(name, value) },
+            None => unsafe { std::env::remove_var(name) },
+        }
+    }
+    wkb
+}
+
+/// Text of the compose sequence typed with `keys`, if it finishes.
+fn compose_text(wkb: &mut WKB, keys: &[u32]) -> Option<String> {
+    let mut text = None;
+    for &code in keys {
+        let result = wkb.key_event(code, wkb::KeyDirection::Down);
+        wkb.key_event(code, wkb::KeyDirection::Up);
+        text = result.commit_text().map(Into::into);
+    }
+    text
+}
+
+#[test]
+fn xcomposefile_includes_locale_file() {
+    if !skip_unless_compose_file() {
+        return;
+    }
+    let path = std::env::temp_dir().join(format!("wkb-xcompose-{}", std::process::id()));
+    std::fs::write(
+        &path,
+        "include \"%L\"\n<Multi_key> <m> <a> : \"me@example.com\"\n",
+    )
+    .unwrap();
+    let mut wkb = wkb_with_env(&[
+        ("XCOMPOSEFILE", path.to_str()),
+        ("LC_ALL", Some(COMPOSE_LOCALE)),
+    ]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, M, A]).as_deref(),
+        Some("me@example.com")
+    );
+    assert_eq!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, APOSTROPHE, E]).as_deref(),
+        Some("é")
+    );
+}
+
+#[test]
+fn locale_alias_selects_compose_file() {
+    if !skip_unless_compose_file() {
+        return;
+    }
+    for locale in ["en_US.utf8", "C", "de_DE.UTF-8"] {
+        let mut wkb = wkb_with_env(&[("XCOMPOSEFILE", None), ("LC_ALL", Some(locale))]);
+        assert_eq!(
+            compose_text(&mut wkb, &[COMPOSE_KEY, APOSTROPHE, E]).as_deref(),
+            Some("é"),
+            "{locale}"
+        );
+    }
+}
+
 fn restore_env(saved: &Option<String>) {
     match saved {
         Some(v) => unsafe { std::env::set_var("LC_ALL", v) },
         None => unsafe { std::env::remove_var("LC_ALL") },
     }
 }
+
+#[test]
+fn installed_compose_table() {
+    let mut wkb = {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        WKB::new_from_names("", "", "us", "", None).unwrap()
+    };
+    wkb.set_compose_key(COMPOSE_KEY);
+
+    let compose = wkb::ir::COMPOSE_KEY_CHAR;
+    let mut table = ComposeTable::from_string(
+        "<Multi_key> <m> <a> : \"me@example.com\"\n<Multi_key> <a> <a> : \"å\" aring\n",
+    );
+    let mut overrides = ComposeTable::new();
+    let keysyms = ["Multi_key", "a", "a"].map(|name| Keysym::from_name(name).unwrap());
+    assert!(overrides.insert_keysyms(&keysyms, 'æ'));
+    assert!(overrides.insert(&[compose, 'q', 'q'], 'µ'));
+    assert!(!overrides.insert(&[], 'x'));
+    table.merge(&overrides);
+    assert_eq!(table.len(), 4);
+    assert!(table.reachability_filter());
+
+    wkb.set_compose_table(&table, None).unwrap();
+    assert_eq!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, M, A]).as_deref(),
+        Some("me@example.com")
+    );
+    assert_eq!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, A, A]).as_deref(),
+        Some("æ")
+    );
+    assert_eq!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, Q, Q]).as_deref(),
+        Some("µ")
+    );
+    assert_ne!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, APOSTROPHE, E]).as_deref(),
+        Some("é")
+    );
+
+    assert!(matches!(
+        wkb.set_compose_table(&table, Some(1)),
+        Err(wkb::WkbError::InvalidLayout(1))
+    ));
+    wkb.reset_compose_table(Some(0)).unwrap();
+    assert_ne!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, M, A]).as_deref(),
+        Some("me@example.com")
+    );
+}
+
+/// Sequences match keysyms, so a keypad key does not take part in
+/// sequences written for the main key producing the same character.
+#[test]
+fn sequences_match_keysyms() {
+    let mut wkb = {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        WKB::new_from_names("", "", "us", "", None).unwrap()
+    };
+    wkb.set_compose_key(COMPOSE_KEY);
+    let table = ComposeTable::from_string(
+        "<Multi_key> <plus> <plus> : \"#\"\n<Multi_key> <KP_Add> <KP_Subtract> : \"±\"\n",
+    );
+    wkb.set_compose_table(&table, None).unwrap();
+    assert_eq!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, KP_ADD, KP_SUBTRACT]).as_deref(),
+        Some("±")
+    );
+    assert_ne!(
+        compose_text(&mut wkb, &[COMPOSE_KEY, KP_ADD, KP_ADD]).as_deref(),
+        Some("#")
+    );
+}
+
+#[test]
+fn compose_table_from_missing_file() {
+    assert!(ComposeTable::from_file("/nonexistent/Compose").is_err());
+}
+
+/// Layout names are not locales: `de` must not pick the ISO 8859-1 Compose
+/// file through `locale.alias`.
+#[test]
+fn layout_name_does_not_select_compose_file() {
+    if !skip_unless_compose_file() {
+        return;
+    }
+    let mut wkb = {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        let saved_lc_all = std::env::var("LC_ALL").ok();
+        unsafe { std::env::set_var("LC_ALL", COMPOSE_LOCALE) };
+        let wkb = WKB::new_from_names("", "", "de", "", None).unwrap();
+        restore_env(&saved_lc_all);
+        wkb
+    };
+    // dead_acute, then e.
+    assert_eq!(compose_text(&mut wkb, &[13, E]).as_deref(), Some("é"));
+}
+
+/// Preedit text, as reported by each key of `keys`.
+fn preedits(wkb: &mut WKB, keys: &[u32]) -> Vec<Option<String>> {
+    keys.iter()
+        .map(|&code| {
+            let result = wkb.key_event(code, wkb::KeyDirection::Down);
+            wkb.key_event(code, wkb::KeyDirection::Up);
+            match result.compose {
+                Some(wkb::ComposeState::Composing(preedit)) => Some(preedit.to_string()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn preedit_and_completions() {
+    let mut wkb = {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        WKB::new_from_names("", "", "de", "", None).unwrap()
+    };
+    wkb.set_compose_key(COMPOSE_KEY);
+    let table = ComposeTable::from_string(
+        "<dead_acute> <e> : \"é\"\n\
+         <dead_acute> <dead_acute> : \"´\"\n\
+         <Multi_key> <s> <s> : \"ß\"\n\
+         <Multi_key> <s> <t> <e> : \"ste\"\n",
+    );
+    wkb.set_compose_table(&table, None).unwrap();
+
+    // dead_acute is the key right of ß on the German layout.
+    assert_eq!(preedits(&mut wkb, &[13]), [Some("´".to_owned())]);
+    assert_eq!(wkb.compose_preedit().as_deref(), Some("´"));
+    let completions = wkb.compose_completions();
+    assert_eq!(completions.len(), 2);
+    let e = completions
+        .iter()
+        .find(|completion| completion.key == ComposeKey::Keysym(0x65))
+        .unwrap();
+    assert_eq!(e.preedit.as_str(), "e");
+    assert_eq!(e.output.as_ref().map(|output| &*output.text), Some("é"));
+    assert_eq!(compose_text(&mut wkb, &[E]).as_deref(), Some("é"));
+    assert_eq!(wkb.compose_preedit(), None);
+
+    assert_eq!(
+        preedits(&mut wkb, &[COMPOSE_KEY, S, 20]),
+        [
+            Some("·".to_owned()),
+            Some("·s".to_owned()),
+            Some("·st".to_owned())
+        ]
+    );
+    let completions = wkb.compose_completions();
+    assert_eq!(completions.len(), 1);
+    assert_eq!(
+        completions[0].output.as_ref().map(|o| &*o.text),
+        Some("ste")
+    );
+    wkb.reset_state();
+
+    wkb.set_compose_placeholder(Some('⎄'));
+    assert_eq!(preedits(&mut wkb, &[COMPOSE_KEY])[0].as_deref(), Some("⎄"));
+    wkb.reset_state();
+    wkb.set_compose_placeholder(None);
+    assert_eq!(
+        preedits(&mut wkb, &[COMPOSE_KEY, S]),
+        [Some(String::new()), Some("s".to_owned())]
+    );
+
+    wkb.reset_state();
+    let starts = wkb.compose_completions();
+    assert!(starts
+        .iter()
+        .any(|completion| completion.key == ComposeKey::Compose));
+}
+
+#[test]
+fn candidates_and_key_sequences() {
+    let (mut us, mut de) = {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        (
+            WKB::new_from_names("", "", "us", "", None).unwrap(),
+            WKB::new_from_names("", "", "de", "", None).unwrap(),
+        )
+    };
+    let table = ComposeTable::from_string(
+        "<Multi_key> <n> <g> : \"ŋ\"\n\
+         <Multi_key> <n> <n> : \"ñ\"\n\
+         <Multi_key> <N> <G> : \"Ŋ\"\n\
+         <dead_acute> <e> : \"é\"\n",
+    );
+    us.set_compose_key(COMPOSE_KEY);
+    us.set_compose_table(&table, None).unwrap();
+    de.set_compose_table(&table, None).unwrap();
+    let shift = us.keymap().mod_mask("Shift");
+    let key = |evdev_code, mods| Keystroke { evdev_code, mods };
+
+    assert_eq!(us.compose_candidates().len(), 3);
+    preedits(&mut us, &[COMPOSE_KEY, N]);
+    let candidates = us.compose_candidates();
+    let texts: Vec<&str> = candidates.iter().map(|c| &*c.output.text).collect();
+    assert_eq!(texts, ["ŋ", "ñ"]);
+    assert_eq!(candidates[0].keys, [ComposeKey::Keysym(0x67)]);
+    us.reset_state();
+
+    assert_eq!(us.key_sequences('A'), [vec![key(A, shift)]]);
+    assert_eq!(
+        us.key_sequences('ŋ'),
+        [vec![key(COMPOSE_KEY, 0), key(N, 0), key(G, 0)]]
+    );
+    assert_eq!(
+        us.key_sequences('Ŋ'),
+        [vec![key(COMPOSE_KEY, 0), key(N, shift), key(G, shift)]]
+    );
+    assert!(us.key_sequences('é').is_empty());
+
+    // The German layout has no Compose key, but types ŋ with AltGr and é
+    // with dead_acute.
+    let level3 = de.keymap().mod_mask("LevelThree");
+    assert_ne!(level3, 0);
+    assert_eq!(de.key_sequences('ŋ'), [vec![key(G, level3)]]);
+    assert_eq!(de.key_sequences('é'), [vec![key(13, 0), key(E, 0)]]);
+}
@@ -0,0 +1,82 @@
+//! Consumed-modifier reporting compared with xkbcommon in both consumption modes.
+
+use std::ffi::c_int;
+
+use test_case::test_matrix;
+use wkb::{ConsumedMode, KeyDirection, ALTGR, LEFT_SHIFT, WKB};
+use xkbcommon::xkb as xkbcmn;
+
+mod common;
+use common::{activate_locks, update_both, xkb_new_from_names};
+
+const KEY_EQUAL: u32 = 13;
+const KEY_A: u32 = 30;
+const MOD_SHIFT: u32 = 1 << 0;
+const MOD_CTRL: u32 = 1 << 2;
+
+extern "C" {
+    fn xkb_state_key_get_consumed_mods2(
+        state: *mut xkbcmn::ffi::xkb_state,
+        key: u32,
+        mode: c_int,
+    ) -> u32;
+}
+
+fn xkb_consumed(xkb: &xkbcmn::State, code: u32, mode: ConsumedMode) -> u32 {
+    let mode = match mode {
+        ConsumedMode::Xkb => 0,
+        ConsumedMode::Gtk => 1,
+    };
+    // SAFETY: the state pointer is valid for the lifetime of `xkb`.
+    unsafe { xkb_state_key_get_consumed_mods2(xkb.get_raw_ptr(), code + 8, mode) }
+}
+
+fn assert_consumed_match(wkb: &WKB, xkb: &xkbcmn::State, context: &str) {
+    for mode in [ConsumedMode::Xkb, ConsumedMode::Gtk] {
+        for code in 1..128 {
+            assert_eq!(
+                wkb.consumed_modifiers(code, mode),
+                xkb_consumed(xkb, code, mode),
+                "{context}: {mode:?} key {code}"
+            );
+        }
+    }
+}
+
+#[test_matrix(["us", "de", "fr"], [0, 1, 2])]
+fn consumed_modifiers_match_xkbcommon(locale: &str, locks: u8) {
+    let mut wkb = WKB::new_from_names("", "", locale, "", None).unwrap();
+    let mut xkb = xkb_new_from_names(locale, "");
+    activate_locks(&mut wkb, &mut xkb, locks);
+    assert_consumed_match(&wkb, &xkb, "base");
+
+    update_both(&mut wkb, &mut xkb, LEFT_SHIFT, KeyDirection::Down);
+    assert_consumed_match(&wkb, &xkb, "shift");
+
+    update_both(&mut wkb, &mut xkb, LEFT_SHIFT, KeyDirection::Up);
+    update_both(&mut wkb, &mut xkb, ALTGR, KeyDirection::Down);
+    assert_consumed_match(&wkb, &xkb, "altgr");
+}
+
+#[test]
+fn shifted_symbol_consumes_shift_but_not_ctrl() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.press_key(29);
+    wkb.press_key(LEFT_SHIFT);
+    for mode in [ConsumedMode::Xkb, ConsumedMode::Gtk] {
+        assert!(wkb.mod_is_consumed(KEY_EQUAL, MOD_SHIFT, mode));
+        assert!(!wkb.mod_is_consumed(KEY_EQUAL, MOD_CTRL, mode));
+    }
+    assert_eq!(wkb.key_sym(KEY_EQUAL).name().as_deref(), Some("plus"));
+}
+
+#[test]
+fn layout_file_keeps_shift_consumption() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let mut loaded = WKB::new_from_layouts(vec![wkb.export_layout(0).unwrap()]).unwrap();
+    for state in [&mut wkb, &mut loaded] {
+        state.press_key(LEFT_SHIFT);
+        assert!(state.mod_is_consumed(KEY_A, MOD_SHIFT, ConsumedMode::Gtk));
+        assert!(!state.mod_is_consumed(1, MOD_SHIFT, ConsumedMode::Gtk));
+    }
+}
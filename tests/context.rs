@@ -0,0 +1,127 @@
+//! Keymaps compiled with custom include paths and in-memory XKB files.
+
+use std::sync::Arc;
+use wkb::{Context, DiagnosticCode, Keymap, Keysym, WKB};
+
+const KEY_A: u32 = 30;
+const KEY_S: u32 = 31;
+
+const KEYCODES: &str = "default xkb_keycodes \"test\" { <AC01> = 38; <AC02> = 39; };";
+const TYPES: &str = "default xkb_types \"test\" {
+    type \"ONE_LEVEL\" { modifiers = none; level_name[1] = \"Any\"; };
+    type \"ALPHABETIC\" {
+        modifiers = Shift + Lock;
+        map[Shift] = Level2;
+        map[Lock] = Level2;
+        level_name[1] = \"Base\";
+        level_name[2] = \"Caps\";
+    };
+};";
+const SYMBOLS: &str = "default xkb_symbols \"test\" {
+    key <AC01> { [ x, X ] };
+    key <AC02> { [ y, Y ] };
+};";
+const RULES: &str = "! model = keycodes
+  * = test
+! model = types
+  * = test
+! model = compat
+  * = test
+! layout = symbols
+  * = test
+";
+
+fn in_memory() -> Context {
+    let mut context = Context::without_default_paths();
+    context.add_file("keycodes/test", KEYCODES);
+    context.add_file("types/test", TYPES);
+    context.add_file("compat/test", "default xkb_compat \"test\" { };");
+    context.add_file("symbols/test", SYMBOLS);
+    context.add_file("rules/test", RULES);
+    context
+}
+
+fn state(keymap: Keymap) -> WKB {
+    WKB::new(Arc::new(keymap))
+}
+
+#[test]
+fn names_from_memory_only() {
+    let context = in_memory();
+    assert!(context.include_paths().is_empty());
+    let (keymap, warnings) = context
+        .keymap_from_names("test", "", "us", "", None)
+        .unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    let wkb = state(keymap);
+    assert_eq!(wkb.key_sym(KEY_A), Keysym::from_char('x'));
+    assert_eq!(wkb.key_sym(KEY_S), Keysym::from_char('y'));
+}
+
+#[test]
+fn string_includes_from_memory() {
+    let keymap = "xkb_keymap {
+        xkb_keycodes { include \"test\" };
+        xkb_types { include \"test\" };
+        xkb_compat { };
+        xkb_symbols { include \"test\" };
+    };";
+    let (keymap, _) = in_memory().keymap_from_string(keymap).unwrap();
+    assert_eq!(state(keymap).key_sym(KEY_A), Keysym::from_char('x'));
+}
+
+#[test]
+fn memory_file_shadows_include_paths() {
+    let mut context = Context::new();
+    context.add_file(
+        "symbols/shadowed",
+        "default xkb_symbols \"basic\" { include \"us(basic)\" key <AC01> { [ b, B ] }; };",
+    );
+    let (keymap, _) = context
+        .keymap_from_names("evdev", "pc105", "shadowed", "", None)
+        .unwrap();
+    let wkb = state(keymap);
+    assert_eq!(wkb.key_sym(KEY_A), Keysym::from_char('b'));
+    assert_eq!(wkb.key_sym(KEY_S), Keysym::from_char('s'));
+}
+
+#[test]
+fn prepended_directory_is_searched_first() {
+    let dir = std::env::temp_dir().join(format!("wkb-context-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("symbols")).unwrap();
+    std::fs::write(dir.join("symbols/test"), SYMBOLS.replace("x, X", "z, Z")).unwrap();
+    let mut context = in_memory();
+    context.remove_file("symbols/test");
+    assert!(context.include_path_prepend(&dir));
+    assert!(!context.include_path_append(dir.join("missing")));
+    assert_eq!(context.include_paths().len(), 1);
+    let result = context.keymap_from_names("test", "", "us", "", None);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        state(result.unwrap().0).key_sym(KEY_A),
+        Keysym::from_char('z')
+    );
+}
+
+#[test]
+fn no_default_paths() {
+    let context = Context::without_default_paths();
+    let error = context
+        .keymap_from_names("evdev", "pc105", "us", "", None)
+        .err()
+        .unwrap();
+    let code = error.diagnostic().map(|diagnostic| diagnostic.code);
+    assert_eq!(code, Some(DiagnosticCode::RulesFileNotFound));
+}
+
+#[test]
+fn cleared_paths_keep_memory_files() {
+    let mut context = in_memory();
+    context.include_path_append_default();
+    assert!(!context.include_paths().is_empty());
+    context.include_path_clear();
+    assert!(context.include_paths().is_empty());
+    assert!(context
+        .keymap_from_names("test", "", "us", "", None)
+        .is_ok());
+}
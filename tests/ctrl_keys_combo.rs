@@ -2,9 +2,10 @@
 //! wkb may suppress more keys with CTRL than xkbcommon, so wkb returning
 //! None is acceptable even when xkbcommon produces a character.
 
+use std::ops::RangeInclusive;
 use test_case::test_matrix;
 use wkb::KeyDirection;
-use xkbcommon::xkb::Keycode;
+use xkbcommon::xkb::{self, Keycode};
 
 include!("../test_data/layouts.rs");
 
@@ -15,6 +16,8 @@ const LEFT_CTRL: u32 = 29;
 const RIGHT_CTRL: u32 = 97;
 const ALT: u32 = 56;
 const SHIFT: u32 = 42;
+const DEAD_KEYSYMS: RangeInclusive<u32> = 0xfe50..=0xfe93;
+const XF86_NUMERIC_KEYSYMS: RangeInclusive<u32> = 0x10081200..=0x1008120b;
 
 /// Returns true if the character is a control character
 fn is_control_char(c: char) -> bool {
@@ -64,10 +67,33 @@ fn ctrl_combo(locale: &str, combo: u8) {
         for &code in mods {
             update_both(&mut wkb, &mut xkb, code, KeyDirection::Down);
         }
+        // Layouts such as ca(multix) or kr(kr104) give the right Ctrl key
+        // another role, so both sides type as usual.
+        let ctrl = xkb.mod_name_is_active(xkb::MOD_NAME_CTRL, xkb::STATE_MODS_EFFECTIVE);
+        assert_eq!(
+            wkb.ctrl(),
+            ctrl,
+            "CTRL combo={combo} state for locale={locale} layout={layout}"
+        );
 
         for keycode in 0..701u32 {
             let wkb_char = wkb.key_char(keycode);
-            let xkb_char = normalize_xkb_char(xkb.key_get_utf8(Keycode::new(keycode + 8)));
+            let xkb_str = xkb.key_get_utf8(Keycode::new(keycode + 8));
+            if !ctrl {
+                // wkb types dead keys and XF86Numeric keys, which type
+                // nothing in xkbcommon.
+                let sym = xkb.key_get_one_sym(Keycode::new(keycode + 8)).raw();
+                if DEAD_KEYSYMS.contains(&sym) || XF86_NUMERIC_KEYSYMS.contains(&sym) {
+                    continue;
+                }
+                assert_eq!(
+                    wkb_char.map(String::from).unwrap_or_default(),
+                    xkb_str,
+                    "CTRL combo={combo} mismatch for locale={locale} layout={layout} key={keycode}",
+                );
+                continue;
+            }
+            let xkb_char = normalize_xkb_char(xkb_str);
 
             // wkb may suppress more keys with CTRL than xkbcommon.
             // If wkb returns a character, it should match xkb.
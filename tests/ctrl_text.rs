@@ -0,0 +1,104 @@
+//! Tests for the Ctrl control-character transformation of `TextMode::Control`,
+//! compared with xkbcommon's `xkb_state_key_get_utf8`.
+
+use test_case::test_matrix;
+use wkb::{KeyDirection, KeyText, TextMode, WKB};
+use xkbcommon::xkb::{self as xkbcmn, Keycode};
+
+mod common;
+use common::{update_both, xkb_new_from_names};
+
+const LEFT_CTRL: u32 = 29;
+const RIGHT_CTRL: u32 = 97;
+const ALT: u32 = 56;
+const SHIFT: u32 = 42;
+const KEY_A: u32 = 30;
+const KEY_LEFTBRACE: u32 = 26;
+
+fn assert_text_matches(wkb: &WKB, xkb: &xkbcmn::State, context: &str) {
+    for code in 1..128 {
+        let utf8 = xkb.key_get_utf8(Keycode::new(code + 8));
+        let mut chars = utf8.chars();
+        let (Some(expected), None) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let text = wkb.key_text(code, TextMode::Control);
+        assert_eq!(
+            text.map(|text| text.ch),
+            Some(expected),
+            "{context}: key {code}"
+        );
+    }
+}
+
+#[test_matrix(["us", "de", "fr", "ru", "gr", "us,ru", "ru,us"], [0u8, 1, 2, 3])]
+fn control_text_matches_xkbcommon(locale: &str, combo: u8) {
+    let mods: &[u32] = match combo {
+        0 => &[LEFT_CTRL],
+        1 => &[RIGHT_CTRL],
+        2 => &[LEFT_CTRL, ALT],
+        3 => &[LEFT_CTRL, SHIFT],
+        _ => unreachable!(),
+    };
+    let mut wkb = WKB::new_from_names("", "", locale, "", None).unwrap();
+    let mut xkb = xkb_new_from_names(locale, "");
+    assert_text_matches(&wkb, &xkb, "no modifiers");
+    for &code in mods {
+        update_both(&mut wkb, &mut xkb, code, KeyDirection::Down);
+    }
+    assert_text_matches(&wkb, &xkb, &format!("{locale} combo={combo}"));
+}
+
+#[test]
+fn control_text_reports_transformation() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    assert_eq!(
+        wkb.key_text(KEY_A, TextMode::Control),
+        Some(KeyText {
+            ch: 'a',
+            control: false
+        })
+    );
+    wkb.press_key(LEFT_CTRL);
+    assert_eq!(wkb.key_text(KEY_A, TextMode::Plain), None);
+    assert_eq!(
+        wkb.key_text(KEY_A, TextMode::Control),
+        Some(KeyText {
+            ch: '\u{1}',
+            control: true
+        })
+    );
+    assert_eq!(
+        wkb.key_text(KEY_LEFTBRACE, TextMode::Control).map(|t| t.ch),
+        Some('\u{1b}')
+    );
+}
+
+#[test]
+fn control_text_falls_back_to_latin_layout() {
+    let mut wkb = WKB::new_from_names("", "", "ru,us", "", None).unwrap();
+    assert_eq!(wkb.key_char(KEY_A), Some('ф'));
+    wkb.press_key(LEFT_CTRL);
+    assert_eq!(
+        wkb.key_text(KEY_A, TextMode::Control),
+        Some(KeyText {
+            ch: '\u{1}',
+            control: true
+        })
+    );
+}
+
+#[test]
+fn key_event_uses_text_mode() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.press_key(LEFT_CTRL);
+    let result = wkb.key_event(KEY_A, KeyDirection::Down);
+    assert_eq!(result.text, None);
+    assert!(!result.control_text);
+
+    wkb.set_text_mode(TextMode::Control);
+    assert_eq!(wkb.text_mode(), TextMode::Control);
+    let result = wkb.key_event(KEY_A, KeyDirection::Down);
+    assert_eq!(result.text, Some('\u{1}'));
+    assert!(result.control_text);
+}
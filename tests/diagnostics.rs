@@ -0,0 +1,102 @@
+//! Errors and warnings reported while compiling keymaps.
+
+use test_case::test_case;
+use wkb::{Diagnostic, DiagnosticCode, Keymap, Section, XkbError};
+
+const KEYMAP: &str = "xkb_keymap {
+xkb_keycodes { <A> = 38; <B> = 56; };
+xkb_types { include \"basic\" };
+xkb_compat { };
+xkb_symbols {
+    key <A> { [ a, A ] };
+    key <B> { [ b, B ] };
+};
+};";
+
+fn compile_error(keymap: &str) -> (XkbError, Diagnostic) {
+    let Err(error) = Keymap::new_from_string(keymap) else {
+        panic!("keymap compiled");
+    };
+    let diagnostic = error.diagnostic().expect("no diagnostic").clone();
+    (error, diagnostic)
+}
+
+fn warnings(keymap: &str) -> Vec<Diagnostic> {
+    Keymap::new_from_string_with_warnings(keymap).unwrap().1
+}
+
+#[test]
+fn clean_keymap_has_no_warnings() {
+    assert!(warnings(KEYMAP).is_empty());
+    let (_, warnings) =
+        Keymap::new_from_names_with_warnings("evdev", "pc105", "us", "", None).unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+#[test]
+fn syntax_error_position() {
+    let (error, diagnostic) = compile_error(&KEYMAP.replace("[ b, B ]", "[ b, B ;"));
+    assert!(matches!(error, XkbError::KeymapParsing(_)));
+    assert_eq!(diagnostic.code, DiagnosticCode::InvalidSyntax);
+    assert_eq!(diagnostic.section, Section::Symbols);
+    assert_eq!((diagnostic.line, diagnostic.column), (7, 22));
+    assert_eq!(diagnostic.token.as_deref(), Some(";"));
+    assert_eq!(diagnostic.file, None);
+}
+
+#[test_case("", DiagnosticCode::InvalidSyntax; "empty")]
+#[test_case("xkb_keymap {\0};", DiagnosticCode::InvalidFileEncoding; "nul byte")]
+#[test_case("xkb_symbols { };", DiagnosticCode::WrongStatementType; "no keymap block")]
+fn invalid_input(keymap: &str, code: DiagnosticCode) {
+    assert_eq!(compile_error(keymap).1.code, code);
+}
+
+#[test]
+fn missing_include() {
+    let (error, diagnostic) =
+        compile_error(&KEYMAP.replace("include \"basic\"", "include \"nosuch\""));
+    assert!(matches!(error, XkbError::KeymapCompilation(_)));
+    assert_eq!(diagnostic.code, DiagnosticCode::IncludedFileNotFound);
+    assert_eq!(diagnostic.section, Section::Types);
+    assert_eq!(diagnostic.token.as_deref(), Some("nosuch"));
+    assert_eq!(diagnostic.line, 3);
+}
+
+#[test]
+fn statement_in_wrong_section() {
+    let keymap = KEYMAP.replace("xkb_compat { };", "xkb_compat { key <A> { [ a ] }; };");
+    let diagnostic = compile_error(&keymap).1;
+    assert_eq!(diagnostic.code, DiagnosticCode::WrongStatementType);
+    assert_eq!(diagnostic.section, Section::Compat);
+    assert_eq!(diagnostic.token.as_deref(), Some("key"));
+}
+
+#[test]
+fn undeclared_modifier() {
+    let keymap = KEYMAP.replace(
+        "key <B> { [ b, B ] };",
+        "key <B> { [ b, B ] }; modifier_map Foo { <B> };",
+    );
+    let diagnostic = compile_error(&keymap).1;
+    assert_eq!(diagnostic.code, DiagnosticCode::UndeclaredModifier);
+    assert_eq!(diagnostic.token.as_deref(), Some("Foo"));
+}
+
+#[test_case("[ b, B ]", "[ bogus_sym, B ]", DiagnosticCode::UnknownKeysym, "bogus_sym"; "unknown keysym")]
+#[test_case("key <B>", "key <Z>", DiagnosticCode::UndefinedKeycode, "<Z>"; "undefined keycode")]
+#[test_case("<B> = 56;", "<B> = 56; alias <X> = <Q>;", DiagnosticCode::UnresolvedKeyAlias, "<X>"; "unresolved alias")]
+fn warning_is_reported(from: &str, to: &str, code: DiagnosticCode, token: &str) {
+    let warnings = warnings(&KEYMAP.replace(from, to));
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert_eq!(warnings[0].code, code);
+    assert!(!code.is_error());
+    assert_eq!(warnings[0].token.as_deref(), Some(token));
+}
+
+#[test]
+fn unknown_rules_file() {
+    let error = Keymap::new_from_names("nosuchrules", "", "us", "", None).err();
+    let diagnostic = error.as_ref().and_then(XkbError::diagnostic).unwrap();
+    assert_eq!(diagnostic.code, DiagnosticCode::RulesFileNotFound);
+    assert_eq!(diagnostic.section, Section::Rules);
+}
@@ -317,6 +317,31 @@ fn alt_shift_toggle_groups_alt_and_shift() {
     assert_eq!(wkb.active_layout_idx(), 1);
 }
 
+#[test]
+fn chord_held_across_set_layout_still_toggles() {
+    let (mut wkb, mut state) = states("grp:alt_shift_toggle");
+
+    // Alt stays held while the compositor switches layouts.
+    update_both(&mut wkb, &mut state, ALT, KeyDirection::Down);
+    wkb.set_layout(1).unwrap();
+    state.update_mask(
+        state.serialize_mods(xkb::STATE_MODS_DEPRESSED),
+        0,
+        0,
+        0,
+        0,
+        1,
+    );
+    assert_group(&wkb, &state, "setting the layout with alt held");
+
+    update_both(&mut wkb, &mut state, LEFT_SHIFT, KeyDirection::Down);
+    assert_group(&wkb, &state, "completing alt+shift after set_layout");
+    update_both(&mut wkb, &mut state, LEFT_SHIFT, KeyDirection::Up);
+    update_both(&mut wkb, &mut state, ALT, KeyDirection::Up);
+    assert_group(&wkb, &state, "releasing alt+shift");
+    assert_eq!(wkb.active_layout_idx(), 0);
+}
+
 #[test]
 fn caps_toggle_switches_group_at_level_zero() {
     let (mut wkb, mut state) = states("grp:caps_toggle");
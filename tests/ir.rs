@@ -2,8 +2,8 @@
 
 use std::collections::BTreeMap;
 
-use wkb::ir::{self, IrError, LayoutFile, ModAction};
-use wkb::{ModType, NamedKey, WKB};
+use wkb::ir::{self, ComposeValue, IrError, LayoutBuilder, LayoutFile, ModAction};
+use wkb::{ComposeKey, KeyDirection, ModType, NamedKey, ALTGR, CAPS_LOCK, LEFT_SHIFT, WKB};
 
 include!("../test_data/layouts.rs");
 
@@ -26,7 +26,7 @@ fn sample_file() -> LayoutFile {
         caps_lock_keymap: BTreeMap::new(),
         caps_num_lock_keys: BTreeMap::new(),
         keysym_map: BTreeMap::new(),
-        compose: vec![(vec![COMPOSE, 'a', 'e'], 'æ')],
+        compose: vec![(vec![COMPOSE, 'a', 'e'], 'æ'.into())],
     }
 }
 
@@ -150,13 +150,63 @@ fn rejects_level_out_of_range() {
 #[test]
 fn rejects_empty_compose_sequence() {
     let mut file = sample_file();
-    file.compose.push((Vec::new(), 'x'));
+    file.compose.push((Vec::new(), 'x'.into()));
     assert!(matches!(
         file.validate(),
         Err(IrError::EmptyComposeSequence)
     ));
 }
 
+#[test]
+fn rejects_empty_compose_output() {
+    let mut file = sample_file();
+    file.compose
+        .push((vec![COMPOSE, 'a'], ComposeValue::Text(String::new())));
+    assert!(matches!(file.validate(), Err(IrError::NullComposeOutput)));
+}
+
+#[test]
+fn compose_string_outputs_roundtrip() {
+    let mut file = sample_file();
+    file.compose.push((
+        vec![COMPOSE, 'a', 'b'],
+        ComposeValue::Text("me@example.com".into()),
+    ));
+    file.compose.push((
+        vec![COMPOSE, 'b', 'a'],
+        ComposeValue::WithKeysym("æ".into(), 0xe6),
+    ));
+    let text = file.to_ron_string().unwrap();
+    assert!(text.contains("        (['·', 'a', 'e'], 'æ'),\n"));
+    assert!(text.contains("        (['·', 'a', 'b'], \"me@example.com\"),\n"));
+    assert!(text.contains("        (['·', 'b', 'a'], (\"æ\", 230)),\n"));
+    assert_eq!(LayoutFile::from_ron_str(&text).unwrap(), file);
+}
+
+#[test]
+fn compose_string_output_is_emitted() {
+    let mut file = sample_file();
+    file.compose = vec![(
+        vec![COMPOSE, 'a', 'b'],
+        ComposeValue::Text("me@example.com".into()),
+    )];
+    let mut wkb = WKB::new_from_layouts(vec![file]).unwrap();
+    wkb.set_compose_key(127);
+    let mut result = None;
+    for code in [127, 0, 1] {
+        result = Some(wkb.key_event(code, wkb::KeyDirection::Down));
+        wkb.key_event(code, wkb::KeyDirection::Up);
+    }
+    let result = result.unwrap();
+    assert_eq!(result.text, None);
+    assert_eq!(result.commit_text().as_deref(), Some("me@example.com"));
+    let Some(wkb::ComposeState::Finished(output)) = result.compose else {
+        panic!("compose did not finish");
+    };
+    assert_eq!(&*output.text, "me@example.com");
+    assert_eq!(output.keysym, None);
+}
+
 #[test]
 fn rejects_empty_modifier_actions() {
     let mut file = sample_file();
@@ -302,6 +352,75 @@ fn new_from_layouts_supports_multiple_groups() {
     assert_eq!(wkb.key_char(21), Some('z')); // QWERTZ: the 'y' key produces 'z'
 }
 
+#[test]
+fn builder_produces_working_layout() {
+    let mut wkb = LayoutBuilder::new("custom")
+        .key(30, ['a', 'A', 'æ'])
+        .key(2, ['1', '!'])
+        .named_key(1, [NamedKey::Escape])
+        .key(1, ['\u{1b}'])
+        .modifier(LEFT_SHIFT, ModAction::Press(ModType::Level2))
+        .modifier(ALTGR, ModAction::Press(ModType::Level3))
+        .modifier(CAPS_LOCK, ModAction::Lock(ModType::Caps))
+        .caps_lock_key(30, 0, 'A')
+        .repeat(2, false)
+        .compose(&[ComposeKey::Char('a'), ComposeKey::Char('1')], 'ā')
+        .build_wkb()
+        .unwrap();
+
+    assert_eq!(wkb.layout_name(0), Some("custom"));
+    assert_eq!(wkb.key_char(30), Some('a'));
+    assert_eq!(wkb.logical_key(1), wkb::LogicalKey::Named(NamedKey::Escape));
+    assert!(wkb.key_repeats(30));
+    assert!(wkb.key_repeats(1));
+    assert!(!wkb.key_repeats(2));
+    assert!(!wkb.key_repeats(LEFT_SHIFT));
+
+    wkb.press_key(LEFT_SHIFT);
+    assert_eq!(wkb.key_char(30), Some('A'));
+    wkb.release_key(LEFT_SHIFT);
+
+    // A two-level key falls back to its base level under AltGr.
+    wkb.press_key(ALTGR);
+    assert_eq!(wkb.key_char(30), Some('æ'));
+    assert_eq!(wkb.key_char(2), Some('1'));
+    wkb.press_key(LEFT_SHIFT);
+    assert_eq!(wkb.key_char(2), Some('!'));
+    wkb.release_key(LEFT_SHIFT);
+    wkb.release_key(ALTGR);
+
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    assert_eq!(wkb.key_char(30), Some('A'));
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+
+    wkb.key_event(30, KeyDirection::Down);
+    wkb.key_event(30, KeyDirection::Up);
+    let result = wkb.key_event(2, KeyDirection::Down);
+    assert_eq!(result.commit_text().as_deref(), Some("ā"));
+}
+
+#[test]
+fn builder_output_roundtrips() {
+    let file = LayoutBuilder::new("custom")
+        .key(30, ['a', 'A'])
+        .modifier_level(ALTGR, 0, ModAction::Press(ModType::Level3))
+        .modifier_level(ALTGR, 1, ModAction::Lock(ModType::Level3))
+        .compose(&[ComposeKey::Compose, ComposeKey::Keysym(0x61)], 'x')
+        .build()
+        .unwrap();
+    assert_eq!(file.repeat_keys, [30]);
+    assert_eq!(file.compose, [(vec![COMPOSE, 'a'], 'x'.into())]);
+    let text = file.to_ron_string().unwrap();
+    assert_eq!(LayoutFile::from_ron_str(&text).unwrap(), file);
+
+    let err = LayoutBuilder::new("custom").key_level(30, 8, 'a').build();
+    assert!(matches!(err, Err(IrError::LevelOutOfRange(8))));
+    let err = LayoutBuilder::new("").key(30, ['a']).build();
+    assert!(matches!(err, Err(IrError::EmptyLayoutName)));
+}
+
 #[test]
 fn list_layouts_finds_the_registry() {
     let layouts = list_layouts();
@@ -0,0 +1,129 @@
+//! Tests for the timestamp-driven key repeat timer.
+
+use wkb::{ComposeState, KeyDirection, KeyRepeat, LEFT_SHIFT, WKB};
+
+const KEY_A: u32 = 30;
+const KEY_S: u32 = 31;
+const KEY_APOSTROPHE: u32 = 40;
+
+fn us() -> WKB {
+    WKB::new_from_names("", "", "us", "", None).unwrap()
+}
+
+fn texts(results: &[wkb::KeyResult]) -> String {
+    results.iter().filter_map(|result| result.text).collect()
+}
+
+#[test]
+fn repeats_after_delay_at_rate() {
+    let mut wkb = us();
+    let mut repeat = KeyRepeat::new(25, 600);
+    let result = repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, 1000);
+    assert_eq!(result.text, Some('a'));
+    assert_eq!(repeat.repeating_key(), Some(KEY_A));
+    assert_eq!(repeat.next_repeat(), Some(1600));
+
+    assert!(repeat.repeat(&mut wkb, 1599).is_empty());
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 1600)), "a");
+    assert_eq!(repeat.next_repeat(), Some(1640));
+    // Late calls catch up.
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 1760)), "aaaa");
+    assert_eq!(repeat.next_repeat(), Some(1800));
+
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Up, 1790);
+    assert_eq!(repeat.repeating_key(), None);
+    assert_eq!(repeat.next_repeat(), None);
+    assert!(repeat.repeat(&mut wkb, 5000).is_empty());
+}
+
+#[test]
+fn late_calls_drop_missed_repeats() {
+    let mut wkb = us();
+    let mut repeat = KeyRepeat::new(25, 600);
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, 0);
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 60_000)), "aaaaaaaa");
+    // The next repeat follows `now` at the rate.
+    assert_eq!(repeat.next_repeat(), Some(60_040));
+    assert!(repeat.repeat(&mut wkb, 60_039).is_empty());
+    assert_eq!(repeat.repeat(&mut wkb, 60_040).len(), 1);
+    assert_eq!(repeat.next_repeat(), Some(60_080));
+}
+
+#[test]
+fn new_key_takes_over() {
+    let mut wkb = us();
+    let mut repeat = KeyRepeat::new(10, 500);
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, 0);
+    repeat.key_event(&mut wkb, KEY_S, KeyDirection::Down, 300);
+    assert_eq!(repeat.repeating_key(), Some(KEY_S));
+    assert_eq!(repeat.next_repeat(), Some(800));
+    // Releasing the earlier key does not stop the repeat.
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Up, 400);
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 900)), "ss");
+
+    repeat.cancel();
+    assert_eq!(repeat.repeating_key(), None);
+}
+
+#[test]
+fn modifiers_change_repeated_text() {
+    let mut wkb = us();
+    let mut repeat = KeyRepeat::new(10, 500);
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, 0);
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 500)), "a");
+    repeat.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Down, 550);
+    assert_eq!(repeat.repeating_key(), Some(KEY_A));
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 700)), "AA");
+    repeat.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Up, 750);
+    assert_eq!(texts(&repeat.repeat(&mut wkb, 800)), "a");
+}
+
+#[test]
+fn repeat_info_changes() {
+    let mut wkb = us();
+    let mut repeat = KeyRepeat::new(0, 500);
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, 0);
+    assert_eq!(repeat.repeating_key(), None);
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Up, 10);
+
+    repeat.set_repeat_info(10, 500);
+    assert_eq!((repeat.rate(), repeat.delay()), (10, 500));
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, 1000);
+    assert_eq!(repeat.repeat(&mut wkb, 1600).len(), 2);
+    // The next repeat keeps its time and the rest follow the new rate.
+    repeat.set_repeat_info(50, 200);
+    assert_eq!(repeat.next_repeat(), Some(1700));
+    assert_eq!(repeat.repeat(&mut wkb, 1760).len(), 4);
+
+    repeat.set_repeat_info(0, 200);
+    assert_eq!(repeat.repeating_key(), None);
+
+    // Non-repeating keys never start a repeat.
+    repeat.set_repeat_info(10, 500);
+    repeat.key_event(&mut wkb, LEFT_SHIFT, KeyDirection::Down, 2000);
+    assert_eq!(repeat.repeating_key(), None);
+}
+
+#[test]
+fn timestamps_wrap() {
+    let mut wkb = us();
+    let mut repeat = KeyRepeat::new(10, 500);
+    repeat.key_event(&mut wkb, KEY_A, KeyDirection::Down, u32::MAX - 99);
+    assert_eq!(repeat.next_repeat(), Some(400));
+    assert!(repeat.repeat(&mut wkb, u32::MAX).is_empty());
+    assert_eq!(repeat.repeat(&mut wkb, 500).len(), 2);
+}
+
+#[test]
+fn repeats_advance_compose() {
+    let mut wkb = WKB::new_from_names("", "", "us", "intl", None).unwrap();
+    let mut repeat = KeyRepeat::new(10, 500);
+    let result = repeat.key_event(&mut wkb, KEY_APOSTROPHE, KeyDirection::Down, 0);
+    assert!(matches!(result.compose, Some(ComposeState::Composing(_))));
+    let results = repeat.repeat(&mut wkb, 500);
+    assert!(matches!(
+        results[0].compose,
+        Some(ComposeState::Finished(_))
+    ));
+    assert_eq!(results[0].text, Some('´'));
+}
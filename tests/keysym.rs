@@ -0,0 +1,136 @@
+//! Tests for the public `Keysym` type and keysym lookups, compared with xkbcommon.
+
+use test_case::test_matrix;
+use wkb::{KeyDirection, Keysym, NamedKey, LEFT_SHIFT, WKB};
+use xkbcommon::xkb::{self as xkbcmn, Keycode};
+
+mod common;
+use common::{activate_locks, update_both, xkb_new_from_names};
+
+const NAMES: &[&str] = &[
+    "a",
+    "A",
+    "Return",
+    "KP_1",
+    "dead_grave",
+    "XF86AudioMute",
+    "ISO_Level3_Shift",
+    "EuroSign",
+    "U20AC",
+    "U0041",
+    "0x1008ff13",
+    "Greek_OMEGA",
+];
+
+#[test]
+fn names_match_xkbcommon() {
+    for &name in NAMES {
+        let xkb = xkbcmn::keysym_from_name(name, xkbcmn::KEYSYM_NO_FLAGS);
+        let keysym = Keysym::from_name(name).unwrap_or_default();
+        assert_eq!(keysym.raw(), xkb.raw(), "from_name({name})");
+        assert_eq!(
+            keysym.to_string(),
+            xkbcmn::keysym_get_name(xkb),
+            "name of {name}"
+        );
+    }
+    assert_eq!(Keysym::from_name("NotAKeysym"), None);
+    assert_eq!(Keysym::from_name("return"), None);
+}
+
+#[test]
+fn char_conversions_match_xkbcommon() {
+    for ch in ['a', 'Z', '€', 'ß', 'Ω', '\u{1F600}'] {
+        let xkb = xkbcmn::utf32_to_keysym(ch as u32);
+        assert_eq!(Keysym::from_char(ch).raw(), xkb.raw(), "from_char({ch:?})");
+        assert_eq!(Keysym::from_char(ch).to_char(), Some(ch));
+    }
+    assert_eq!(
+        Keysym::from_name("Escape").unwrap().named_key(),
+        NamedKey::Escape
+    );
+    assert!(Keysym::from_name("KP_Enter").unwrap().is_keypad());
+    assert_eq!(Keysym::from_char('a').to_upper(), Keysym::from_char('A'));
+}
+
+fn assert_all_syms_match(wkb: &WKB, xkb: &xkbcmn::State, context: &str) {
+    for code in 1..200 {
+        let expected = xkb.key_get_one_sym(Keycode::new(code + 8)).raw();
+        let actual = wkb.key_sym(code).raw();
+        assert_eq!(actual, expected, "{context}: key {code}");
+    }
+}
+
+#[test_matrix(["us", "de"], [0, 1, 2, 3])]
+fn key_sym_matches_xkbcommon(locale: &str, locks: u8) {
+    let mut wkb = WKB::new_from_names("", "", locale, "", None).unwrap();
+    let mut xkb = xkb_new_from_names(locale, "");
+    activate_locks(&mut wkb, &mut xkb, locks);
+    assert_all_syms_match(&wkb, &xkb, "base");
+
+    update_both(&mut wkb, &mut xkb, LEFT_SHIFT, KeyDirection::Down);
+    assert_all_syms_match(&wkb, &xkb, "shift");
+}
+
+#[test]
+fn level_sym_reads_keymap_levels() {
+    let wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    assert_eq!(wkb.level_sym(30, 0, 0), Keysym::from_name("a").unwrap());
+    assert_eq!(wkb.level_sym(30, 0, 1), Keysym::from_name("A").unwrap());
+    assert_eq!(wkb.level_sym(1, 0, 0), Keysym::from_name("Escape").unwrap());
+}
+
+#[test]
+fn key_sym_survives_layout_file_roundtrip() {
+    let wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let file = wkb.export_layout(0).unwrap();
+    let loaded = WKB::new_from_layouts(vec![file]).unwrap();
+    for code in [1, 16, 28, 30, 57] {
+        assert_eq!(loaded.key_sym(code), wkb.key_sym(code), "key {code}");
+    }
+}
+
+const MULTI_KEYSYM_KEYMAP: &str = r#"xkb_keymap {
+    xkb_keycodes { include "evdev" };
+    xkb_types { include "complete" };
+    xkb_compat { include "complete" };
+    xkb_symbols {
+        include "pc+us"
+        key <AC01> { [ { a, e }, { A, B, C } ] };
+    };
+};"#;
+
+#[test]
+fn key_syms_match_xkbcommon() {
+    let mut wkb = WKB::new_from_string(MULTI_KEYSYM_KEYMAP).unwrap();
+    let context = xkbcmn::Context::new(xkbcmn::CONTEXT_NO_FLAGS);
+    let keymap = xkbcmn::Keymap::new_from_string(
+        &context,
+        MULTI_KEYSYM_KEYMAP.to_string(),
+        xkbcmn::KEYMAP_FORMAT_TEXT_V1,
+        xkbcmn::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .unwrap();
+    let mut xkb = xkbcmn::State::new(&keymap);
+    let check = |wkb: &WKB, xkb: &xkbcmn::State, context: &str| {
+        for code in 1..200 {
+            let expected: Vec<u32> = xkb
+                .key_get_syms(Keycode::new(code + 8))
+                .iter()
+                .map(|sym| sym.raw())
+                .collect();
+            let actual: Vec<u32> = wkb.key_syms(code).iter().map(|sym| sym.raw()).collect();
+            assert_eq!(actual, expected, "{context}: key {code}");
+        }
+    };
+    check(&wkb, &xkb, "base");
+    update_both(&mut wkb, &mut xkb, LEFT_SHIFT, KeyDirection::Down);
+    check(&wkb, &xkb, "shift");
+
+    let names = |syms: &[Keysym]| syms.iter().map(Keysym::to_string).collect::<Vec<_>>();
+    assert_eq!(names(wkb.level_syms(30, 0, 0)), ["a", "e"]);
+    assert_eq!(names(wkb.level_syms(30, 0, 1)), ["A", "B", "C"]);
+    assert_eq!(names(wkb.level_syms(31, 0, 0)), ["s"]);
+    assert!(wkb.level_syms(30, 0, 2).is_empty());
+    assert_eq!(wkb.level_sym(30, 0, 1), Keysym::from_name("A").unwrap());
+}
@@ -0,0 +1,200 @@
+//! Tests for keymap-aware modifier names, indices and virtual modifier
+//! resolution, compared with xkbcommon.
+
+use test_case::test_case;
+use wkb::{Keymap, WKB};
+use xkbcommon::xkb::{self as xkbcmn, Keycode};
+
+mod common;
+use common::xkb_new_keymap_from_names;
+
+const KEY_Q: u32 = 16;
+const RALT: u32 = 100;
+const MOD4: u32 = 1 << 6;
+const NUM_LOCK: u32 = 69;
+const KP1: u32 = 79;
+
+/// LevelThree mapped to Mod4 instead of the usual Mod5.
+fn keymap_level3_on_mod4() -> String {
+    r#"xkb_keymap {
+    xkb_keycodes {
+        minimum = 8;
+        maximum = 255;
+        <RALT> = 108;
+        <AD01> = 24;
+    };
+    xkb_types {
+        virtual_modifiers LevelThree = Mod4;
+        type "FOUR_LEVEL" {
+            modifiers = Shift+LevelThree;
+            map[Shift] = Level2;
+            map[LevelThree] = Level3;
+            map[Shift+LevelThree] = Level4;
+        };
+    };
+    xkb_compat { };
+    xkb_symbols {
+        key <RALT> { [ ISO_Level3_Shift ] };
+        key <AD01> { type = "FOUR_LEVEL", [ q, Q, at, Greek_OMEGA ] };
+        modifier_map Mod4 { <RALT> };
+    };
+};"#
+    .into()
+}
+
+/// NumLock mapped to the real modifier `real` instead of the usual Mod2.
+fn keymap_num_lock_on(real: &str) -> String {
+    format!(
+        r#"xkb_keymap {{
+    xkb_keycodes {{
+        minimum = 8;
+        maximum = 255;
+        <NMLK> = 77;
+        <KP1> = 87;
+    }};
+    xkb_types {{
+        virtual_modifiers NumLock;
+        type "KEYPAD" {{
+            modifiers = Shift+NumLock;
+            map[Shift] = Level2;
+            map[NumLock] = Level2;
+        }};
+    }};
+    xkb_compat {{
+        virtual_modifiers NumLock;
+        interpret Num_Lock {{
+            virtualModifier = NumLock;
+            action = LockMods(modifiers = NumLock);
+        }};
+    }};
+    xkb_symbols {{
+        key <NMLK> {{ [ Num_Lock ] }};
+        key <KP1> {{ type = "KEYPAD", [ KP_End, KP_1 ] }};
+        modifier_map {real} {{ <NMLK> }};
+    }};
+}};"#
+    )
+}
+
+#[test_case("us"; "us")]
+#[test_case("de"; "de")]
+fn mod_indices_match_xkbcommon(locale: &str) {
+    let keymap = Keymap::new_from_names("", "", locale, "", None).unwrap();
+    let xkb = xkb_new_keymap_from_names(locale, "");
+    for name in [
+        "Shift", "Lock", "Control", "Mod1", "Mod2", "Mod3", "Mod4", "Mod5",
+    ] {
+        assert_eq!(
+            keymap.mod_index(name),
+            Some(xkb.mod_get_index(name) as usize),
+            "{name}"
+        );
+    }
+    for idx in 0..keymap.num_mods() {
+        let name = keymap.mod_name(idx).unwrap();
+        assert_eq!(keymap.mod_index(name), Some(idx));
+    }
+    assert_eq!(keymap.mod_index("NotAModifier"), None);
+    assert_eq!(keymap.mod_name(keymap.num_mods()), None);
+    assert_eq!(keymap.mod_mask("Alt"), 1 << 3);
+    assert_eq!(keymap.mod_mask("NumLock"), 1 << 4);
+    assert_eq!(keymap.mod_mask("LevelThree"), 1 << 7);
+}
+
+#[test]
+fn level_three_follows_keymap_mapping() {
+    let keymap = keymap_level3_on_mod4();
+    let mut wkb = WKB::new_from_string(&keymap).unwrap();
+    assert_eq!(wkb.keymap().mod_mask("LevelThree"), MOD4);
+
+    wkb.press_key(RALT);
+    assert_eq!(wkb.raw_modifiers().depressed, MOD4);
+    assert_eq!(wkb.key_char(KEY_Q), Some('@'));
+    assert!(wkb.mod_name_is_active("LevelThree"));
+    assert!(wkb.mod_name_is_active("Mod4"));
+    assert!(!wkb.mod_name_is_active("Mod5"));
+    wkb.release_key(RALT);
+
+    let context = xkbcmn::Context::new(xkbcmn::CONTEXT_NO_FLAGS);
+    let xkb_keymap = xkbcmn::Keymap::new_from_string(
+        &context,
+        keymap,
+        xkbcmn::KEYMAP_FORMAT_TEXT_V1,
+        xkbcmn::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .unwrap();
+    let mut xkb = xkbcmn::State::new(&xkb_keymap);
+    xkb.update_mask(MOD4, 0, 0, 0, 0, 0);
+    wkb.update_modifiers(MOD4, 0, 0, 0);
+    let expected = xkb.key_get_utf8(Keycode::new(KEY_Q + 8)).chars().next();
+    assert_eq!(wkb.key_char(KEY_Q), expected);
+}
+
+#[test]
+fn virtual_modifier_bits_resolve_to_real_modifiers() {
+    let mut wkb = WKB::new_from_string(&keymap_level3_on_mod4()).unwrap();
+    let level3 = wkb.keymap().mod_index("LevelThree").unwrap();
+    assert!(level3 >= 8);
+
+    wkb.update_modifiers(1 << level3, 0, 0, 0);
+    assert_eq!(wkb.raw_modifiers().depressed, MOD4);
+    assert_eq!(wkb.key_char(KEY_Q), Some('@'));
+}
+
+#[test]
+fn alt_and_logo_are_tracked_from_modifier_map() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.press_key(wkb::LEFT_SHIFT);
+    assert!(!wkb.alt());
+    wkb.press_key(56);
+    assert!(wkb.alt());
+    assert!(wkb.mod_name_is_active("Alt"));
+    assert_eq!(wkb.key_char(KEY_Q), None);
+    wkb.release_key(56);
+
+    wkb.press_key(125);
+    assert!(wkb.logo());
+    assert!(wkb.mod_name_is_active("Mod4"));
+    assert!(!wkb.mod_name_is_active("Alt"));
+}
+
+#[test]
+fn layout_files_use_default_modifiers() {
+    let wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let loaded = WKB::new_from_layouts(vec![wkb.export_layout(0).unwrap()]).unwrap();
+    let keymap = loaded.keymap();
+    assert_eq!(keymap.mod_index("Mod5"), Some(7));
+    assert_eq!(keymap.mod_mask("LevelThree"), 1 << 7);
+    assert_eq!(keymap.mod_mask("Super"), MOD4);
+}
+
+#[test_case("Mod2", 1 << 4; "mod2")]
+#[test_case("Mod3", 1 << 5; "mod3")]
+#[test_case("Mod4", MOD4; "mod4")]
+fn num_lock_follows_keymap_mapping(real: &str, mask: u32) {
+    let keymap = keymap_num_lock_on(real);
+    let mut wkb = WKB::new_from_string(&keymap).unwrap();
+    assert_eq!(wkb.keymap().mod_mask("NumLock"), mask);
+
+    let context = xkbcmn::Context::new(xkbcmn::CONTEXT_NO_FLAGS);
+    let xkb_keymap = xkbcmn::Keymap::new_from_string(
+        &context,
+        keymap,
+        xkbcmn::KEYMAP_FORMAT_TEXT_V1,
+        xkbcmn::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .unwrap();
+    let mut xkb = xkbcmn::State::new(&xkb_keymap);
+    for direction in [xkbcmn::KeyDirection::Down, xkbcmn::KeyDirection::Up] {
+        xkb.update_key(Keycode::new(NUM_LOCK + 8), direction);
+    }
+    wkb.press_key(NUM_LOCK);
+    wkb.release_key(NUM_LOCK);
+    assert!(wkb.num_lock());
+    assert_eq!(wkb.raw_modifiers().locked, mask);
+
+    let keycode = Keycode::new(KP1 + 8);
+    assert_eq!(wkb.key_sym(KP1).raw(), xkb.key_get_one_sym(keycode).raw());
+    assert_eq!(wkb.key_char(KP1), xkb.key_get_utf8(keycode).chars().next());
+    assert_eq!(wkb.key_char(KP1), Some('1'));
+}
@@ -1,6 +1,9 @@
 //! Physical vs logical keys, state-change reporting, and multi-symbol parse errors.
 
-use wkb::{NamedKey, PhysicalKey, StateChanges, CAPS_LOCK, LEFT_SHIFT, NUM_LOCK, WKB};
+use wkb::{
+    KeyDirection, LogicalKey, NamedKey, PhysicalKey, StateChanges, CAPS_LOCK, LEFT_SHIFT, NUM_LOCK,
+    WKB,
+};
 
 const KEY_Q: u32 = 16;
 const KEY_ESC: u32 = 1;
@@ -255,3 +258,81 @@ fn num_lock_led_follows_leds_state() {
     let press = wkb.press_key(NUM_LOCK);
     assert_eq!(press.leds_updated, before != wkb.leds_state());
 }
+
+fn keymap_level3_latch_four_level() -> String {
+    r#"xkb_keymap {
+    xkb_keycodes {
+        minimum = 8;
+        maximum = 255;
+        <RALT> = 108;
+        <AD01> = 24;
+    };
+    xkb_types {
+        virtual_modifiers LevelThree;
+        type "FOUR_LEVEL" {
+            modifiers = Shift+LevelThree;
+            map[Shift] = Level2;
+            map[LevelThree] = Level3;
+            map[Shift+LevelThree] = Level4;
+        };
+    };
+    xkb_compat { };
+    xkb_symbols {
+        key <RALT> { [ ISO_Level3_Latch ] };
+        key <AD01> { type = "FOUR_LEVEL", [ q, Q, at, Greek_OMEGA ] };
+        modifier_map Mod5 { <RALT> };
+    };
+};"#
+    .into()
+}
+
+#[test]
+fn logical_key_prefers_named_then_character() {
+    let mut wkb = us();
+    assert_eq!(
+        wkb.logical_key(KEY_ESC),
+        LogicalKey::Named(NamedKey::Escape)
+    );
+    assert_eq!(wkb.logical_key(KEY_Q), LogicalKey::Character('q'));
+    assert_eq!(wkb.logical_key(0), LogicalKey::Unidentified);
+
+    wkb.press_key(KEY_LEFTCTRL);
+    assert_eq!(wkb.key_char(KEY_Q), None);
+    assert_eq!(wkb.logical_key(KEY_Q), LogicalKey::Character('q'));
+}
+
+#[test]
+fn key_event_resolves_latched_level_before_consuming_it() {
+    let mut wkb = WKB::new_from_string(&keymap_level3_latch_four_level()).unwrap();
+    let ralt = 100;
+    let latch = wkb.key_event(ralt, KeyDirection::Down);
+    assert!(latch.changes.is_modifier);
+    assert!(latch.changes.modifiers_updated);
+    assert_eq!(latch.text, None);
+    wkb.key_event(ralt, KeyDirection::Up);
+
+    let press = wkb.key_event(KEY_Q, KeyDirection::Down);
+    assert_eq!(press.physical_key, PhysicalKey::KeyQ);
+    assert_eq!(press.logical_key, LogicalKey::Character('@'));
+    assert_eq!(press.text, Some('@'));
+    assert!(press.changes.modifiers_updated);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+
+    let release = wkb.key_event(KEY_Q, KeyDirection::Up);
+    assert_eq!(release.logical_key, LogicalKey::Character('q'));
+    assert_eq!(release.text, None);
+    assert_eq!(release.compose, None);
+    assert_eq!(release.changes, StateChanges::default());
+}
+
+#[test]
+fn repeat_key_reports_text_without_state_change() {
+    let mut wkb = us();
+    wkb.key_event(LEFT_SHIFT, KeyDirection::Down);
+    let press = wkb.key_event(KEY_Q, KeyDirection::Down);
+    let repeat = wkb.repeat_key(KEY_Q);
+    assert_eq!(repeat.text, Some('Q'));
+    assert_eq!(repeat.logical_key, press.logical_key);
+    assert_eq!(repeat.repeats, wkb.key_repeats(KEY_Q));
+    assert_eq!(repeat.changes, StateChanges::default());
+}
@@ -0,0 +1,87 @@
+//! Tests for inspecting and releasing held keys.
+
+use wkb::{AccessX, AccessXConfig, GroupChange, GroupKind, StickyKeys, CAPS_LOCK, LEFT_SHIFT, WKB};
+
+#[cfg(feature = "compose")]
+use wkb::ComposeState;
+
+const KEY_A: u32 = 30;
+const KEY_S: u32 = 31;
+const KEY_APOSTROPHE: u32 = 40;
+const LOGO: u32 = 125;
+
+#[test]
+fn tracks_held_keys() {
+    let mut wkb = WKB::new_from_names("", "", "us,ru", "", None).unwrap();
+    assert_eq!(wkb.pressed_keys().count(), 0);
+    wkb.press_key(KEY_S);
+    wkb.press_key(LEFT_SHIFT);
+    wkb.press_key(KEY_A);
+    assert!(wkb.is_key_down(KEY_A));
+    assert!(wkb.is_key_down(LEFT_SHIFT));
+    assert_eq!(
+        wkb.pressed_keys().collect::<Vec<_>>(),
+        [KEY_A, KEY_S, LEFT_SHIFT]
+    );
+
+    wkb.release_key(KEY_S);
+    assert!(!wkb.is_key_down(KEY_S));
+
+    // Layout changes from the compositor keep held keys.
+    wkb.update_modifiers(1, 0, 0, 1);
+    assert_eq!(wkb.pressed_keys().collect::<Vec<_>>(), [KEY_A, LEFT_SHIFT]);
+    wkb.set_layout(0).unwrap();
+    assert!(wkb.is_key_down(KEY_A));
+}
+
+#[test]
+fn release_all_returns_to_neutral() {
+    let mut wkb = WKB::new_from_names("", "", "us", "intl", None).unwrap();
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    wkb.press_key(KEY_APOSTROPHE);
+    #[cfg(feature = "compose")]
+    assert!(matches!(
+        wkb.compose(KEY_APOSTROPHE),
+        Some(ComposeState::Composing(_))
+    ));
+    wkb.press_key(LEFT_SHIFT);
+    wkb.press_key(KEY_A);
+
+    let released = wkb.release_all();
+    let codes: Vec<u32> = released.iter().map(|(code, _)| *code).collect();
+    assert_eq!(codes, [KEY_A, KEY_APOSTROPHE, LEFT_SHIFT]);
+    assert!(released
+        .iter()
+        .any(|(_, changes)| changes.modifiers_updated));
+    assert_eq!(wkb.pressed_keys().count(), 0);
+    assert_eq!(wkb.raw_modifiers().depressed, 0);
+    assert!(wkb.leds_state().caps_lock);
+    #[cfg(feature = "compose")]
+    assert_eq!(wkb.compose_preedit(), None);
+
+    assert!(wkb.release_all().is_empty());
+}
+
+#[test]
+fn release_all_clears_latches() {
+    let mut wkb = WKB::new_from_names("", "", "us,ru", "", None).unwrap();
+    AccessX::new(
+        &mut wkb,
+        AccessXConfig {
+            sticky_keys: Some(StickyKeys::default()),
+            ..Default::default()
+        },
+    );
+    wkb.set_group_key(LOGO, GroupKind::LatchOnRelease(GroupChange::Relative(1)));
+
+    // Releasing the held latch keys would latch Shift and the next layout.
+    wkb.press_key(LEFT_SHIFT);
+    wkb.press_key(LOGO);
+    let released = wkb.release_all();
+    assert_eq!(released.len(), 2);
+    assert!(released[1].1.modifiers_updated);
+    assert_eq!(wkb.raw_modifiers().latched, 0);
+    assert_eq!(wkb.active_layout_idx(), 0);
+    assert_eq!(wkb.key_char(KEY_A), Some('a'));
+}
@@ -0,0 +1,118 @@
+//! Tests for per-state key remapping.
+
+use std::sync::Arc;
+
+use wkb::{GroupKind, Keymap, ModType, NamedKey, WkbError, CAPS_LOCK, LEFT_SHIFT, WKB};
+
+const LEFT_CTRL: u32 = 29;
+const ALT: u32 = 56;
+const LOGO: u32 = 125;
+const KEY_A: u32 = 30;
+const KEY_S: u32 = 31;
+const KEY_Q: u32 = 16;
+const KEY_SPACE: u32 = 57;
+
+fn us() -> WKB {
+    WKB::new_from_names("", "", "us", "", None).unwrap()
+}
+
+#[test]
+fn caps_lock_becomes_control() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us", "", None).unwrap());
+    let mut wkb = WKB::new(keymap.clone());
+    let other = WKB::new(keymap.clone());
+    wkb.remap_key(CAPS_LOCK, LEFT_CTRL).unwrap();
+
+    wkb.press_key(CAPS_LOCK);
+    assert!(wkb.mod_name_is_active("Control"));
+    assert_eq!(wkb.named_key(CAPS_LOCK), NamedKey::LeftControl);
+    wkb.release_key(CAPS_LOCK);
+    assert!(!wkb.mod_name_is_active("Control"));
+    assert!(!wkb.active_mod_type(ModType::Caps));
+
+    // Other states sharing the keymap keep Caps Lock.
+    assert!(!Arc::ptr_eq(wkb.keymap(), &keymap));
+    assert_eq!(other.named_key(CAPS_LOCK), NamedKey::CapsLock);
+
+    // Clients receive the remapped keymap.
+    let mut client = WKB::new_from_string(&wkb.as_xkb_string().unwrap()).unwrap();
+    client.press_key(CAPS_LOCK);
+    assert!(client.mod_name_is_active("Control"));
+    assert_eq!(client.named_key(CAPS_LOCK), NamedKey::LeftControl);
+
+    wkb.reset_remapping();
+    assert!(Arc::ptr_eq(wkb.keymap(), &keymap));
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    assert!(wkb.active_mod_type(ModType::Caps));
+}
+
+#[test]
+fn swapped_alt_and_logo() {
+    let mut wkb = us();
+    wkb.swap_keys(ALT, LOGO).unwrap();
+    assert_eq!(wkb.named_key(ALT), NamedKey::LeftSuper);
+    assert_eq!(wkb.named_key(LOGO), NamedKey::LeftAlt);
+    wkb.press_key(LOGO);
+    assert!(wkb.mod_name_is_active("Alt"));
+    assert!(!wkb.mod_name_is_active("Super"));
+    wkb.release_key(LOGO);
+    wkb.press_key(ALT);
+    assert!(wkb.mod_name_is_active("Super"));
+}
+
+#[test]
+fn reassigned_and_disabled_keys() {
+    let mut wkb = us();
+    wkb.set_key_char(0, KEY_A, 0, Some('ä')).unwrap();
+    wkb.set_key_char(0, KEY_SPACE, 0, Some('_')).unwrap();
+    wkb.set_key_named(0, KEY_S, 0, NamedKey::Escape).unwrap();
+    wkb.disable_key(KEY_Q).unwrap();
+
+    assert_eq!(wkb.key_char(KEY_A), Some('ä'));
+    assert_eq!(wkb.key_char(KEY_SPACE), Some('_'));
+    assert_eq!(wkb.named_key(KEY_S), NamedKey::Escape);
+    assert_eq!(wkb.key_char(KEY_Q), None);
+    assert!(!wkb.key_repeats(KEY_Q));
+
+    wkb.press_key(LEFT_SHIFT);
+    assert_eq!(wkb.key_char(KEY_A), Some('A'));
+    assert_eq!(wkb.key_char(KEY_SPACE), Some('_'));
+    wkb.release_key(LEFT_SHIFT);
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    assert_eq!(wkb.key_char(KEY_A), Some('Ä'));
+
+    let client = WKB::new_from_string(&wkb.as_xkb_string().unwrap()).unwrap();
+    assert_eq!(client.key_char(KEY_A), Some('ä'));
+    assert_eq!(client.key_char(KEY_SPACE), Some('_'));
+    assert_eq!(client.key_char(KEY_Q), None);
+
+    assert!(matches!(
+        wkb.set_key_char(1, KEY_A, 0, None),
+        Err(WkbError::InvalidLayout(1))
+    ));
+    assert!(matches!(
+        wkb.set_key_char(0, KEY_A, 8, None),
+        Err(WkbError::InvalidLevel(8))
+    ));
+    assert!(matches!(
+        wkb.remap_key(100_000, KEY_A),
+        Err(WkbError::InvalidKey(100_000))
+    ));
+}
+
+#[test]
+fn remapped_group_keys() {
+    let mut wkb = WKB::new_from_names("", "", "us,ru", "", None).unwrap();
+    wkb.set_group_key(LOGO, GroupKind::LockOnPress(wkb::GroupChange::Relative(1)));
+    wkb.remap_key(CAPS_LOCK, LOGO).unwrap();
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    assert_eq!(wkb.active_layout_idx(), 1);
+
+    wkb.disable_key(CAPS_LOCK).unwrap();
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    assert_eq!(wkb.active_layout_idx(), 1);
+}
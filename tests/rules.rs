@@ -0,0 +1,112 @@
+//! Keymaps resolved from RMLVO names through the XKB rules files, compared
+//! with xkbcommon.
+
+use test_case::test_case;
+use wkb::{KeyDirection, Keysym, ALTGR, CAPS_LOCK, LEFT_SHIFT, WKB};
+use xkbcommon::xkb::{self as xkbcmn, Keycode};
+
+mod common;
+use common::update_both;
+
+const MOD_CTRL: u32 = 1 << 2;
+const MOD4: u32 = 1 << 6;
+const LEFT_ALT: u32 = 56;
+
+fn new_both(
+    rules: &str,
+    model: &str,
+    layout: &str,
+    variant: &str,
+    options: Option<&str>,
+) -> (WKB, xkbcmn::State) {
+    let wkb = WKB::new_from_names(rules, model, layout, variant, options).unwrap();
+    let context = xkbcmn::Context::new(xkbcmn::CONTEXT_NO_FLAGS);
+    let keymap = xkbcmn::Keymap::new_from_names(
+        &context,
+        rules,
+        model,
+        layout,
+        variant,
+        options.map(Into::into),
+        xkbcmn::KEYMAP_COMPILE_NO_FLAGS,
+    )
+    .unwrap();
+    (wkb, xkbcmn::State::new(&keymap))
+}
+
+fn assert_all_syms_match(wkb: &WKB, xkb: &xkbcmn::State, context: &str) {
+    for code in 1..200 {
+        let expected = xkb.key_get_one_sym(Keycode::new(code + 8)).raw();
+        let actual = wkb.key_sym(code).raw();
+        assert_eq!(actual, expected, "{context}: key {code}");
+    }
+}
+
+#[test_case("evdev", "pc105", "us", "", None; "default")]
+#[test_case("evdev", "pc104", "us", "", None; "pc104")]
+#[test_case("evdev", "applealu_jis", "jp", "", None; "model keycodes")]
+#[test_case("base", "pc105", "de", "", None; "base rules")]
+#[test_case("evdev", "pc105", "ar", "", None; "renamed layout")]
+#[test_case("evdev", "pc105", "dvorak", "pl_basic", None; "layout variant rule")]
+#[test_case("evdev", "pc105", "us", "", Some("ctrl:nocaps"); "ctrl nocaps")]
+#[test_case("evdev", "pc105", "us", "", Some("compose:ralt,ctrl:swapcaps"); "two options")]
+#[test_case("evdev", "pc105", "de", "", Some("lv3:ralt_alt"); "layout option rule")]
+fn syms_match_xkbcommon(
+    rules: &str,
+    model: &str,
+    layout: &str,
+    variant: &str,
+    options: Option<&str>,
+) {
+    let (mut wkb, mut xkb) = new_both(rules, model, layout, variant, options);
+    assert_all_syms_match(&wkb, &xkb, "base");
+    update_both(&mut wkb, &mut xkb, LEFT_SHIFT, KeyDirection::Down);
+    assert_all_syms_match(&wkb, &xkb, "shift");
+}
+
+#[test]
+fn ctrl_nocaps_turns_caps_lock_into_control() {
+    let (mut wkb, mut xkb) = new_both("", "", "us", "", Some("ctrl:nocaps"));
+    update_both(&mut wkb, &mut xkb, CAPS_LOCK, KeyDirection::Down);
+    assert_eq!(wkb.raw_modifiers().depressed, MOD_CTRL);
+    assert_eq!(
+        wkb.raw_modifiers().depressed,
+        xkb.serialize_mods(xkbcmn::STATE_MODS_DEPRESSED)
+    );
+    update_both(&mut wkb, &mut xkb, CAPS_LOCK, KeyDirection::Up);
+    assert_eq!(wkb.raw_modifiers().locked, 0);
+}
+
+#[test]
+fn altwin_swap_lalt_lwin_moves_alt_to_mod4() {
+    let (mut wkb, mut xkb) = new_both("", "", "us", "", Some("altwin:swap_lalt_lwin"));
+    update_both(&mut wkb, &mut xkb, LEFT_ALT, KeyDirection::Down);
+    assert_eq!(wkb.raw_modifiers().depressed, MOD4);
+    assert_eq!(
+        wkb.raw_modifiers().depressed,
+        xkb.serialize_mods(xkbcmn::STATE_MODS_DEPRESSED)
+    );
+}
+
+#[test]
+fn compose_ralt_binds_multi_key() {
+    let (wkb, _) = new_both("", "", "us", "", Some("compose:ralt"));
+    assert_eq!(wkb.key_sym(ALTGR), Keysym::from_name("Multi_key").unwrap());
+}
+
+#[test]
+fn layout_list_resolves_every_group() {
+    let (wkb, xkb) = new_both("", "", "us,de", ",nodeadkeys", None);
+    let keymap = xkb.get_keymap();
+    assert_eq!(wkb.num_layouts(), keymap.num_layouts() as usize);
+    for code in 1..200 {
+        let expected = keymap.key_get_syms_by_level(Keycode::new(code + 8), 1, 0);
+        let expected = expected.first().map_or(0, |sym| sym.raw());
+        assert_eq!(wkb.level_sym(code, 1, 0).raw(), expected, "key {code}");
+    }
+}
+
+#[test]
+fn unknown_rules_file_fails() {
+    assert!(WKB::new_from_names("no-such-rules", "", "us", "", None).is_err());
+}
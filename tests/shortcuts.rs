@@ -0,0 +1,70 @@
+//! Tests for resolving shortcut keys on non-Latin layouts.
+
+use wkb::{Keysym, WkbError, LEFT_SHIFT, WKB};
+
+const KEY_1: u32 = 2;
+const KEY_Y: u32 = 21;
+const KEY_LEFTBRACE: u32 = 26;
+const KEY_C: u32 = 46;
+const KEY_ENTER: u32 = 28;
+
+#[test]
+fn cyrillic_falls_back_to_latin_layout() {
+    let mut wkb = WKB::new_from_names("", "", "us,ru", "", None).unwrap();
+    assert_eq!(wkb.shortcut_layout(), Some(0));
+    wkb.set_layout(1).unwrap();
+    assert_eq!(wkb.key_char(KEY_C), Some('с'));
+
+    let key = wkb.shortcut_key(KEY_C);
+    assert_eq!(key.layout, 0);
+    assert_eq!(key.ch, Some('c'));
+    assert_eq!(key.keysym, Keysym::from_char('c'));
+
+    // Keys typing ASCII and named keys stay on the active layout.
+    assert_eq!(wkb.shortcut_key(KEY_1).layout, 1);
+    assert_eq!(wkb.shortcut_key(KEY_1).ch, Some('1'));
+    assert_eq!(wkb.shortcut_key(KEY_ENTER).layout, 1);
+
+    wkb.press_key(LEFT_SHIFT);
+    assert_eq!(wkb.shortcut_key(KEY_C).ch, Some('C'));
+}
+
+#[test]
+fn first_latin_layout_is_found() {
+    let mut wkb = WKB::new_from_names("", "", "ru,gr,de", "", None).unwrap();
+    assert_eq!(wkb.shortcut_layout(), Some(2));
+    let key = wkb.shortcut_key(KEY_Y);
+    assert_eq!((key.layout, key.ch), (2, Some('z')));
+    wkb.set_layout(1).unwrap();
+    assert_eq!(wkb.shortcut_key(KEY_Y).ch, Some('z'));
+
+    // A Latin layout keeps its own non-ASCII keys.
+    wkb.set_layout(2).unwrap();
+    let key = wkb.shortcut_key(KEY_LEFTBRACE);
+    assert_eq!((key.layout, key.ch), (2, Some('ü')));
+}
+
+#[test]
+fn configured_shortcut_layout() {
+    let mut wkb = WKB::new_from_names("", "", "us,de,ru", "", None).unwrap();
+    wkb.set_layout(2).unwrap();
+    assert_eq!(wkb.shortcut_key(KEY_Y).ch, Some('y'));
+    wkb.set_shortcut_layout(Some(1)).unwrap();
+    assert_eq!(wkb.shortcut_layout(), Some(1));
+    let key = wkb.shortcut_key(KEY_Y);
+    assert_eq!((key.layout, key.ch), (1, Some('z')));
+    wkb.set_shortcut_layout(None).unwrap();
+    assert_eq!(wkb.shortcut_layout(), Some(0));
+    assert!(matches!(
+        wkb.set_shortcut_layout(Some(3)),
+        Err(WkbError::InvalidLayout(3))
+    ));
+}
+
+#[test]
+fn without_latin_layout() {
+    let wkb = WKB::new_from_names("", "", "ru", "", None).unwrap();
+    assert_eq!(wkb.shortcut_layout(), None);
+    let key = wkb.shortcut_key(KEY_C);
+    assert_eq!((key.layout, key.ch), (0, Some('с')));
+}
@@ -0,0 +1,119 @@
+//! Tests for saving and restoring keyboard state.
+
+use std::sync::Arc;
+
+use wkb::{
+    AccessX, AccessXConfig, ComposeState, GroupChange, GroupKind, Keymap, StateSnapshot,
+    StickyKeys, WkbError, CAPS_LOCK, LEFT_SHIFT, WKB,
+};
+
+const KEY_A: u32 = 30;
+const KEY_E: u32 = 18;
+const KEY_APOSTROPHE: u32 = 40;
+const LOGO: u32 = 125;
+
+fn roundtrip(snapshot: &StateSnapshot) -> StateSnapshot {
+    ron::from_str(&ron::to_string(snapshot).unwrap()).unwrap()
+}
+
+#[test]
+fn restores_modifiers_and_compose() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us", "intl", None).unwrap());
+    let mut wkb = WKB::new(keymap.clone());
+    wkb.press_key(CAPS_LOCK);
+    wkb.release_key(CAPS_LOCK);
+    wkb.press_key(LEFT_SHIFT);
+    wkb.press_key(KEY_APOSTROPHE);
+    wkb.compose(KEY_APOSTROPHE);
+    wkb.release_key(KEY_APOSTROPHE);
+
+    let snapshot = roundtrip(&wkb.snapshot());
+    assert_eq!(snapshot, wkb.snapshot());
+    assert_eq!(snapshot.version(), StateSnapshot::VERSION);
+
+    let mut restored = WKB::new(keymap);
+    restored.restore(&snapshot).unwrap();
+    assert_eq!(restored.snapshot(), snapshot);
+    assert_eq!(restored.raw_modifiers(), wkb.raw_modifiers());
+    assert_eq!(restored.leds_state(), wkb.leds_state());
+    assert_eq!(restored.compose_preedit(), wkb.compose_preedit());
+
+    // The held Shift key is released like on the original state.
+    restored.release_key(LEFT_SHIFT);
+    assert_eq!(restored.key_char(KEY_A), Some('A'));
+    match restored.compose(KEY_E) {
+        Some(ComposeState::Finished(output)) => assert_eq!(output.char(), Some('Ë')),
+        state => panic!("unexpected {state:?}"),
+    }
+}
+
+#[test]
+fn restores_group_state() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us,ru", "", None).unwrap());
+    let mut wkb = WKB::new(keymap.clone());
+    let tap = GroupKind::Tap(GroupChange::Relative(1));
+    wkb.set_group_key(LOGO, tap);
+    wkb.press_key(LOGO);
+
+    let mut restored = WKB::new(keymap.clone());
+    restored.set_group_key(LOGO, tap);
+    restored.restore(&roundtrip(&wkb.snapshot())).unwrap();
+    restored.release_key(LOGO);
+    assert_eq!(restored.active_layout_idx(), 1);
+    assert_eq!(restored.key_char(KEY_A), Some('ф'));
+
+    // The chord needs the same group keys.
+    let mut other = WKB::new(keymap);
+    wkb.press_key(LOGO);
+    assert!(matches!(
+        other.restore(&wkb.snapshot()),
+        Err(WkbError::SnapshotMismatch)
+    ));
+}
+
+#[test]
+fn rejects_other_keymaps_and_versions() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.press_key(LEFT_SHIFT);
+    let snapshot = wkb.snapshot();
+
+    let mut other = WKB::new_from_names("", "", "us,de", "", None).unwrap();
+    assert!(matches!(
+        other.restore(&snapshot),
+        Err(WkbError::SnapshotMismatch)
+    ));
+    assert!(!other.shift());
+
+    let text = ron::to_string(&snapshot)
+        .unwrap()
+        .replace("version:1", "version:2");
+    let future: StateSnapshot = ron::from_str(&text).unwrap();
+    assert!(matches!(
+        wkb.restore(&future),
+        Err(WkbError::UnsupportedSnapshot(2))
+    ));
+}
+
+#[test]
+fn rejects_other_modifier_kinds() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us", "", None).unwrap());
+    let mut sticky = WKB::new(keymap.clone());
+    AccessX::new(
+        &mut sticky,
+        AccessXConfig {
+            sticky_keys: Some(StickyKeys::default()),
+            ..Default::default()
+        },
+    );
+    sticky.press_key(LEFT_SHIFT);
+    sticky.release_key(LEFT_SHIFT);
+
+    // Shift latches under StickyKeys but is a plain modifier here.
+    let mut plain = WKB::new(keymap);
+    assert!(matches!(
+        plain.restore(&sticky.snapshot()),
+        Err(WkbError::SnapshotMismatch)
+    ));
+    assert_eq!(plain.raw_modifiers().latched, 0);
+    assert!(plain.restore(&plain.snapshot()).is_ok());
+}
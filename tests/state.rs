@@ -0,0 +1,60 @@
+//! Tests for sharing one compiled `Keymap` between several `WKB` states.
+
+use std::sync::Arc;
+
+use wkb::{KeyDirection, Keymap, ModType, LEFT_SHIFT, WKB};
+
+mod common;
+use common::{update_both, xkb_new_from_names};
+
+const KEY_A: u32 = 30;
+
+#[test]
+fn states_share_one_keymap() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us", "", None).unwrap());
+    let first = WKB::new(keymap.clone());
+    let second = WKB::new(keymap.clone());
+    let cloned = first.clone();
+
+    assert!(Arc::ptr_eq(first.keymap(), &keymap));
+    assert!(Arc::ptr_eq(second.keymap(), &keymap));
+    assert!(Arc::ptr_eq(cloned.keymap(), &keymap));
+    assert_eq!(Arc::strong_count(&keymap), 4);
+}
+
+#[test]
+fn states_track_modifiers_independently() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us", "", None).unwrap());
+    let mut shifted = WKB::new(keymap.clone());
+    let plain = WKB::new(keymap);
+
+    shifted.press_key(LEFT_SHIFT);
+    assert!(shifted.active_mod_type(ModType::Level2));
+    assert!(!plain.active_mod_type(ModType::Level2));
+    assert_eq!(shifted.key_char(KEY_A), Some('A'));
+    assert_eq!(plain.key_char(KEY_A), Some('a'));
+}
+
+#[test]
+fn state_from_keymap_matches_xkbcommon() {
+    let keymap = Arc::new(Keymap::new_from_names("", "", "us", "", None).unwrap());
+    let mut wkb = WKB::new(keymap);
+    let mut xkb = xkb_new_from_names("us", "");
+
+    update_both(&mut wkb, &mut xkb, LEFT_SHIFT, KeyDirection::Down);
+    for code in 2..=13 {
+        let xkb_char = xkb
+            .key_get_utf8(xkbcommon::xkb::Keycode::new(code + 8))
+            .chars()
+            .last();
+        assert_eq!(wkb.key_char(code), xkb_char, "key {code}");
+    }
+}
+
+#[test]
+fn keymap_serializes_without_state() {
+    let keymap = Keymap::new_from_names("", "", "us", "", None).unwrap();
+    let wkb = WKB::new(Arc::new(keymap.clone()));
+    assert_eq!(wkb.as_xkb_string(), Some(keymap.as_xkb_string()));
+    assert_eq!(keymap.num_layouts(), wkb.num_layouts());
+}
@@ -0,0 +1,142 @@
+//! Tests for planning the key events that type a string.
+
+use wkb::{ComposeTable, KeyDirection, TypingEvent, TypingPlan, LEFT_SHIFT, WKB};
+
+const COMPOSE_KEY: u32 = 119;
+const H: u32 = 35;
+const I: u32 = 23;
+const N: u32 = 49;
+const G: u32 = 34;
+const A: u32 = 30;
+const F: u32 = 33;
+
+fn down(evdev_code: u32) -> TypingEvent {
+    TypingEvent::Key {
+        evdev_code,
+        direction: KeyDirection::Down,
+    }
+}
+
+fn up(evdev_code: u32) -> TypingEvent {
+    TypingEvent::Key {
+        evdev_code,
+        direction: KeyDirection::Up,
+    }
+}
+
+/// Replay `plan` on `wkb` and collect the committed text.
+fn replay(wkb: &mut WKB, plan: &TypingPlan) -> String {
+    let mut text = String::new();
+    for event in &plan.events {
+        match *event {
+            TypingEvent::Key {
+                evdev_code,
+                direction,
+            } => {
+                if let Some(commit) = wkb.key_event(evdev_code, direction).commit_text() {
+                    text.push_str(&commit);
+                }
+            }
+            TypingEvent::SetLayout(idx) => wkb.set_layout(idx).unwrap(),
+        }
+    }
+    text
+}
+
+#[test]
+fn shifted_text() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let plan = wkb.type_text("Hi");
+    assert_eq!(
+        plan.events,
+        [
+            down(LEFT_SHIFT),
+            down(H),
+            up(H),
+            up(LEFT_SHIFT),
+            down(I),
+            up(I)
+        ]
+    );
+    assert!(plan.untypable.is_empty());
+    assert_eq!(plan.keymap, None);
+    assert_eq!(replay(&mut wkb, &plan), "Hi");
+}
+
+#[test]
+fn caps_lock_is_taken_into_account() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.press_key(wkb::CAPS_LOCK);
+    wkb.release_key(wkb::CAPS_LOCK);
+    let plan = wkb.type_text("aA");
+    assert_eq!(replay(&mut wkb, &plan), "aA");
+}
+
+#[test]
+fn layout_switches_and_compose() {
+    let mut wkb = WKB::new_from_names("", "", "us,ru", "", None).unwrap();
+    wkb.set_compose_key(COMPOSE_KEY);
+    wkb.set_compose_table(
+        &ComposeTable::from_string("<Multi_key> <n> <g> : \"ŋ\"\n"),
+        None,
+    )
+    .unwrap();
+    let plan = wkb.type_text("aфŋ");
+    assert!(plan.untypable.is_empty());
+    assert_eq!(
+        plan.events,
+        [
+            down(A),
+            up(A),
+            TypingEvent::SetLayout(1),
+            down(A),
+            up(A),
+            TypingEvent::SetLayout(0),
+            down(COMPOSE_KEY),
+            up(COMPOSE_KEY),
+            down(N),
+            up(N),
+            down(G),
+            up(G),
+        ]
+    );
+    assert_eq!(replay(&mut wkb, &plan), "aфŋ");
+    assert_eq!(wkb.active_layout_idx(), 0);
+}
+
+#[test]
+fn untypable_characters_are_reported() {
+    let wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let plan = wkb.type_text("f☃f");
+    assert_eq!(plan.events, [down(F), up(F), down(F), up(F)]);
+    assert_eq!(plan.untypable, [(1, '☃')]);
+    assert_eq!(plan.keymap, None);
+}
+
+#[test]
+fn extra_keys_for_untypable_characters() {
+    let wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let plan = wkb.type_text_with_extra_keys("☃f☃✓");
+    assert!(plan.untypable.is_empty());
+    let keymap = plan.keymap.as_deref().expect("keymap with extra keys");
+    let mut typing = WKB::new_from_string(keymap).unwrap();
+    assert_eq!(replay(&mut typing, &plan), "☃f☃✓");
+}
+
+#[test]
+fn characters_without_keysym_leave_extra_keys_unused() {
+    let wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    let plan = wkb.type_text_with_extra_keys("☃\0✓");
+    assert_eq!(plan.untypable, [(3, '\0')]);
+    let without = wkb.type_text_with_extra_keys("☃✓");
+    assert_eq!(plan.events, without.events);
+    assert_eq!(plan.keymap, without.keymap);
+}
+
+#[test]
+fn level3_text() {
+    let mut wkb = WKB::new_from_names("", "", "de", "", None).unwrap();
+    let plan = wkb.type_text("@€ö");
+    assert!(plan.untypable.is_empty());
+    assert_eq!(replay(&mut wkb, &plan), "@€ö");
+}
@@ -0,0 +1,76 @@
+//! Tests for lookups under a caller-supplied modifier mask and layout.
+
+use test_case::test_matrix;
+use wkb::{LogicalKey, NamedKey, LEFT_SHIFT, WKB};
+use xkbcommon::xkb::Keycode;
+
+mod common;
+use common::xkb_new_from_names;
+
+const MOD_SHIFT: u32 = 1 << 0;
+const MOD_CAPS: u32 = 1 << 1;
+const MOD_CTRL: u32 = 1 << 2;
+const MOD_NUM: u32 = 1 << 4;
+const MOD_ALTGR: u32 = 1 << 7;
+const LEFT_CTRL: u32 = 29;
+const KEY_A: u32 = 30;
+const KEY_ESC: u32 = 1;
+const KEY_KP7: u32 = 71;
+
+#[test_matrix(
+    ["us", "de", "fr", "ru"],
+    [0, MOD_SHIFT, MOD_CAPS, MOD_NUM, MOD_ALTGR, MOD_SHIFT | MOD_ALTGR, MOD_CAPS | MOD_NUM]
+)]
+fn char_with_modifiers_matches_xkbcommon(locale: &str, mods: u32) {
+    let wkb = WKB::new_from_names("", "", locale, "", None).unwrap();
+    let mut xkb = xkb_new_from_names(locale, "");
+    let locks = mods & (MOD_CAPS | MOD_NUM);
+    xkb.update_mask(mods & !locks, 0, locks, 0, 0, 0);
+    for code in 1..128 {
+        let expected = xkb.key_get_utf8(Keycode::new(code + 8)).chars().last();
+        if expected.is_none_or(char::is_control) {
+            continue;
+        }
+        assert_eq!(
+            wkb.char_with_modifiers(code, 0, mods),
+            expected,
+            "{locale} mods={mods:#x} key {code}"
+        );
+    }
+}
+
+#[test]
+fn key_without_modifiers_ignores_state() {
+    let mut wkb = WKB::new_from_names("", "", "us", "", None).unwrap();
+    wkb.press_key(LEFT_CTRL);
+    wkb.press_key(LEFT_SHIFT);
+    assert_eq!(wkb.logical_key(KEY_A), LogicalKey::Character('A'));
+    assert_eq!(wkb.key_without_modifiers(KEY_A), LogicalKey::Character('a'));
+    assert_eq!(
+        wkb.key_without_modifiers(KEY_ESC),
+        LogicalKey::Named(NamedKey::Escape)
+    );
+
+    let raw = wkb.raw_modifiers();
+    let without_ctrl = (raw.depressed | raw.latched | raw.locked) & !MOD_CTRL;
+    assert_eq!(
+        wkb.key_with_modifiers(KEY_A, 0, without_ctrl),
+        LogicalKey::Character('A')
+    );
+}
+
+#[test]
+fn key_with_modifiers_selects_layout_and_locks() {
+    let wkb = WKB::new_from_names("", "", "us,ru", "", None).unwrap();
+    assert_eq!(wkb.char_with_modifiers(KEY_A, 1, 0), Some('ф'));
+    assert_eq!(wkb.char_with_modifiers(KEY_A, 1, MOD_CAPS), Some('Ф'));
+    assert_eq!(wkb.char_with_modifiers(KEY_KP7, 0, MOD_NUM), Some('7'));
+    assert_eq!(
+        wkb.key_with_modifiers(KEY_KP7, 0, 0),
+        LogicalKey::Named(NamedKey::Home)
+    );
+    assert_eq!(
+        wkb.key_with_modifiers(KEY_A, 2, 0),
+        LogicalKey::Unidentified
+    );
+}